@@ -21,14 +21,17 @@ impl<'a, Tz: TimeZone> CronEvaluator<'a, Tz> {
         box days,
         box day_of_weeks,
       } => {
-        let last_day = get_days_from_month(self.instant.date().year(), self.instant.date().month());
+        let last_day = get_days_from_month(self.instant.date_naive().year(), self.instant.date_naive().month());
         let fmins = self.visit(&CronEnvironment::new(self.instant.time().minute() as u8, 59), mins);
         let fhours = self.visit(&CronEnvironment::new(self.instant.time().hour() as u8, 23), hours);
         let fdays = self.visit(
-          &CronEnvironment::new(self.instant.date().day() as u8, last_day as u8),
+          &CronEnvironment::new(self.instant.date_naive().day() as u8, last_day as u8),
           days,
         );
-        let fmonths = self.visit(&CronEnvironment::new(self.instant.date().month() as u8, 12), months);
+        let fmonths = self.visit(
+          &CronEnvironment::new(self.instant.date_naive().month() as u8, 12),
+          months,
+        );
         let fday_of_weeks = self.visit(
           &CronEnvironment::new(self.instant.time().minute() as u8, 7),
           day_of_weeks,
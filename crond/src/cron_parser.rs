@@ -3,65 +3,60 @@ use crate::cron_expr::CronExpr::*;
 use oni_comb_parser_rs::prelude::*;
 
 fn min_digit<'a>() -> Parser<'a, char, CronExpr> {
-  ((elm_in('1', '5') + elm_digit())
+  (elm_in('1', '5') + elm_digit())
     .map(|(e1, e2)| ValueExpr((e1 as u8 - 48) * 10 + e2 as u8 - 48))
     .attempt()
     | (elm('0') * elm_digit()).map(|e| ValueExpr(e as u8 - 48)).attempt()
-    | (elm_digit()).map(|e| ValueExpr(e as u8 - 48)))
-  .cache()
+    | (elm_digit()).map(|e| ValueExpr(e as u8 - 48))
 }
 
 fn hour_digit<'a>() -> Parser<'a, char, CronExpr> {
-  ((elm('2') + elm_in('0', '3'))
+  (elm('2') + elm_in('0', '3'))
     .map(|(e1, e2)| ValueExpr((e1 as u8 - 48) * 10 + e2 as u8 - 48))
     .attempt()
     | (elm('1') + elm_digit())
       .map(|(e1, e2)| ValueExpr((e1 as u8 - 48) * 10 + e2 as u8 - 48))
       .attempt()
     | (elm('0') * elm_digit()).map(|e| ValueExpr(e as u8 - 48)).attempt()
-    | elm_digit().map(|e| ValueExpr(e as u8 - 48)).debug("hour_digit_4"))
-  .cache()
+    | elm_digit().map(|e| ValueExpr(e as u8 - 48)).debug("hour_digit_4")
 }
 
 fn day_digit<'a>() -> Parser<'a, char, CronExpr> {
-  ((elm('3') + elm_of("01"))
+  (elm('3') + elm_of("01"))
     .map(|(e1, e2)| ValueExpr((e1 as u8 - 48) * 10 + e2 as u8 - 48))
     .attempt()
     | (elm_of("12") + elm_digit())
       .map(|(e1, e2)| ValueExpr((e1 as u8 - 48) * 10 + e2 as u8 - 48))
       .attempt()
     | (elm('0') * elm_digit_1_9()).map(|e| ValueExpr(e as u8 - 48)).attempt()
-    | elm_digit_1_9().map(|e| ValueExpr(e as u8 - 48)))
-  .cache()
+    | elm_digit_1_9().map(|e| ValueExpr(e as u8 - 48))
 }
 
 fn month_digit<'a>() -> Parser<'a, char, CronExpr> {
-  ((elm('1') + elm_of("012"))
+  (elm('1') + elm_of("012"))
     .map(|(e1, e2)| ValueExpr((e1 as u8 - 48) * 10 + e2 as u8 - 48))
     .attempt()
     | (elm('0') * elm_digit_1_9()).map(|e| ValueExpr(e as u8 - 48)).attempt()
-    | elm_digit_1_9().map(|e| ValueExpr(e as u8 - 48)))
-  .cache()
+    | elm_digit_1_9().map(|e| ValueExpr(e as u8 - 48))
 }
 
 fn day_of_week_digit<'a>() -> Parser<'a, char, CronExpr> {
-  (tag("SUN").map(|_| ValueExpr(1)).attempt()
+  tag("SUN").map(|_| ValueExpr(1)).attempt()
     | tag("MON").map(|_| ValueExpr(2)).attempt()
     | tag("TUE").map(|_| ValueExpr(3)).attempt()
     | tag("WED").map(|_| ValueExpr(4)).attempt()
     | tag("THU").map(|_| ValueExpr(5)).attempt()
     | tag("FRI").map(|_| ValueExpr(6)).attempt()
     | tag("SAT").map(|_| ValueExpr(7)).attempt()
-    | elm('L').map(|_| LastValueExpr))
-  .cache()
+    | elm('L').map(|_| LastValueExpr)
 }
 
 fn day_of_week_text<'a>() -> Parser<'a, char, CronExpr> {
-  elm_in('1', '7').map(|e| ValueExpr(e as u8 - 48)).cache()
+  elm_in('1', '7').map(|e| ValueExpr(e as u8 - 48))
 }
 
 fn asterisk<'a>() -> Parser<'a, char, CronExpr> {
-  elm('*').map(|_| AnyValueExpr).cache()
+  elm('*').map(|_| AnyValueExpr)
 }
 
 fn per(p: Parser<char, CronExpr>) -> Parser<char, CronExpr> {
@@ -75,7 +70,6 @@ fn asterisk_per(p: Parser<char, CronExpr>) -> Parser<char, CronExpr> {
       option: Box::from(op.clone()),
     })
     .attempt()
-    .cache()
 }
 
 fn range_per(p: Parser<char, CronExpr>) -> Parser<char, CronExpr> {
@@ -88,7 +82,7 @@ fn range_per(p: Parser<char, CronExpr>) -> Parser<char, CronExpr> {
 fn list(p: Parser<char, CronExpr>) -> Parser<char, CronExpr> {
   p.of_many1_sep(elm(','))
     .map(|e| match e {
-      e if e.len() == 1 => e.get(0).unwrap().clone(),
+      e if e.len() == 1 => e.first().unwrap().clone(),
       e => ListExpr(e),
     })
     .attempt()
@@ -128,12 +122,18 @@ pub(crate) fn instruction<'a>() -> Parser<'a, char, CronExpr> {
   })
 }
 
+// The rules are not cached: cache() returns a bound parser, which would make the grammar rebuild its parsers for
+// every input, and the rules are too short for the memoization to pay off.
+thread_local! {
+  static CRON: Grammar<char, CronExpr> = Grammar::new(|_| instruction() - end());
+}
+
 pub struct CronParser;
 
 impl CronParser {
-  pub fn parse<'a>(input: &str) -> Result<CronExpr, String> {
+  pub fn parse(input: &str) -> Result<CronExpr, String> {
     let input = input.chars().collect::<Vec<_>>();
-    let x = (instruction() - end()).parse(&input).to_result();
+    let x = CRON.with(|grammar| grammar.parse(&input).to_result());
     x.map_err(|e| e.to_string())
   }
 }
@@ -154,6 +154,11 @@ mod tests {
     let _ = env_logger::builder().is_test(true).try_init();
   }
 
+  #[test]
+  fn test_grammar_is_built_once() {
+    assert!(CRON.with(Grammar::is_built));
+  }
+
   #[test]
   fn test_instruction_1() {
     let result = CronParser::parse("* * * * *").unwrap();
@@ -252,7 +257,7 @@ mod tests {
     let s = (0..=59).map(|v| v.to_string()).collect::<Vec<_>>().join(",");
     let input = s.chars().collect::<Vec<_>>();
     let result = (list(min_digit()) - end()).parse(&input).to_result().unwrap();
-    let values = (0..=59).map(|v| ValueExpr(v)).collect::<Vec<_>>();
+    let values = (0..=59).map(ValueExpr).collect::<Vec<_>>();
     assert_eq!(result, ListExpr(values));
   }
 
@@ -21,7 +21,7 @@ mod cron_schedule;
 mod cron_specification;
 
 fn get_days_from_month(year: i32, month: u32) -> i64 {
-  NaiveDate::from_ymd(
+  NaiveDate::from_ymd_opt(
     match month {
       12 => year + 1,
       _ => year,
@@ -32,6 +32,7 @@ fn get_days_from_month(year: i32, month: u32) -> i64 {
     },
     1,
   )
-  .signed_duration_since(NaiveDate::from_ymd(year, month, 1))
+  .unwrap()
+  .signed_duration_since(NaiveDate::from_ymd_opt(year, month, 1).unwrap())
   .num_days()
 }
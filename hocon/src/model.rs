@@ -2,7 +2,7 @@ use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::io::Read;
 
-use oni_comb_parser_rs::prelude::ParserRunner;
+use oni_comb_parser_rs::prelude::{Grammar, ParserRunner};
 
 use crate::parsers::hocon;
 
@@ -57,10 +57,20 @@ impl FileReader for DefaultFileReader {
   }
 }
 
+thread_local! {
+  static HOCON: Grammar<u8, Vec<ConfigValue>> = Grammar::new(|_| hocon());
+}
+
 pub struct ConfigFactory {
   file_reader: Box<dyn FileReader>,
 }
 
+impl Default for ConfigFactory {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl ConfigFactory {
   pub fn new() -> Self {
     Self {
@@ -75,26 +85,21 @@ impl ConfigFactory {
   }
 
   pub fn parse_from_string(&self, text: &str) -> Result<Config, ConfigError> {
-    hocon()
-      .parse(text.as_bytes())
-      .to_result()
+    HOCON
+      .with(|grammar| grammar.parse(text.as_bytes()).to_result())
       .map(|config_values| Self::resolve_stage0(&config_values))
       .map(|config_value| Self::resolve_stage1(&config_value))
-      .map(|config_value| Config::new(config_value))
-      .map_err(|pe| {
-        let msg = pe.to_string();
-        let input_string = pe.input_string();
-        ConfigError::ParseError(format!("{}: {:?}", msg, input_string))
-      })
+      .map(Config::new)
+      .map_err(|pe| ConfigError::ParseError(pe.render(text)))
   }
 
   fn resolve_stage1(config_value: &ConfigValue) -> ConfigValue {
     let mut c = config_value.clone();
-    c.resolve(Some(&config_value));
+    c.resolve(Some(config_value));
     c
   }
 
-  fn resolve_stage0(config_values: &Vec<ConfigValue>) -> ConfigValue {
+  fn resolve_stage0(config_values: &[ConfigValue]) -> ConfigValue {
     let mut cur = config_values[0].clone();
     cur.resolve(None);
     for cv in &config_values[1..] {
@@ -113,7 +118,7 @@ pub struct Config {
 
 impl Display for Config {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{}", self.config_value.to_string())
+    write!(f, "{}", self.config_value)
   }
 }
 
@@ -148,6 +153,11 @@ mod tests {
     let _ = env_logger::builder().is_test(true).try_init();
   }
 
+  #[test]
+  fn test_grammar_is_built_once() {
+    assert!(HOCON.with(Grammar::is_built));
+  }
+
   #[test]
   fn test_simple() {
     let input = r#"
@@ -309,4 +319,22 @@ mod tests {
     let a_value = config.get_value("foo.test.a");
     assert_eq!(a_value, Some(&ConfigValue::String("aaaa".to_string())));
   }
+
+  #[test]
+  fn test_parse_error() {
+    let input = "foo {\n  bar ! 1\n}\n";
+    let result = ConfigFactory::new().parse_from_string(input);
+    match result {
+      Err(ConfigError::ParseError(msg)) => assert_eq!(
+        msg,
+        "error: expected one of: `one of: \" \\t\\r\\n\"`, `seq [47, 47]`, `one of: \"=:\"`
+ --> line 2, column 7
+  |
+2 |   bar ! 1
+  |       ^
+"
+      ),
+      _ => panic!("unexpected result: {:?}", result),
+    }
+  }
 }
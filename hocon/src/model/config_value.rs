@@ -42,7 +42,7 @@ impl Display for ConfigValue {
     let s = match self {
       ConfigValue::Null => "null".to_string(),
       ConfigValue::Bool(value) => value.to_string(),
-      ConfigValue::String(value) => format!("\"{}\"", value.to_string()),
+      ConfigValue::String(value) => format!("\"{}\"", value),
       ConfigValue::Number(cnv) => cnv.to_string(),
       ConfigValue::Duration(cdv) => cdv.to_string(),
       ConfigValue::Array(cav) => cav.to_string(),
@@ -50,7 +50,7 @@ impl Display for ConfigValue {
       ConfigValue::Enumeration { values, .. } => values.to_string(),
       ConfigValue::Reference { path, missing, .. } => format!("${{{}{}}}", if *missing { "?" } else { "" }, path),
       ConfigValue::Include(civ) => civ.to_string(),
-      ConfigValue::Link(cvl) => (&*cvl).to_string(),
+      ConfigValue::Link(cvl) => cvl.to_string(),
     };
     write!(f, "{}", s)
   }
@@ -58,13 +58,10 @@ impl Display for ConfigValue {
 
 impl Monoid for ConfigValue {
   fn combine(&mut self, other: &Self) {
-    match other {
-      cv @ ConfigValue::Link(..) => {
-        for child_cv in cv.to_vec() {
-          self.push(child_cv);
-        }
+    if let cv @ ConfigValue::Link(..) = other {
+      for child_cv in cv.to_vec() {
+        self.push(child_cv);
       }
-      _ => {}
     }
   }
 }
@@ -165,19 +162,16 @@ impl ConfigResolver for ConfigValue {
           *cvo = ConfigValue::Object(ConfigObjectValue::new(key_values));
         }
       }
-      (cvr @ ConfigValue::Enumeration { .. }, Some(_)) => match cvr {
-        ConfigValue::Enumeration { prev, values, .. } => {
+      (cvr @ ConfigValue::Enumeration { .. }, Some(_)) => {
+        if let ConfigValue::Enumeration { prev, values, .. } = cvr {
           let mut result = String::new();
           for cv in &mut values.0 {
             println!("cv = {:?}", cv);
             cv.resolve(source);
             println!("cv = {:?}", cv);
-            match cv {
-              ConfigValue::String(s) => {
-                println!("s = {}", s);
-                result.push_str(s);
-              }
-              _ => {}
+            if let ConfigValue::String(s) = cv {
+              println!("s = {}", s);
+              result.push_str(s);
             }
           }
           if result.is_empty() {
@@ -186,22 +180,18 @@ impl ConfigResolver for ConfigValue {
             *cvr = ConfigValue::String(result);
           }
         }
-        _ => {}
-      },
+      }
       (cvr @ ConfigValue::Reference { .. }, Some(src)) => {
         let mut ref_value = src
           .get_value(cvr.ref_name().unwrap())
           .cloned()
-          .or_else(|| env::var(cvr.ref_name().unwrap()).ok().map(|s| ConfigValue::String(s)));
-        match ref_value.as_mut() {
-          Some(ref_value_mut) => {
-            ref_value_mut.resolve(source);
-          }
-          _ => {}
+          .or_else(|| env::var(cvr.ref_name().unwrap()).ok().map(ConfigValue::String));
+        if let Some(ref_value_mut) = ref_value.as_mut() {
+          ref_value_mut.resolve(source);
         }
         if cvr.ref_missing().unwrap() {
-          if ref_value.is_some() {
-            *cvr = ref_value.unwrap();
+          if let Some(ref_value) = ref_value {
+            *cvr = ref_value;
           } else {
             *cvr = cvr.ref_prev().clone().unwrap().prev_latest().clone();
           }
@@ -223,10 +213,7 @@ impl ConfigValue {
   }
 
   pub fn is_null(&self) -> bool {
-    match self {
-      ConfigValue::Null => true,
-      _ => false,
-    }
+    matches!(self, ConfigValue::Null)
   }
 
   pub fn as_bool(&self) -> Option<bool> {
@@ -299,7 +286,7 @@ impl ConfigValue {
     match self {
       ConfigValue::Object(cov) => match cov.0.get(key) {
         Some(cv) if child_count > 0 => {
-          let next_path = &path[(key.len() + 1) as usize..];
+          let next_path = &path[(key.len() + 1)..];
           cv.latest().has_path(next_path)
         }
         Some(..) => true,
@@ -318,7 +305,7 @@ impl ConfigValue {
     match self {
       ConfigValue::Object(cov) => match cov.0.get(key) {
         Some(cv) if child_count > 0 => {
-          let next_path = &path[(key.len() + 1) as usize..];
+          let next_path = &path[(key.len() + 1)..];
           cv.latest().get_value(next_path)
         }
         Some(cv) => Some(cv.latest()),
@@ -339,10 +326,7 @@ impl ConfigValue {
     println!("self = {:?}", self);
     match self {
       ConfigValue::Link(cv) => match &*(cv.prev) {
-        ConfigValue::Link(prev_cv) => {
-          let ret = &(prev_cv.value);
-          ret
-        }
+        ConfigValue::Link(prev_cv) => &prev_cv.value,
         cv => cv,
       },
       cv => cv,
@@ -431,7 +415,7 @@ impl ConfigValue {
 
   fn get_value_link(&self) -> Option<&ConfigValueLink> {
     match self {
-      ConfigValue::Link(cvl) => Some(&*cvl),
+      ConfigValue::Link(cvl) => Some(cvl),
       _ => None,
     }
   }
@@ -10,7 +10,7 @@ pub struct ConfigValueLink {
 
 impl Display for ConfigValueLink {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{}", self.value.to_string())
+    write!(f, "{}", self.value)
   }
 }
 
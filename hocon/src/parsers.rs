@@ -5,14 +5,14 @@ use crate::model::*;
 
 use oni_comb_parser_rs::prelude::*;
 
-fn comment<'a>() -> Parser<'a, u8, &'a [u8]> {
-  let head = seq(b"//").collect().attempt() | elm_ref(b'#').collect();
-  let tail = take_till0(|c| matches!(*c, b'\r' | b'\n'));
-  (space() + (head + tail).of_many1()).collect()
+fn comment<'a>() -> Parser<'a, u8, Vec<u8>> {
+  let head = seq(b"//").attempt().discard() | elm(b'#').discard();
+  let tail = none_of(b"\r\n").of_many0();
+  (space() + (head + tail).of_many1()).collect_vec()
 }
 
-fn space<'a>() -> Parser<'a, u8, &'a [u8]> {
-  elm_ref_of(b" \t\r\n").of_many0().collect()
+fn space<'a>() -> Parser<'a, u8, Vec<u8>> {
+  elm_of(b" \t\r\n").of_many0()
 }
 
 fn space_or_comment<'a>() -> Parser<'a, u8, ()> {
@@ -21,7 +21,6 @@ fn space_or_comment<'a>() -> Parser<'a, u8, ()> {
 
 fn include_method<'a>() -> Parser<'a, u8, String> {
   (seq(b"file") | seq(b"url"))
-    .collect()
     .map_res(std::str::from_utf8)
     .map(String::from)
 }
@@ -31,14 +30,16 @@ fn include_config_value<'a>() -> Parser<'a, u8, ConfigValue> {
     .map(|(method, path)| ConfigValue::Include(ConfigIncludeValue::new(method, format!("\"{}\"", path))))
 }
 
-fn number<'a>() -> Parser<'a, u8, (Option<&'a u8>, String, Option<String>, Option<String>)> {
-  let integer = (elm_digit_1_9_ref() + elm_digit_ref().of_many0()).collect() | elm_ref(b'0').collect();
-  let frac = elm_ref(b'.') + elm_digit_ref().of_many1();
-  let exp = elm_of(b"eE") + elm_of(b"+-").opt() + elm_digit_ref().of_many1();
-  let number = elm_ref(b'-').opt()
-    + integer.map_res(std::str::from_utf8).map(String::from)
-    + frac.collect().map_res(std::str::from_utf8).map(String::from).opt()
-    + exp.collect().map_res(std::str::from_utf8).map(String::from).opt();
+type Number = (Option<u8>, String, Option<String>, Option<String>);
+
+fn number<'a>() -> Parser<'a, u8, Number> {
+  let integer = (elm_digit_1_9() + elm_digit().of_many0()).collect_vec() | elm(b'0').collect_vec();
+  let frac = elm(b'.') + elm_digit().of_many1();
+  let exp = elm_of(b"eE") + elm_of(b"+-").opt() + elm_digit().of_many1();
+  let number = elm(b'-').opt()
+    + integer.map_res(String::from_utf8)
+    + frac.collect_vec().map_res(String::from_utf8).opt()
+    + exp.collect_vec().map_res(String::from_utf8).opt();
   number
     .surround(space_or_comment(), space_or_comment())
     .map(|(((s, i), f), e)| (s, i, f, e))
@@ -94,27 +95,26 @@ fn number_value<'a>() -> Parser<'a, u8, ConfigNumberValue> {
   })
 }
 
-fn string_double_quote_bracket<'a>() -> Parser<'a, u8, &'a u8> {
-  elm_ref(b'"').surround(space_or_comment(), space_or_comment())
+fn string_double_quote_bracket<'a>() -> Parser<'a, u8, u8> {
+  elm(b'"').surround(space_or_comment(), space_or_comment())
 }
 
-fn string_single_quote_bracket<'a>() -> Parser<'a, u8, &'a u8> {
-  elm_ref(b'\'').surround(space_or_comment(), space_or_comment())
+fn string_single_quote_bracket<'a>() -> Parser<'a, u8, u8> {
+  elm(b'\'').surround(space_or_comment(), space_or_comment())
 }
 
 fn string<'a>() -> Parser<'a, u8, String> {
-  let special_char = elm_ref(b'\\')
-    | elm_ref(b'/')
-    | elm_ref(b'"')
-    | elm_ref(b'b').map(|_| &b'\x08')
-    | elm_ref(b'f').map(|_| &b'\x0C')
-    | elm_ref(b'n').map(|_| &b'\n')
-    | elm_ref(b'r').map(|_| &b'\r')
-    | elm_ref(b't').map(|_| &b'\t');
-  let escape_sequence = elm_ref(b'\\') * special_char;
-  // let char_string = (none_ref_of(b"\r\n\\\"'") | escape_sequence)
-  let char_string = (none_ref_of(b"[]\r\n\\\"'") | escape_sequence)
-    .map(Clone::clone)
+  let special_char = elm(b'\\')
+    | elm(b'/')
+    | elm(b'"')
+    | elm(b'b').map(|_| b'\x08')
+    | elm(b'f').map(|_| b'\x0C')
+    | elm(b'n').map(|_| b'\n')
+    | elm(b'r').map(|_| b'\r')
+    | elm(b't').map(|_| b'\t');
+  let escape_sequence = elm(b'\\') * special_char;
+  // let char_string = (none_of(b"\r\n\\\"'") | escape_sequence)
+  let char_string = (none_of(b"[]\r\n\\\"'") | escape_sequence)
     .of_many1()
     .map_res(String::from_utf8);
   let utf16_char = seq(b"\\u")
@@ -133,88 +133,56 @@ fn string<'a>() -> Parser<'a, u8, String> {
   (string_surround1.attempt() | string_surround2.attempt() | string_surround()).map(|strings| strings.concat())
 }
 
-fn string_zero<'a>() -> Parser<'a, u8, String> {
-  let special_char = elm_ref(b'\\')
-    | elm_ref(b'/')
-    | elm_ref(b'"')
-    | elm_ref(b'b').map(|_| &b'\x08')
-    | elm_ref(b'f').map(|_| &b'\x0C')
-    | elm_ref(b'n').map(|_| &b'\n')
-    | elm_ref(b'r').map(|_| &b'\r')
-    | elm_ref(b't').map(|_| &b'\t');
-  let escape_sequence = elm_ref(b'\\') * special_char;
-  // let char_string = (none_ref_of(b"\r\n\\\"'") | escape_sequence)
-  let char_string = (none_ref_of(b"[]\r\n\\\"'") | escape_sequence)
-    .map(Clone::clone)
-    .of_many1()
-    .map_res(String::from_utf8);
-  let utf16_char = seq(b"\\u")
-    * elm_hex_digit()
-      .of_count(4)
-      .map_res(String::from_utf8)
-      .map_res(|digits| u16::from_str_radix(&digits, 16));
-  let utf16_string = utf16_char.of_many1().map(|chars| {
-    decode_utf16(chars)
-      .map(|r| r.unwrap_or(REPLACEMENT_CHARACTER))
-      .collect::<String>()
-  });
-  let string_surround = || (char_string.clone().attempt() | utf16_string.clone()).of_many0();
-  let string_surround1 = string_surround().surround(string_double_quote_bracket(), string_double_quote_bracket());
-  let string_surround2 = string_surround().surround(string_single_quote_bracket(), string_single_quote_bracket());
-  (string_surround1.attempt() | string_surround2.attempt() | string_surround()).map(|strings| strings.concat())
-}
-
 fn string_config_value<'a>() -> Parser<'a, u8, ConfigValue> {
   string().map(ConfigValue::String)
 }
 
-fn string_config_value2<'a>() -> Parser<'a, u8, ConfigValue> {
-  string().map(ConfigValue::String)
-}
-
-fn array_left_bracket<'a>() -> Parser<'a, u8, &'a u8> {
-  elm_ref(b'[').surround(space_or_comment(), space_or_comment())
+fn array_left_bracket<'a>() -> Parser<'a, u8, u8> {
+  elm(b'[').surround(space_or_comment(), space_or_comment())
 }
 
-fn array_right_bracket<'a>() -> Parser<'a, u8, &'a u8> {
-  elm_ref(b']').surround(space_or_comment(), space_or_comment())
+fn array_right_bracket<'a>() -> Parser<'a, u8, u8> {
+  elm(b']').surround(space_or_comment(), space_or_comment())
 }
 
-fn array<'a>() -> Parser<'a, u8, Vec<ConfigValue>> {
-  let elems = lazy(config_value).of_many0_sep(comma());
+fn array<'a>(config_value: Parser<'a, u8, ConfigValue>) -> Parser<'a, u8, Vec<ConfigValue>> {
+  let elems = config_value.of_many0_sep(comma());
   elems.surround(array_left_bracket(), array_right_bracket())
 }
 
 fn kv<'a>() -> Parser<'a, u8, ()> {
-  elm_ref_of(b"=:").discard()
+  elm_of(b"=:").discard()
 }
 
 pub fn key<'a>() -> Parser<'a, u8, String> {
   (path().attempt() | string()).surround(space_or_comment(), space_or_comment())
 }
 
-fn property<'a>() -> Parser<'a, u8, (String, ConfigValue)> {
-  key() + ((kv() * lazy(config_value)).attempt() | object_config_value().attempt() | array_config_value())
+fn property<'a>(
+  config_value: Parser<'a, u8, ConfigValue>,
+  object_config_value: Parser<'a, u8, ConfigValue>,
+) -> Parser<'a, u8, (String, ConfigValue)> {
+  key() + ((kv() * config_value.clone()).attempt() | object_config_value.attempt() | array_config_value(config_value))
 }
 
-fn property_config_value<'a>() -> Parser<'a, u8, (String, ConfigValue)> {
-  property().map(|(k, v)| (k, v))
+fn property_config_value<'a>(config_value: Parser<'a, u8, ConfigValue>) -> Parser<'a, u8, (String, ConfigValue)> {
+  property(config_value.clone(), object_config_value(config_value)).map(|(k, v)| (k, v))
 }
 
-fn object_left_bracket<'a>() -> Parser<'a, u8, &'a u8> {
-  elm_ref(b'{').surround(space_or_comment(), space_or_comment())
+fn object_left_bracket<'a>() -> Parser<'a, u8, u8> {
+  elm(b'{').surround(space_or_comment(), space_or_comment())
 }
 
-fn object_right_bracket<'a>() -> Parser<'a, u8, &'a u8> {
-  elm_ref(b'}').surround(space_or_comment(), space_or_comment())
+fn object_right_bracket<'a>() -> Parser<'a, u8, u8> {
+  elm(b'}').surround(space_or_comment(), space_or_comment())
 }
 
-fn comma<'a>() -> Parser<'a, u8, &'a u8> {
-  elm_ref(b',').surround(space_or_comment(), space_or_comment())
+fn comma<'a>() -> Parser<'a, u8, u8> {
+  elm(b',').surround(space_or_comment(), space_or_comment())
 }
 
-fn object<'a>() -> Parser<'a, u8, HashMap<String, ConfigValue>> {
-  let properties: Parser<'a, u8, Vec<(String, ConfigValue)>> = lazy(property).of_many0_sep(comma().opt());
+fn object<'a>(property: Parser<'a, u8, (String, ConfigValue)>) -> Parser<'a, u8, HashMap<String, ConfigValue>> {
+  let properties: Parser<'a, u8, Vec<(String, ConfigValue)>> = property.of_many0_sep(comma().opt());
   let obj: Parser<'a, u8, Vec<(String, ConfigValue)>> =
     properties.surround(object_left_bracket(), object_right_bracket());
   obj.map(|properties| {
@@ -231,20 +199,19 @@ fn object<'a>() -> Parser<'a, u8, HashMap<String, ConfigValue>> {
   })
 }
 
-fn path_element<'a>() -> Parser<'a, u8, &'a [u8]> {
-  (elm_alpha() | elm_of(b"-_")).of_many1().collect()
+fn path_element<'a>() -> Parser<'a, u8, Vec<u8>> {
+  (elm_alpha() | elm_of(b"-_")).of_many1()
 }
 
 fn path<'a>() -> Parser<'a, u8, String> {
   path_element()
     .of_many1_sep(elm(b'.'))
-    .collect()
-    .map_res(std::str::from_utf8)
-    .map(String::from)
+    .collect_vec()
+    .map_res(String::from_utf8)
 }
 
 fn reference_left_bracket<'a>() -> Parser<'a, u8, &'a [u8]> {
-  seq(b"${").collect().surround(space_or_comment(), space_or_comment())
+  seq(b"${").surround(space_or_comment(), space_or_comment())
 }
 
 fn reference_right_bracket<'a>() -> Parser<'a, u8, &'a [u8]> {
@@ -252,21 +219,22 @@ fn reference_right_bracket<'a>() -> Parser<'a, u8, &'a [u8]> {
 }
 
 fn reference<'a>() -> Parser<'a, u8, (bool, String)> {
-  (elm_ref(b'?').opt().map(|v| v.is_some())
+  (elm(b'?').opt().map(|v| v.is_some())
     + path_element()
       .of_many1_sep(elm(b'.'))
-      .collect()
-      .map_res(std::str::from_utf8)
-      .map(String::from))
+      .collect_vec()
+      .map_res(String::from_utf8))
   .surround(reference_left_bracket(), reference_right_bracket())
 }
 
-fn object_config_value<'a>() -> Parser<'a, u8, ConfigValue> {
-  object().map(|v| ConfigValue::Object(ConfigObjectValue::new(v)))
+fn object_config_value<'a>(config_value: Parser<'a, u8, ConfigValue>) -> Parser<'a, u8, ConfigValue> {
+  recursive(|object_config_value| {
+    object(property(config_value.clone(), object_config_value)).map(|v| ConfigValue::Object(ConfigObjectValue::new(v)))
+  })
 }
 
-fn array_config_value<'a>() -> Parser<'a, u8, ConfigValue> {
-  array().map(|v| ConfigValue::Array(ConfigArrayValue::new(v)))
+fn array_config_value<'a>(config_value: Parser<'a, u8, ConfigValue>) -> Parser<'a, u8, ConfigValue> {
+  array(config_value).map(|v| ConfigValue::Array(ConfigArrayValue::new(v)))
 }
 
 fn reference_config_value<'a>() -> Parser<'a, u8, ConfigValue> {
@@ -297,16 +265,19 @@ fn simple_config_value<'a>() -> Parser<'a, u8, ConfigValue> {
 }
 
 fn config_value<'a>() -> Parser<'a, u8, ConfigValue> {
-  (array_config_value().attempt()
-    | object_config_value().attempt()
-    | reference_config_value().attempt()
-    | simple_config_value().attempt()
-    | enumeration_config_value())
-  .surround(space_or_comment(), space_or_comment())
+  recursive(|config_value| {
+    (array_config_value(config_value.clone()).attempt()
+      | object_config_value(config_value).attempt()
+      | reference_config_value().attempt()
+      | simple_config_value().attempt()
+      | enumeration_config_value())
+    .surround(space_or_comment(), space_or_comment())
+  })
 }
 
 fn config<'a>() -> Parser<'a, u8, Vec<ConfigValue>> {
-  property_config_value()
+  let config_value = config_value();
+  property_config_value(config_value.clone())
     .of_many1()
     .map(|values: Vec<(String, ConfigValue)>| {
       let map = values.into_iter().fold(HashMap::new(), |mut key_values, (k, v)| {
@@ -324,7 +295,7 @@ fn config<'a>() -> Parser<'a, u8, Vec<ConfigValue>> {
     })
     .attempt()
     | include_config_value().of_many1().attempt()
-    | (object_config_value().attempt() | array_config_value()).of_many0()
+    | (object_config_value(config_value.clone()).attempt() | array_config_value(config_value)).of_many0()
 }
 
 pub fn hocon<'a>() -> Parser<'a, u8, Vec<ConfigValue>> {
@@ -335,27 +306,10 @@ pub fn hocon<'a>() -> Parser<'a, u8, Vec<ConfigValue>> {
 mod gens {
   use super::*;
   use prop_check_rs::gen::{Gen, Gens};
-  use std::collections::BTreeMap;
 
   pub fn comment_gen() -> Gen<String> {
-    let array: [(i32, Gen<char>); 2] = [(5, Gens::choose_char('a', 'z')), (5, Gens::choose_char('A', 'Z'))];
-    let (map, total) = array
-      .into_iter()
-      .fold((BTreeMap::new(), 0), |(mut map, mut total), (weight, value)| {
-        total += weight;
-        map.insert(total, value);
-        (map, total)
-      });
-    let char_gen = Gens::choose_i32(1, total).flat_map(move |n| map.range(n..).into_iter().next().unwrap().1.clone());
-
-    let tail = Gens::choose_u8(0, 128)
-      .flat_map(move |n| Gens::list_of_n(n as usize, char_gen.clone()).map(|e| e.into_iter().collect::<String>()));
-    let head = Gens::choose_u8(1, 2).map(|n| match n {
-      1 => "#".to_string(),
-      2 => "//".to_string(),
-      n => panic!("n = {}", n),
-    });
-    head.flat_map(move |h| tail.clone().map(move |t| format!("{}{}", h, t)))
+    let generator = Generator::new(comment().syntax()).with_max_repeat(128);
+    Gens::one_u64().map(move |seed| String::from_utf8(generator.generate(seed).unwrap()).unwrap())
   }
 
   pub fn space_gen() -> Gen<String> {
@@ -386,24 +340,9 @@ mod gens {
     include_method_gen().flat_map(|method| Gens::pure(format!("include {}(\"{}\")", method, "abc")))
   }
 
-  pub fn path_element_gen() -> Gen<String> {
-    Gens::choose_u32(1, 128).flat_map(|n| {
-      Gens::list_of_n(
-        n as usize,
-        Gens::frequency([
-          (1, Gens::pure('-')),
-          (1, Gens::pure('_')),
-          (4, Gens::choose_char('a', 'z')),
-          (4, Gens::choose_char('A', 'Z')),
-        ]),
-      )
-      .map(|chars| chars.iter().collect::<String>())
-    })
-  }
-
   pub fn path_gen() -> Gen<String> {
-    Gens::choose_u32(1, 128)
-      .flat_map(|n| Gens::list_of_n(n as usize, path_element_gen()).map(|elements| elements.join(".")))
+    let generator = Generator::new(path().syntax()).with_max_repeat(128);
+    Gens::one_u64().map(move |seed| String::from_utf8(generator.generate(seed).unwrap()).unwrap())
   }
 }
 
@@ -438,7 +377,7 @@ mod tests {
       log::debug!("{:>03}, comment:string = {}", counter, input);
       let input_bytes = input.as_bytes();
       let result = (comment() - end()).parse(input_bytes).to_result();
-      let comment = std::str::from_utf8(result.unwrap()).unwrap();
+      let comment = String::from_utf8(result.unwrap()).unwrap();
       assert_eq!(comment.to_string(), input);
       true
     });
@@ -450,7 +389,7 @@ mod tests {
     let prop = prop::for_all_gen(space_gen(), move |input| {
       let input_bytes = input.as_bytes();
       let result = (space() - end()).parse(input_bytes).to_result();
-      let comment = std::str::from_utf8(result.unwrap()).unwrap();
+      let comment = String::from_utf8(result.unwrap()).unwrap();
       assert_eq!(comment.to_string(), input);
       true
     });
@@ -12,12 +12,14 @@
 
 use criterion::*;
 
-use crate::nom_json::nom_parse_json;
-use crate::oni_comb_json::oni_comb_parse_json;
-use crate::pom_json::pom_parse_json;
+use crate::oni_comb_cache::{nested_input, oni_comb_parse_nested};
+use crate::oni_comb_grammar::{expr_grammar, expr_input, oni_comb_parse_expr, oni_comb_parse_expr_with};
+use crate::oni_comb_json::{oni_comb_parse_json, oni_comb_parse_json_document};
 use pprof::criterion::{Output, PProfProfiler};
 
 mod nom_json;
+mod oni_comb_cache;
+mod oni_comb_grammar;
 mod oni_comb_json;
 mod pom_json;
 
@@ -39,6 +41,37 @@ fn criterion_benchmark(criterion: &mut Criterion) {
   // group.bench_with_input(BenchmarkId::new("oni-comb-rs", "bool"), data, |b, i| {
   //   b.iter(|| oni_comb_parse_json(i))
   // });
+  let data = r#"{ "a" : 42, "b" : [ "x", "y", 12 ], "c": { "hello" : "world" } }"#;
+
+  group.bench_function(BenchmarkId::new("oni-comb-rs", "document"), |b| {
+    b.iter(|| oni_comb_parse_json_document(data, false))
+  });
+  group.bench_function(BenchmarkId::new("oni-comb-rs", "document-cached"), |b| {
+    b.iter(|| oni_comb_parse_json_document(data, true))
+  });
+  group.finish();
+
+  let mut group = criterion.benchmark_group("cache");
+  let data = nested_input(8);
+
+  group.bench_function(BenchmarkId::new("oni-comb-rs", "nested"), |b| {
+    b.iter(|| oni_comb_parse_nested(&data, false))
+  });
+  group.bench_function(BenchmarkId::new("oni-comb-rs", "nested-cached"), |b| {
+    b.iter(|| oni_comb_parse_nested(&data, true))
+  });
+  group.finish();
+
+  let mut group = criterion.benchmark_group("grammar");
+  let data = expr_input();
+  let grammar = expr_grammar();
+
+  group.bench_function(BenchmarkId::new("oni-comb-rs", "rebuilt"), |b| {
+    b.iter(|| oni_comb_parse_expr(&data))
+  });
+  group.bench_function(BenchmarkId::new("oni-comb-rs", "built-once"), |b| {
+    b.iter(|| oni_comb_parse_expr_with(&grammar, &data))
+  });
   group.finish();
 }
 
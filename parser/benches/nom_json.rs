@@ -86,11 +86,11 @@ fn null<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (), E> {
 ///
 /// `context` and `cut` are related to error management:
 /// - `cut` transforms an `Err::Error(e)` in `Err::Failure(e)`, signaling to
-/// combinators like  `alt` that they should not try other parsers. We were in the
-/// right branch (since we found the `"` character) but encountered an error when
-/// parsing the string
+///   combinators like  `alt` that they should not try other parsers. We were in the
+///   right branch (since we found the `"` character) but encountered an error when
+///   parsing the string
 /// - `context` lets you add a static string to provide more information in the
-/// error chain (to indicate which parser had an error)
+///   error chain (to indicate which parser had an error)
 fn string<'a, E: ParseError<&'a str> + ContextError<&'a str>>(i: &'a str) -> IResult<&'a str, &'a str, E> {
   context("string", preceded(char('\"'), cut(terminated(parse_str, char('\"')))))(i)
 }
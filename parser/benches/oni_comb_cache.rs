@@ -0,0 +1,20 @@
+use oni_comb_parser_rs::prelude::*;
+
+// A nested group is tried by three alternatives, so without memoization each level parses the inner groups three
+// times, and the work grows as 3^depth.
+fn nested<'a>(cached: bool) -> Parser<'a, u8, usize> {
+  let group = surround(elm(b'('), lazy(move || nested(cached)), elm(b')'));
+  let group = if cached { group.cache() } else { group };
+  (group.clone() - elm(b'+')).attempt().map(|n| n + 1)
+    | (group.clone() - elm(b'-')).attempt().map(|n| n + 1)
+    | group.map(|n| n + 1)
+    | elm(b'1').map(|_| 0)
+}
+
+pub fn nested_input(depth: usize) -> String {
+  format!("{}1{}", "(".repeat(depth), ")".repeat(depth))
+}
+
+pub fn oni_comb_parse_nested(s: &str, cached: bool) {
+  let _ = (nested(cached) - end()).parse_str(s).success().unwrap();
+}
@@ -0,0 +1,29 @@
+use oni_comb_parser_rs::prelude::*;
+
+// An arithmetic expression of sums of products, whose parser is free, so a [Grammar] builds it once.
+fn expr<'a>() -> Parser<'a, u8, i64> {
+  recursive(|expr| {
+    let number = elm_digit()
+      .of_many1()
+      .map(|digits| digits.iter().fold(0, |n, d| n * 10 + (d - b'0') as i64));
+    let atom = number | surround(elm(b'('), expr, elm(b')'));
+    let term = atom.of_many1_sep(elm(b'*')).map(|ns| ns.iter().product::<i64>());
+    term.of_many1_sep(elm(b'+')).map(|ns| ns.iter().sum::<i64>())
+  }) - end()
+}
+
+pub fn expr_grammar() -> Grammar<u8, i64> {
+  Grammar::new(|_| expr())
+}
+
+pub fn expr_input() -> String {
+  "1+2*(3+4)*5+6".to_string()
+}
+
+pub fn oni_comb_parse_expr(s: &str) {
+  let _ = expr().parse_str(s).success().unwrap();
+}
+
+pub fn oni_comb_parse_expr_with(grammar: &Grammar<u8, i64>, s: &str) {
+  let _ = grammar.parse_str(s).success().unwrap();
+}
@@ -19,7 +19,7 @@ fn space<'a>() -> Parser<'a, char, ()> {
 }
 
 fn number<'a>() -> Parser<'a, char, f64> {
-  let integer = elm_digit_1_9_ref() - elm_digit_ref().of_many0() | elm_ref('0');
+  let integer = (elm_digit_1_9_ref() - elm_digit_ref().of_many0()) | elm_ref('0');
   let frac = elm_ref('.') + elm_digit_ref().of_many1();
   let exp = elm_of("eE") + elm_of("+-").opt() + elm_digit_ref().of_many1();
   let number = elm_ref('-').opt() + integer + frac.opt() + exp.opt();
@@ -41,7 +41,7 @@ fn string<'a>() -> Parser<'a, char, String> {
     .of_many1()
     .map(String::from_iter);
   let utf16_char: Parser<char, u16> = tag("\\u")
-    * elm_pred(|c: &char| c.is_digit(16))
+    * elm_pred(|c: &char| c.is_ascii_hexdigit())
       .of_count(4)
       .map(String::from_iter)
       .map_res(|digits| u16::from_str_radix(&digits, 16));
@@ -54,13 +54,13 @@ fn string<'a>() -> Parser<'a, char, String> {
   string.map(|strings| strings.concat())
 }
 
-fn array<'a>() -> Parser<'a, char, Vec<JsonValue>> {
-  let elems = lazy(value).of_many0_sep(space() * elm_ref(',') - space());
+fn array<'a>(cached: bool) -> Parser<'a, char, Vec<JsonValue>> {
+  let elems = lazy(move || value(cached)).of_many0_sep(space() * elm_ref(',') - space());
   surround(elm_ref('[') - space(), elems, space() * elm_ref(']'))
 }
 
-fn object<'a>() -> Parser<'a, char, HashMap<String, JsonValue>> {
-  let member = string() - space() - elm_ref(':') - space() + lazy(value);
+fn object<'a>(cached: bool) -> Parser<'a, char, HashMap<String, JsonValue>> {
+  let member = string() - space() - elm_ref(':') - space() + lazy(move || value(cached));
   let members = member.of_many0_sep(space() + elm_ref(',') + space());
   let obj = surround(elm_ref('{') + space(), members, space() + elm_ref('}'));
   obj.map(|members| members.into_iter().collect::<HashMap<_, _>>())
@@ -70,18 +70,25 @@ fn boolean<'a>() -> Parser<'a, char, bool> {
   tag("true").map(|_| true) | tag("false").map(|_| false)
 }
 
-fn value<'a>() -> Parser<'a, char, JsonValue> {
-  (tag("null").map(|_| JsonValue::Null)
-    | boolean().map(|b| JsonValue::Bool(b))
-    | number().map(|num| JsonValue::Num(num))
-    | string().map(|text| JsonValue::Str(text))
-    | array().map(|arr| JsonValue::Array(arr))
-    | object().map(|obj| JsonValue::Object(obj)))
-    - space()
+// With cached, every value is memoized by its offset, to measure what cache() costs on a grammar that does not
+// backtrack over values.
+fn value<'a>(cached: bool) -> Parser<'a, char, JsonValue> {
+  let value = (tag("null").map(|_| JsonValue::Null)
+    | boolean().map(JsonValue::Bool)
+    | number().map(JsonValue::Num)
+    | string().map(JsonValue::Str)
+    | array(cached).map(JsonValue::Array)
+    | object(cached).map(JsonValue::Object))
+    - space();
+  if cached {
+    value.cache()
+  } else {
+    value
+  }
 }
 
-pub fn json<'a>() -> Parser<'a, char, JsonValue> {
-  space() * value() - end()
+pub fn json<'a>(cached: bool) -> Parser<'a, char, JsonValue> {
+  space() * value(cached) - end()
 }
 
 pub fn oni_comb_parse_json(s: &str) {
@@ -89,3 +96,8 @@ pub fn oni_comb_parse_json(s: &str) {
   let _ = boolean().parse(&input).success().unwrap();
   // println!("{:?}", r);
 }
+
+pub fn oni_comb_parse_json_document(s: &str, cached: bool) {
+  let input: Vec<char> = s.chars().collect();
+  let _ = json(cached).parse(&input).success().unwrap();
+}
@@ -20,7 +20,7 @@ fn space<'a>() -> Parser<'a, char, ()> {
 }
 
 fn number<'a>() -> Parser<'a, char, f64> {
-  let integer = one_of("123456789") - one_of("0123456789").repeat(0..) | sym('0');
+  let integer = (one_of("123456789") - one_of("0123456789").repeat(0..)) | sym('0');
   let frac = sym('.') + one_of("0123456789").repeat(1..);
   let exp = one_of("eE") + one_of("+-").opt() + one_of("0123456789").repeat(1..);
   let number = sym('-').opt() + integer + frac.opt() + exp.opt();
@@ -39,7 +39,7 @@ fn string<'a>() -> Parser<'a, char, String> {
   let escape_sequence = sym('\\') * special_char;
   let char_string = (none_of("\\\"") | escape_sequence).repeat(1..).map(String::from_iter);
   let utf16_char = tag("\\u")
-    * is_a(|c: char| c.is_digit(16))
+    * is_a(|c: char| c.is_ascii_hexdigit())
       .repeat(4)
       .map(String::from_iter)
       .convert(|digits| u16::from_str_radix(&digits, 16));
@@ -70,11 +70,11 @@ fn boolean<'a>() -> Parser<'a, char, bool> {
 
 fn value<'a>() -> Parser<'a, char, JsonValue> {
   (tag("null").map(|_| JsonValue::Null)
-    | boolean().map(|b| JsonValue::Bool(b))
-    | number().map(|num| JsonValue::Num(num))
-    | string().map(|text| JsonValue::Str(text))
-    | array().map(|arr| JsonValue::Array(arr))
-    | object().map(|obj| JsonValue::Object(obj)))
+    | boolean().map(JsonValue::Bool)
+    | number().map(JsonValue::Num)
+    | string().map(JsonValue::Str)
+    | array().map(JsonValue::Array)
+    | object().map(JsonValue::Object))
     - space()
 }
 
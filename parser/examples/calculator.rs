@@ -44,22 +44,6 @@ fn expr<'a>() -> Parser<'a, char, Rc<Expr>> {
   additive()
 }
 
-fn add<'a>() -> Parser<'a, char, &'a char> {
-  space() * elm_ref('+') - space()
-}
-
-fn sub<'a>() -> Parser<'a, char, &'a char> {
-  space() * elm_ref('-') - space()
-}
-
-fn mul<'a>() -> Parser<'a, char, &'a char> {
-  space() * elm_ref('*') - space()
-}
-
-fn div<'a>() -> Parser<'a, char, &'a char> {
-  space() * elm_ref('/') - space()
-}
-
 fn multitive<'a>() -> Parser<'a, char, Rc<Expr>> {
   let aster = elm_ref('*');
   let slash = elm_ref('/');
@@ -104,7 +88,7 @@ fn primary0<'a>() -> Parser<'a, char, Rc<Expr>> {
 
 fn value<'a>() -> Parser<'a, char, Rc<Expr>> {
   regex(r"^\d+([.]\d+)?")
-    .map_res(|s| Decimal::from_str(&s))
+    .map_res(|s: String| Decimal::from_str(&s))
     .map(Expr::Value)
     .map(Rc::new)
 }
@@ -18,7 +18,7 @@ fn space<'a>() -> Parser<'a, u8, ()> {
 }
 
 fn number<'a>() -> Parser<'a, u8, f64> {
-  let integer = elm_digit_1_9_ref() - elm_digit_ref().of_many0() | elm_ref(b'0');
+  let integer = (elm_digit_1_9_ref() - elm_digit_ref().of_many0()) | elm_ref(b'0');
   let frac = elm_ref(b'.') + elm_digit_ref().of_many1();
   let exp = elm_of(b"eE") + elm_of(b"+-").opt() + elm_digit_ref().of_many1();
   let number = elm_ref(b'-').opt() + integer + frac.opt() + exp.opt();
@@ -69,10 +69,10 @@ fn value<'a>() -> Parser<'a, u8, JsonValue> {
   (seq(b"null").map(|_| JsonValue::Null)
     | seq(b"true").map(|_| JsonValue::Bool(true))
     | seq(b"false").map(|_| JsonValue::Bool(false))
-    | number().map(|num| JsonValue::Num(num))
-    | string().map(|text| JsonValue::Str(text))
-    | array().map(|arr| JsonValue::Array(arr))
-    | object().map(|obj| JsonValue::Object(obj)))
+    | number().map(JsonValue::Num)
+    | string().map(JsonValue::Str)
+    | array().map(JsonValue::Array)
+    | object().map(JsonValue::Object))
     - space()
 }
 
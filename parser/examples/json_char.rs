@@ -19,7 +19,7 @@ fn space<'a>() -> Parser<'a, char, ()> {
 }
 
 fn number<'a>() -> Parser<'a, char, f64> {
-  let integer = elm_digit_1_9_ref() - elm_digit_ref().of_many0() | elm_ref('0');
+  let integer = (elm_digit_1_9_ref() - elm_digit_ref().of_many0()) | elm_ref('0');
   let frac = elm_ref('.') + elm_digit_ref().of_many1();
   let exp = elm_of("eE") + elm_of("+-").opt() + elm_digit_ref().of_many1();
   let number = elm_ref('-').opt() + integer + frac.opt() + exp.opt();
@@ -41,7 +41,7 @@ fn string<'a>() -> Parser<'a, char, String> {
     .of_many1()
     .map(String::from_iter);
   let utf16_char: Parser<char, u16> = tag("\\u")
-    * elm_pred(|c: &char| c.is_digit(16))
+    * elm_pred(|c: &char| c.is_ascii_hexdigit())
       .of_count(4)
       .map(String::from_iter)
       .map_res(|digits| u16::from_str_radix(&digits, 16));
@@ -70,10 +70,10 @@ fn value<'a>() -> Parser<'a, char, JsonValue> {
   (tag("null").map(|_| JsonValue::Null)
     | tag("true").map(|_| JsonValue::Bool(true))
     | tag("false").map(|_| JsonValue::Bool(false))
-    | number().map(|num| JsonValue::Num(num))
-    | string().map(|text| JsonValue::Str(text))
-    | array().map(|arr| JsonValue::Array(arr))
-    | object().map(|obj| JsonValue::Object(obj)))
+    | number().map(JsonValue::Num)
+    | string().map(JsonValue::Str)
+    | array().map(JsonValue::Array)
+    | object().map(JsonValue::Object))
     - space()
 }
 
@@ -1,5 +1,13 @@
 pub use committed_status::*;
+pub use diagnostic::*;
 pub use element::*;
+pub use expr_builder::*;
+pub use generator::*;
+pub use grammar::*;
+pub use grammar_error::*;
+pub use input::*;
+pub use lexer::*;
+pub use owned_parse_error::*;
 pub use parse_error::*;
 pub use parse_result::*;
 pub use parse_state::*;
@@ -10,9 +18,25 @@ pub use parser_monad::*;
 pub use parser_pure::*;
 pub use parser_runner::*;
 pub use parsers::*;
+pub use position::*;
+pub use regex_match::*;
+pub use span::*;
+pub use stream_runner::*;
+pub use syntax::*;
+pub use text_input::*;
+pub use token::*;
+pub use tracer::*;
 
 mod committed_status;
+mod diagnostic;
 mod element;
+mod expr_builder;
+mod generator;
+mod grammar;
+mod grammar_error;
+mod input;
+mod lexer;
+mod owned_parse_error;
 mod parse_error;
 mod parse_result;
 mod parse_state;
@@ -23,3 +47,12 @@ mod parser_monad;
 mod parser_pure;
 mod parser_runner;
 mod parsers;
+mod position;
+mod railroad;
+mod regex_match;
+mod span;
+mod stream_runner;
+mod syntax;
+mod text_input;
+mod token;
+mod tracer;
@@ -0,0 +1,197 @@
+use crate::core::{Locate, ParseError, Position};
+use std::fmt;
+use std::fmt::Display;
+
+/// A structure representing a human-readable report of a [ParseError].<br/>
+/// [ParseError]を人間が読める形で報告するための構造体。
+///
+/// The primary message and the span come from the innermost error, and the enclosing `Expect`/`Custom` errors
+/// become notes.<br/>
+/// 主メッセージと範囲は最も内側のエラーから取り、それを包む`Expect`/`Custom`エラーは注記になる。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+  message: String,
+  position: Option<Position>,
+  width: usize,
+  notes: Vec<String>,
+}
+
+impl Diagnostic {
+  /// Returns a [Diagnostic].<br/>
+  /// [Diagnostic]を返す。
+  ///
+  /// - message: the primary message
+  /// - position: the start of the span
+  /// - width: the number of columns of the span
+  /// - notes: the additional notes
+  pub fn new(message: String, position: Option<Position>, width: usize, notes: Vec<String>) -> Self {
+    Self {
+      message,
+      position,
+      width,
+      notes,
+    }
+  }
+
+  /// Returns the primary message.<br/>
+  /// 主メッセージを返す。
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Returns the start of the span.<br/>
+  /// 範囲の開始位置を返す。
+  pub fn position(&self) -> Option<Position> {
+    self.position
+  }
+
+  /// Returns the number of columns of the span.<br/>
+  /// 範囲のカラム数を返す。
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  /// Returns the notes.<br/>
+  /// 注記を返す。
+  pub fn notes(&self) -> &[String] {
+    &self.notes
+  }
+
+  /// Renders the diagnostic with the offending line of the source.<br/>
+  /// ソースの該当行を含めて診断結果を描画する。
+  ///
+  /// ```text
+  /// error: expect tag "]", found: ,
+  ///  --> line 2, column 7
+  ///   |
+  /// 2 | a = [1, 2
+  ///   |       ^
+  ///   |
+  ///   = note: Expect array (at line 2, column 5)
+  /// ```
+  pub fn render(&self, source: &str) -> String {
+    let mut out = format!("error: {}\n", self.message);
+    let line = self
+      .position
+      .and_then(|position| split_lines(source).nth(position.line - 1).map(|line| (position, line)));
+    let gutter = match self.position {
+      Some(position) => position.line.to_string().len(),
+      None => 0,
+    };
+    let blank = " ".repeat(gutter);
+    if let Some((position, text)) = line {
+      let rest = text.chars().count().saturating_sub(position.column - 1);
+      let width = self.width.min(rest).max(1);
+      out.push_str(&format!("{}--> {}\n", blank, position));
+      out.push_str(&format!("{} |\n", blank));
+      out.push_str(&format!("{} | {}\n", position.line, text));
+      out.push_str(&format!(
+        "{} | {}{}\n",
+        blank,
+        " ".repeat(position.column - 1),
+        "^".repeat(width)
+      ));
+    } else if let Some(position) = self.position {
+      out.push_str(&format!("{}--> {}\n", blank, position));
+    }
+    if !self.notes.is_empty() {
+      out.push_str(&format!("{} |\n", blank));
+      for note in &self.notes {
+        out.push_str(&format!("{} = note: {}\n", blank, note));
+      }
+    }
+    out
+  }
+}
+
+impl Display for Diagnostic {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "error: {}", self.message)?;
+    if let Some(position) = self.position {
+      write!(f, " at {}", position)?;
+    }
+    for note in &self.notes {
+      write!(f, "\nnote: {}", note)?;
+    }
+    Ok(())
+  }
+}
+
+fn split_lines(source: &str) -> impl Iterator<Item = &str> {
+  source.split('\n').flat_map(|line| {
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    line.split('\r')
+  })
+}
+
+impl<'a, I: Locate, E> ParseError<'a, I, E> {
+  /// Returns the [Diagnostic] of the error.<br/>
+  /// エラーの[Diagnostic]を返す。
+  pub fn to_diagnostic(&self) -> Diagnostic {
+    let mut notes = vec![];
+    let mut current = self;
+    while let Some((note, inner)) = current.enclosed() {
+      notes.push(Self::note_of(current, &note));
+      current = inner;
+    }
+    notes.reverse();
+    let message = match current {
+      ParseError::Incomplete => "unexpected end of input".to_string(),
+      ParseError::Expected { .. } => current.expected_message(),
+      ParseError::Mismatch { message, .. }
+      | ParseError::Conversion { message, .. }
+      | ParseError::Expect { message, .. }
+      | ParseError::Custom { message, .. } => message.clone(),
+    };
+    let position = current.position().or_else(|| self.position());
+    let width = match current {
+      ParseError::Mismatch {
+        input, offset, length, ..
+      }
+      | ParseError::Conversion {
+        input, offset, length, ..
+      } => I::width(input.to_slice(), *offset, *length),
+      _ => 0,
+    };
+    Diagnostic::new(message, position, width, notes)
+  }
+
+  /// Renders the error with the offending line of the source.<br/>
+  /// ソースの該当行を含めてエラーを描画する。
+  ///
+  /// See [Diagnostic::render].<br/>
+  /// [Diagnostic::render]を参照。
+  pub fn render(&self, source: &str) -> String {
+    self.to_diagnostic().render(source)
+  }
+
+  /// Returns the innermost error, which the primary message and the span come from.<br/>
+  /// 主メッセージと範囲の元となる、最も内側のエラーを返す。
+  pub(crate) fn innermost(&self) -> &ParseError<'a, I, E> {
+    let mut current = self;
+    while let Some((_, inner)) = current.enclosed() {
+      current = inner;
+    }
+    current
+  }
+
+  fn enclosed(&self) -> Option<(String, &ParseError<'a, I, E>)> {
+    match self {
+      ParseError::Expect { message, inner, .. } => Some((format!("Expect {}", message), inner.as_ref())),
+      ParseError::Expected { errors, .. } if errors.len() == 1 => Some((self.expected_message(), &errors[0])),
+      ParseError::Custom {
+        message,
+        inner: Some(inner),
+        ..
+      } => Some((message.clone(), inner.as_ref())),
+      _ => None,
+    }
+  }
+
+  fn note_of(error: &ParseError<'a, I, E>, message: &str) -> String {
+    match error.position() {
+      Some(position) => format!("{} (at {})", message, position),
+      None => message.to_string(),
+    }
+  }
+}
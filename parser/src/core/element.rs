@@ -1,10 +1,21 @@
+use crate::core::ThreadSafe;
 use std::fmt::Debug;
+pub use unicode_general_category::GeneralCategory;
 
 /// A Element.<br/>
 /// 要素。
-pub trait Element: Debug {
+pub trait Element: Debug + ThreadSafe {
   /// 文字に変換する。
   fn to_char(self) -> char;
+  /// 文字から変換する。要素で表せない文字の場合は`None`を返す。
+  ///
+  /// The default implementation returns `None` for every character.<br/>
+  /// デフォルトの実装はすべての文字に対して`None`を返す。
+  fn from_char(_c: char) -> Option<Self>
+  where
+    Self: Sized, {
+    None
+  }
   /// 空白かどうか。
   fn is_ascii_space(&self) -> bool;
   /// 改行を含む空白かどうか。
@@ -37,6 +48,52 @@ pub trait Element: Debug {
   fn is_ascii_punctuation(&self) -> bool;
   fn is_ascii_graphic(&self) -> bool;
   fn is_ascii_control(&self) -> bool;
+
+  /// 文字の先頭の要素かどうか(UTF-8の継続バイトではないかどうか)。
+  fn is_char_boundary(&self) -> bool {
+    true
+  }
+
+  /// 要素の列を文字列に変換する(UTF-8として不正なバイトは置換文字になる)。
+  fn to_string_lossy(elements: &[Self]) -> String
+  where
+    Self: Sized + Copy, {
+    elements.iter().map(|e| e.to_char()).collect()
+  }
+}
+
+/// An [Element] classified by the Unicode properties of its character.<br/>
+/// 文字のUnicodeプロパティで分類される[Element]。
+///
+/// `u8` is a byte of UTF-8, so only the ASCII bytes are classified.<br/>
+/// `u8`はUTF-8のバイトなので、ASCIIのバイトのみが分類される。
+pub trait UnicodeElement: Element {
+  /// Unicodeの英字(Alphabetic)かどうか。
+  fn is_unicode_alpha(&self) -> bool;
+  /// Unicodeの数字(Numeric)かどうか。
+  fn is_unicode_digit(&self) -> bool;
+  /// Unicodeの英数字かどうか。
+  fn is_unicode_alpha_digit(&self) -> bool;
+  /// Unicodeの大文字(Uppercase)かどうか。
+  fn is_unicode_uppercase(&self) -> bool;
+  /// Unicodeの小文字(Lowercase)かどうか。
+  fn is_unicode_lowercase(&self) -> bool;
+  /// Unicodeの空白(White_Space)かどうか。
+  fn is_unicode_whitespace(&self) -> bool;
+  /// Unicodeの制御文字(Cc)かどうか。
+  fn is_unicode_control(&self) -> bool;
+  /// 識別子の先頭の文字(XID_Start)かどうか。
+  fn is_xid_start(&self) -> bool;
+  /// 識別子の後続の文字(XID_Continue)かどうか。
+  fn is_xid_continue(&self) -> bool;
+  /// Unicodeの一般カテゴリ(General_Category)。分類できない要素の場合は`None`を返す。
+  fn unicode_category(&self) -> Option<GeneralCategory>;
+  /// Unicodeの一般カテゴリがいずれかのカテゴリに含まれるかどうか。
+  fn is_unicode_category(&self, categories: &[GeneralCategory]) -> bool {
+    self
+      .unicode_category()
+      .is_some_and(|category| categories.contains(&category))
+  }
 }
 
 impl Element for u8 {
@@ -44,6 +101,10 @@ impl Element for u8 {
     char::from(self)
   }
 
+  fn from_char(c: char) -> Option<Self> {
+    u8::try_from(c).ok()
+  }
+
   fn is_ascii_space(&self) -> bool {
     matches!(*self, b' ' | b'\t')
   }
@@ -61,19 +122,19 @@ impl Element for u8 {
   }
 
   fn is_ascii_alpha_uppercase(&self) -> bool {
-    matches!(*self, b'A'..=b'Z')
+    u8::is_ascii_uppercase(self)
   }
 
   fn is_ascii_alpha_lowercase(&self) -> bool {
-    matches!(*self, b'a'..=b'z')
+    u8::is_ascii_lowercase(self)
   }
 
   fn is_ascii_alpha(&self) -> bool {
-    matches!(*self, b'A'..=b'Z' | b'a'..=b'z')
+    u8::is_ascii_alphabetic(self)
   }
 
   fn is_ascii_digit(&self) -> bool {
-    matches!(*self, b'0'..=b'9')
+    u8::is_ascii_digit(self)
   }
 
   fn is_ascii_digit_zero(&self) -> bool {
@@ -89,7 +150,7 @@ impl Element for u8 {
   }
 
   fn is_ascii_hex_digit(&self) -> bool {
-    matches!(*self, b'0'..=b'9' | b'A'..=b'F' | b'a'..=b'f')
+    u8::is_ascii_hexdigit(self)
   }
 
   fn is_ascii_oct_digit(&self) -> bool {
@@ -107,6 +168,58 @@ impl Element for u8 {
   fn is_ascii_control(&self) -> bool {
     matches!(*self, b'\0'..=b'\x1F' | b'\x7F')
   }
+
+  fn is_char_boundary(&self) -> bool {
+    (*self as i8) >= -0x40
+  }
+
+  fn to_string_lossy(elements: &[Self]) -> String {
+    String::from_utf8_lossy(elements).into_owned()
+  }
+}
+
+impl UnicodeElement for u8 {
+  fn is_unicode_alpha(&self) -> bool {
+    self.is_ascii() && char::from(*self).is_alphabetic()
+  }
+
+  fn is_unicode_digit(&self) -> bool {
+    self.is_ascii() && char::from(*self).is_numeric()
+  }
+
+  fn is_unicode_alpha_digit(&self) -> bool {
+    self.is_ascii() && char::from(*self).is_alphanumeric()
+  }
+
+  fn is_unicode_uppercase(&self) -> bool {
+    self.is_ascii() && char::from(*self).is_uppercase()
+  }
+
+  fn is_unicode_lowercase(&self) -> bool {
+    self.is_ascii() && char::from(*self).is_lowercase()
+  }
+
+  fn is_unicode_whitespace(&self) -> bool {
+    self.is_ascii() && char::from(*self).is_whitespace()
+  }
+
+  fn is_unicode_control(&self) -> bool {
+    self.is_ascii() && char::from(*self).is_control()
+  }
+
+  fn is_xid_start(&self) -> bool {
+    self.is_ascii() && unicode_ident::is_xid_start(char::from(*self))
+  }
+
+  fn is_xid_continue(&self) -> bool {
+    self.is_ascii() && unicode_ident::is_xid_continue(char::from(*self))
+  }
+
+  fn unicode_category(&self) -> Option<GeneralCategory> {
+    self
+      .is_ascii()
+      .then(|| unicode_general_category::get_general_category(char::from(*self)))
+  }
 }
 
 impl Element for char {
@@ -114,6 +227,10 @@ impl Element for char {
     self
   }
 
+  fn from_char(c: char) -> Option<Self> {
+    Some(c)
+  }
+
   fn is_ascii_space(&self) -> bool {
     matches!(*self, ' ' | '\t')
   }
@@ -131,19 +248,19 @@ impl Element for char {
   }
 
   fn is_ascii_alpha_uppercase(&self) -> bool {
-    matches!(*self, 'A'..='Z')
+    char::is_ascii_uppercase(self)
   }
 
   fn is_ascii_alpha_lowercase(&self) -> bool {
-    matches!(*self, 'a'..='z')
+    char::is_ascii_lowercase(self)
   }
 
   fn is_ascii_alpha(&self) -> bool {
-    matches!(*self, 'A'..='Z' | 'a'..='z')
+    char::is_ascii_alphabetic(self)
   }
 
   fn is_ascii_digit(&self) -> bool {
-    matches!(*self, '0'..='9')
+    char::is_ascii_digit(self)
   }
 
   fn is_ascii_digit_zero(&self) -> bool {
@@ -159,7 +276,7 @@ impl Element for char {
   }
 
   fn is_ascii_hex_digit(&self) -> bool {
-    matches!(*self, '0'..='9' | 'A'..='F' | 'a'..='f')
+    char::is_ascii_hexdigit(self)
   }
 
   fn is_ascii_oct_digit(&self) -> bool {
@@ -178,3 +295,45 @@ impl Element for char {
     matches!(*self, '\0'..='\x1F' | '\x7F')
   }
 }
+
+impl UnicodeElement for char {
+  fn is_unicode_alpha(&self) -> bool {
+    self.is_alphabetic()
+  }
+
+  fn is_unicode_digit(&self) -> bool {
+    self.is_numeric()
+  }
+
+  fn is_unicode_alpha_digit(&self) -> bool {
+    self.is_alphanumeric()
+  }
+
+  fn is_unicode_uppercase(&self) -> bool {
+    self.is_uppercase()
+  }
+
+  fn is_unicode_lowercase(&self) -> bool {
+    self.is_lowercase()
+  }
+
+  fn is_unicode_whitespace(&self) -> bool {
+    self.is_whitespace()
+  }
+
+  fn is_unicode_control(&self) -> bool {
+    self.is_control()
+  }
+
+  fn is_xid_start(&self) -> bool {
+    unicode_ident::is_xid_start(*self)
+  }
+
+  fn is_xid_continue(&self) -> bool {
+    unicode_ident::is_xid_continue(*self)
+  }
+
+  fn unicode_category(&self) -> Option<GeneralCategory> {
+    Some(unicode_general_category::get_general_category(*self))
+  }
+}
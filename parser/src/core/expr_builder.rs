@@ -0,0 +1,322 @@
+use crate::core::{ParseError, ParseResult, ParseState, Parser, ParserRunner, Shared, ThreadSafe, UserError};
+
+#[cfg(not(feature = "sync"))]
+type Unary<'a, A> = Shared<dyn Fn(A) -> A + 'a>;
+#[cfg(feature = "sync")]
+type Unary<'a, A> = Shared<dyn Fn(A) -> A + Send + Sync + 'a>;
+#[cfg(not(feature = "sync"))]
+type Binary<'a, A> = Shared<dyn Fn(A, A) -> A + 'a>;
+#[cfg(feature = "sync")]
+type Binary<'a, A> = Shared<dyn Fn(A, A) -> A + Send + Sync + 'a>;
+#[cfg(not(feature = "sync"))]
+type Ternary<'a, A> = Shared<dyn Fn(A, A, A) -> A + 'a>;
+#[cfg(feature = "sync")]
+type Ternary<'a, A> = Shared<dyn Fn(A, A, A) -> A + Send + Sync + 'a>;
+
+enum Associativity {
+  Left,
+  Right,
+  Non,
+}
+
+enum Operator<'a, I, A, E> {
+  Postfix(Parser<'a, I, Unary<'a, A>, E>),
+  Infix(Associativity, Parser<'a, I, Binary<'a, A>, E>),
+  Ternary(Parser<'a, I, (), E>, Parser<'a, I, (), E>, Ternary<'a, A>),
+}
+
+/// A builder of the [Parser] for the expressions, by the table of the operators and their precedences.<br/>
+/// 演算子とその優先順位の表によって、式の[Parser]を構築するビルダー。
+///
+/// The operators of the higher precedence bind tighter, and the operators are parsed by precedence climbing (Pratt
+/// parsing) instead of a tower of [crate::prelude::OperatorParser::chain_left1]. At the same precedence, a prefix
+/// operator binds tighter than the infix operators. The parser of an operator returns the function that builds the
+/// value, like the operator of `chain_left1`.<br/>
+/// 優先順位の高い演算子ほど強く結合し、演算子は[crate::prelude::OperatorParser::chain_left1]の積み重ねではなく
+/// 優先順位上昇法(Pratt構文解析)で解析される。同じ優先順位では、前置演算子が中置演算子より強く結合する。
+/// 演算子のパーサーは、`chain_left1`の演算子と同様に値を構築する関数を返す。
+///
+/// Mixfix operators such as calls and indexing are postfix operators whose parsers parse the inner expressions.<br/>
+/// 呼び出しや添字のような混置演算子は、内側の式を解析するパーサーを持つ後置演算子として定義する。
+///
+/// # Example
+///
+/// ```rust
+/// use oni_comb_parser_rs::prelude::*;
+///
+/// let text: &str = "-1+2*3^2^0";
+/// let input = text.chars().collect::<Vec<_>>();
+///
+/// let number = elm_digit().map(|c: char| c.to_digit(10).unwrap() as i64);
+/// let parser: Parser<char, i64> = ExprBuilder::new(number)
+///   .infix_left(1, elm('+').map(|_| |a, b| a + b))
+///   .infix_left(2, elm('*').map(|_| |a, b| a * b))
+///   .prefix(3, elm('-').map(|_| |a: i64| -a))
+///   .infix_right(4, elm('^').map(|_| |a: i64, b| a.pow(b as u32)))
+///   .build();
+///
+/// let result: ParseResult<char, i64> = parser.parse(&input);
+///
+/// assert!(result.is_success());
+/// assert_eq!(result.success().unwrap(), 5);
+/// ```
+pub struct ExprBuilder<'a, I, A, E = ()> {
+  atom: Parser<'a, I, A, E>,
+  #[allow(clippy::type_complexity)]
+  prefixes: Vec<(usize, Parser<'a, I, Unary<'a, A>, E>)>,
+  operators: Vec<(usize, Operator<'a, I, A, E>)>,
+}
+
+impl<'a, I, A, E> ExprBuilder<'a, I, A, E>
+where
+  I: 'a,
+  A: 'a,
+  E: UserError,
+{
+  /// Returns an [ExprBuilder] of the expressions made of the atoms, without operators.<br/>
+  /// 演算子を持たない、アトムからなる式の[ExprBuilder]を返す。
+  pub fn new(atom: Parser<'a, I, A, E>) -> Self {
+    Self {
+      atom,
+      prefixes: vec![],
+      operators: vec![],
+    }
+  }
+
+  /// Adds a prefix operator.<br/>
+  /// 前置演算子を追加する。
+  pub fn prefix<F>(mut self, precedence: usize, op: Parser<'a, I, F, E>) -> Self
+  where
+    F: Fn(A) -> A + ThreadSafe + 'a, {
+    self
+      .prefixes
+      .push((precedence, Self::erase(op, |f| Shared::new(f) as Unary<'a, A>)));
+    self
+  }
+
+  /// Adds a postfix operator.<br/>
+  /// 後置演算子を追加する。
+  pub fn postfix<F>(mut self, precedence: usize, op: Parser<'a, I, F, E>) -> Self
+  where
+    F: Fn(A) -> A + ThreadSafe + 'a, {
+    let op = Self::erase(op, |f| Shared::new(f) as Unary<'a, A>);
+    self.operators.push((precedence, Operator::Postfix(op)));
+    self
+  }
+
+  /// Adds a left-associative infix operator.<br/>
+  /// 左結合の中置演算子を追加する。
+  pub fn infix_left<F>(self, precedence: usize, op: Parser<'a, I, F, E>) -> Self
+  where
+    F: Fn(A, A) -> A + ThreadSafe + 'a, {
+    self.infix(Associativity::Left, precedence, op)
+  }
+
+  /// Adds a right-associative infix operator.<br/>
+  /// 右結合の中置演算子を追加する。
+  pub fn infix_right<F>(self, precedence: usize, op: Parser<'a, I, F, E>) -> Self
+  where
+    F: Fn(A, A) -> A + ThreadSafe + 'a, {
+    self.infix(Associativity::Right, precedence, op)
+  }
+
+  /// Adds a non-associative infix operator, which fails when chained with the operators of the same precedence.<br/>
+  /// 非結合の中置演算子を追加する。同じ優先順位の演算子と連鎖した場合は失敗する。
+  pub fn infix_non<F>(self, precedence: usize, op: Parser<'a, I, F, E>) -> Self
+  where
+    F: Fn(A, A) -> A + ThreadSafe + 'a, {
+    self.infix(Associativity::Non, precedence, op)
+  }
+
+  /// Adds a right-associative ternary operator, such as `c ? a : b`.<br/>
+  /// `c ? a : b`のような右結合の三項演算子を追加する。
+  ///
+  /// - precedence: the precedence
+  /// - precedence: 優先順位
+  /// - first: a parser of the first part of the operator, such as `?`
+  /// - first: `?`のような演算子の前半のパーサー
+  /// - second: a parser of the second part of the operator, such as `:`
+  /// - second: `:`のような演算子の後半のパーサー
+  /// - f: a function that builds the value from the three operands
+  /// - f: 三つの被演算子から値を構築する関数
+  pub fn ternary<B, C, F>(
+    mut self,
+    precedence: usize,
+    first: Parser<'a, I, B, E>,
+    second: Parser<'a, I, C, E>,
+    f: F,
+  ) -> Self
+  where
+    B: 'a,
+    C: 'a,
+    F: Fn(A, A, A) -> A + ThreadSafe + 'a, {
+    let first = Self::erase(first, |_| ());
+    let second = Self::erase(second, |_| ());
+    self
+      .operators
+      .push((precedence, Operator::Ternary(first, second, Shared::new(f))));
+    self
+  }
+
+  /// Returns the [Parser] of the expressions.<br/>
+  /// 式の[Parser]を返す。
+  pub fn build(self) -> Parser<'a, I, A, E> {
+    let builder = Shared::new(self);
+    Parser::new(move |parse_state| builder.parse(parse_state, 0))
+  }
+
+  fn infix<F>(mut self, associativity: Associativity, precedence: usize, op: Parser<'a, I, F, E>) -> Self
+  where
+    F: Fn(A, A) -> A + ThreadSafe + 'a, {
+    let op = Self::erase(op, |f| Shared::new(f) as Binary<'a, A>);
+    self.operators.push((precedence, Operator::Infix(associativity, op)));
+    self
+  }
+
+  fn erase<B: 'a, C: 'a>(parser: Parser<'a, I, B, E>, f: impl Fn(B) -> C + ThreadSafe + 'a) -> Parser<'a, I, C, E> {
+    crate::internal::combine!((parser) => move |parse_state| parser.run(parse_state).map(|value, length| (f(value), length)))
+  }
+
+  /// Parses an expression whose operators bind at least as tight as the binding power.
+  fn parse(&self, parse_state: &ParseState<'a, I>, min_bp: usize) -> ParseResult<'a, I, A, E> {
+    let (mut lhs, mut length) = match self.parse_prefix(parse_state) {
+      ParseResult::Success { value, length } => (value, length),
+      ParseResult::Failure {
+        error,
+        committed_status,
+      } => return ParseResult::failed(error, committed_status),
+    };
+    let mut last_non_associative = None;
+    loop {
+      let ps = parse_state.add_offset(length);
+      let (precedence, matched) = match self.parse_operator(&ps) {
+        Ok(Some(matched)) => matched,
+        Ok(None) => break,
+        Err(error) => return ParseResult::failed_with_commit(error),
+      };
+      let (left_bp, right_bp) = match &matched {
+        Matched::Postfix(..) | Matched::Infix(Associativity::Left | Associativity::Non, ..) => {
+          (2 * precedence, 2 * precedence + 1)
+        }
+        Matched::Infix(Associativity::Right, ..) | Matched::Ternary(..) => (2 * precedence + 1, 2 * precedence),
+      };
+      if left_bp < min_bp {
+        break;
+      }
+      let parse_result = match matched {
+        Matched::Postfix(f, n) => ParseResult::successful(f(lhs), n),
+        Matched::Infix(associativity, f, n1) => {
+          if let Associativity::Non = associativity {
+            if last_non_associative == Some(precedence) {
+              let pe = ParseError::of_mismatch(
+                ps.source(),
+                ps.next_offset(),
+                n1,
+                "expect no operator of the same precedence after a non-associative operator".to_string(),
+              );
+              return ParseResult::failed_with_commit(pe);
+            }
+            last_non_associative = Some(precedence);
+          }
+          match self.parse(&ps.add_offset(n1), right_bp) {
+            ParseResult::Success { value: rhs, length: n2 } => ParseResult::successful(f(lhs, rhs), n1 + n2),
+            ParseResult::Failure {
+              error,
+              committed_status,
+            } => ParseResult::failed(error, committed_status).with_committed_fallback(n1 != 0),
+          }
+        }
+        Matched::Ternary(second, f, n1) => match self.parse(&ps.add_offset(n1), 0) {
+          ParseResult::Success {
+            value: middle,
+            length: n2,
+          } => match second.run(&ps.add_offset(n1 + n2)) {
+            ParseResult::Success { length: n3, .. } => match self.parse(&ps.add_offset(n1 + n2 + n3), right_bp) {
+              ParseResult::Success { value: rhs, length: n4 } => {
+                ParseResult::successful(f(lhs, middle, rhs), n1 + n2 + n3 + n4)
+              }
+              ParseResult::Failure {
+                error,
+                committed_status,
+              } => ParseResult::failed(error, committed_status).with_committed_fallback(true),
+            },
+            ParseResult::Failure {
+              error,
+              committed_status,
+            } => ParseResult::failed(error, committed_status).with_committed_fallback(n1 + n2 != 0),
+          },
+          ParseResult::Failure {
+            error,
+            committed_status,
+          } => ParseResult::failed(error, committed_status).with_committed_fallback(n1 != 0),
+        },
+      };
+      match parse_result {
+        ParseResult::Success { value, length: n } => {
+          lhs = value;
+          length += n;
+        }
+        ParseResult::Failure {
+          error,
+          committed_status,
+        } => return ParseResult::failed(error, committed_status).with_committed_fallback(length != 0),
+      }
+    }
+    ParseResult::successful(lhs, length)
+  }
+
+  fn parse_prefix(&self, parse_state: &ParseState<'a, I>) -> ParseResult<'a, I, A, E> {
+    for (precedence, op) in &self.prefixes {
+      match op.run(parse_state) {
+        ParseResult::Success { value: f, length: n1 } => {
+          return match self.parse(&parse_state.add_offset(n1), 2 * precedence + 1) {
+            ParseResult::Success { value, length: n2 } => ParseResult::successful(f(value), n1 + n2),
+            ParseResult::Failure {
+              error,
+              committed_status,
+            } => ParseResult::failed(error, committed_status).with_committed_fallback(n1 != 0),
+          };
+        }
+        ParseResult::Failure {
+          error,
+          committed_status,
+        } if committed_status.is_committed() => return ParseResult::failed(error, committed_status),
+        ParseResult::Failure { error, .. } => parse_state.record_failure(error),
+      }
+    }
+    self.atom.run(parse_state)
+  }
+
+  /// Parses the first operator that matches, and returns it with its precedence.
+  #[allow(clippy::type_complexity)]
+  fn parse_operator(
+    &self,
+    parse_state: &ParseState<'a, I>,
+  ) -> Result<Option<(usize, Matched<'_, 'a, I, A, E>)>, ParseError<'a, I, E>> {
+    for (precedence, operator) in &self.operators {
+      let parse_result = match operator {
+        Operator::Postfix(op) => op.run(parse_state).map(|f, n| (Matched::Postfix(f, n), n)),
+        Operator::Infix(associativity, op) => op.run(parse_state).map(|f, n| (Matched::Infix(associativity, f, n), n)),
+        Operator::Ternary(first, second, f) => first
+          .run(parse_state)
+          .map(|_, n| (Matched::Ternary(second, f.clone(), n), n)),
+      };
+      match parse_result {
+        ParseResult::Success { value, .. } => return Ok(Some((*precedence, value))),
+        ParseResult::Failure {
+          error,
+          committed_status,
+        } if committed_status.is_committed() => return Err(error),
+        ParseResult::Failure { error, .. } => parse_state.record_failure(error),
+      }
+    }
+    Ok(None)
+  }
+}
+
+/// The operator that matched, with the length of its parser.
+enum Matched<'b, 'a, I, A, E> {
+  Postfix(Unary<'a, A>, usize),
+  Infix(&'b Associativity, Binary<'a, A>, usize),
+  Ternary(&'b Parser<'a, I, (), E>, Ternary<'a, A>, usize),
+}
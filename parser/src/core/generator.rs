@@ -0,0 +1,351 @@
+use crate::core::syntax::class_notation;
+use crate::core::{ClassKind, Element, ElementClass, LazyKey, Shared, Syntax};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+type Filter<'a, I> = dyn Fn(&[I]) -> bool + 'a;
+
+/// The maximum number of the inputs generated for a seed, until one is accepted by the filter.
+const MAX_ATTEMPTS: usize = 1000;
+/// The cost of a recursion that never terminates.
+const UNBOUNDED: usize = usize::MAX;
+/// The maximum size of a range of elements enumerated as the candidates, larger ranges are sampled.
+const MAX_ENUMERATED_RANGE: u32 = 4096;
+/// The number of the elements sampled from a large range.
+const SAMPLES: usize = 64;
+/// The elements tried after the printable ASCII characters, for the classes that contain none of them.
+const FALLBACK: &str = "αЖあ漢٣\u{3000}\u{1D538}";
+
+/// An error of generating an input from a [Syntax].<br/>
+/// [Syntax]から入力を生成する際のエラー。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+  /// A syntax that is not described, with its note.<br/>
+  /// 記述されていない構文とその注記。
+  Opaque(String),
+  /// A class of which no element is found, with its notation.<br/>
+  /// 要素が見つからないクラスとその表記。
+  Class(String),
+  /// A recursive syntax that never terminates, with the key of its lazy syntax.<br/>
+  /// 終了しない再帰的な構文と、その遅延構文のキー。
+  Recursion(String),
+  /// All the inputs generated are rejected by the filter, with the number of them.<br/>
+  /// 生成したすべての入力がフィルタに拒否された。その数を持つ。
+  Rejected(usize),
+}
+
+impl Display for GenerateError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      GenerateError::Opaque(note) if note.is_empty() => write!(f, "cannot generate the syntax that is not described"),
+      GenerateError::Opaque(note) => write!(f, "cannot generate the syntax that is not described: <{}>", note),
+      GenerateError::Class(notation) => write!(f, "no element of the class {} is found", notation),
+      GenerateError::Recursion(key) => write!(f, "the recursive syntax {} never terminates", key),
+      GenerateError::Rejected(attempts) => write!(f, "all the {} inputs generated are rejected", attempts),
+    }
+  }
+}
+
+impl Error for GenerateError {}
+
+/// A generator of random inputs that match a [Syntax], for property tests and fuzzing.<br/>
+/// プロパティテストやファジングのための、[Syntax]に一致するランダムな入力の生成器。
+///
+/// The input is derived from the grammar, so the parser accepts it unless the parser depends on what the syntax does
+/// not describe: the order of the choices, lookaheads such as `not`, or filters. A random choice or repetition that
+/// would nest the recursive rules deeper than the maximum depth is avoided, and the elements of a class are chosen
+/// from the printable ASCII characters first. The same seed generates the same input. `with_rule` generates a rule
+/// from a narrower syntax, and `with_filter` restricts the inputs to those the parser accepts.<br/>
+/// 入力は文法から導出されるため、構文が記述しないもの、つまり選択の順序、`not`などの先読み、フィルタにパーサーが
+/// 依存しない限り、パーサーはその入力を受理する。再帰的な規則を最大の深さより深く入れ子にするランダムな選択や
+/// 繰り返しは避け、クラスの要素は印字可能なASCII文字から優先して選ぶ。同じシードからは同じ入力を生成する。
+/// `with_rule`で規則をより狭い構文から生成でき、`with_filter`で入力をパーサーが受理するものに限定できる。
+///
+/// # Example
+///
+/// ```rust
+/// use oni_comb_parser_rs::prelude::*;
+///
+/// fn value<'a>() -> Parser<'a, char, ()> {
+///   let number = elm_digit().of_many1().name("number");
+///   let list = surround(elm('['), lazy(value).of_many0_sep(elm(',')), elm(']')).name("list");
+///   (number.discard() | list.discard()).name("value")
+/// }
+///
+/// let generator = Generator::new(value().syntax()).with_max_depth(3);
+/// for seed in 0..100 {
+///   let input = generator.generate(seed).unwrap();
+///   assert!((value() - end()).parse(&input).is_success());
+/// }
+/// ```
+pub struct Generator<'a, I> {
+  syntax: Shared<Syntax<'a, I>>,
+  rules: HashMap<String, Shared<Syntax<'a, I>>>,
+  expansions: HashMap<LazyKey, Shared<Syntax<'a, I>>>,
+  costs: HashMap<LazyKey, usize>,
+  max_depth: usize,
+  max_repeat: usize,
+  filter: Option<Box<Filter<'a, I>>>,
+}
+
+impl<'a, I: Element + Clone> Generator<'a, I> {
+  /// Returns the generator of the syntax, with the maximum depth 8 and the maximum repetition 4.<br/>
+  /// 最大の深さが8、最大の繰り返しが4の、構文の生成器を返す。
+  pub fn new(syntax: Syntax<'a, I>) -> Self {
+    Self {
+      syntax: Shared::new(syntax),
+      rules: HashMap::new(),
+      expansions: HashMap::new(),
+      costs: HashMap::new(),
+      max_depth: 8,
+      max_repeat: 4,
+      filter: None,
+    }
+    .analyze()
+  }
+
+  /// Returns the generator that generates the named rule from the syntax instead of its own.<br/>
+  /// 名前付きの規則を、規則自身の構文の代わりに指定した構文から生成する生成器を返す。
+  ///
+  /// The syntax should describe a part of the rule, for example its normal forms, so that the parser still accepts
+  /// the inputs.<br/>
+  /// パーサーが入力を受理し続けるように、構文は規則の一部、例えば正規形を記述するべきである。
+  pub fn with_rule(mut self, name: &str, syntax: Syntax<'a, I>) -> Self {
+    self.rules.insert(name.to_string(), Shared::new(syntax));
+    self.analyze()
+  }
+
+  /// Returns the generator with the maximum depth, the number of the nested recursive rules.<br/>
+  /// 最大の深さ、つまり入れ子になった再帰的な規則の数を指定した生成器を返す。
+  pub fn with_max_depth(self, max_depth: usize) -> Self {
+    Self { max_depth, ..self }
+  }
+
+  /// Returns the generator with the maximum repetition, the number of the repetitions beyond the minimum.<br/>
+  /// 最大の繰り返し、つまり最小の回数を超える繰り返しの数を指定した生成器を返す。
+  pub fn with_max_repeat(self, max_repeat: usize) -> Self {
+    Self { max_repeat, ..self }
+  }
+
+  /// Returns the generator of the inputs accepted by the filter. The rejected inputs are generated again.<br/>
+  /// フィルタが受理する入力の生成器を返す。拒否された入力は生成し直す。
+  pub fn with_filter<F>(self, f: F) -> Self
+  where
+    F: Fn(&[I]) -> bool + 'a, {
+    Self {
+      filter: Some(Box::new(f)),
+      ..self
+    }
+  }
+
+  /// Returns the input generated from the seed.<br/>
+  /// シードから生成した入力を返す。
+  pub fn generate(&self, seed: u64) -> Result<Vec<I>, GenerateError> {
+    let mut random = Random(seed);
+    for _ in 0..MAX_ATTEMPTS {
+      let mut input = vec![];
+      self.emit(&self.syntax, 0, &mut random, &mut input)?;
+      if self.filter.as_ref().is_none_or(|filter| filter(&input)) {
+        return Ok(input);
+      }
+    }
+    Err(GenerateError::Rejected(MAX_ATTEMPTS))
+  }
+
+  /// Expands the lazy syntaxes, and computes the depth of the recursive rules each of them needs.
+  fn analyze(mut self) -> Self {
+    self.expansions.clear();
+    self.expand(&self.syntax.clone());
+    self.costs = self.expansions.keys().map(|key| (*key, UNBOUNDED)).collect();
+    loop {
+      let mut changed = false;
+      for (key, syntax) in &self.expansions {
+        let cost = self.cost(syntax);
+        if cost < self.costs[key] {
+          self.costs.insert(*key, cost);
+          changed = true;
+        }
+      }
+      if !changed {
+        break self;
+      }
+    }
+  }
+
+  /// Returns the syntax to generate the rule from.
+  fn rule<'s>(&'s self, name: &str, syntax: &'s Shared<Syntax<'a, I>>) -> &'s Shared<Syntax<'a, I>> {
+    self.rules.get(name).unwrap_or(syntax)
+  }
+
+  /// Expands the lazy syntaxes once for each key.
+  fn expand(&mut self, syntax: &Syntax<'a, I>) {
+    match syntax {
+      Syntax::Sequence(syntaxes) | Syntax::Choice(syntaxes) => syntaxes.iter().for_each(|syntax| self.expand(syntax)),
+      Syntax::Repeat { syntax, separator, .. } => {
+        self.expand(syntax);
+        if let Some(separator) = separator {
+          self.expand(separator);
+        }
+      }
+      Syntax::Rule { name, syntax } => self.expand(&self.rule(name, syntax).clone()),
+      Syntax::Lazy { key, expand } if !self.expansions.contains_key(key) => {
+        let syntax = expand();
+        self.expansions.insert(*key, syntax.clone());
+        self.expand(&syntax);
+      }
+      _ => (),
+    }
+  }
+
+  /// Returns the depth of the recursive rules needed to generate the syntax.
+  fn cost(&self, syntax: &Syntax<'a, I>) -> usize {
+    match syntax {
+      Syntax::Empty | Syntax::Terminal(_) | Syntax::Class(_) | Syntax::Opaque(_) => 0,
+      Syntax::Sequence(syntaxes) => syntaxes.iter().map(|syntax| self.cost(syntax)).max().unwrap_or(0),
+      Syntax::Choice(syntaxes) => syntaxes.iter().map(|syntax| self.cost(syntax)).min().unwrap_or(0),
+      Syntax::Repeat { min: 0, .. } => 0,
+      Syntax::Repeat {
+        syntax, min, separator, ..
+      } => match separator {
+        Some(separator) if *min > 1 => self.cost(syntax).max(self.cost(separator)),
+        _ => self.cost(syntax),
+      },
+      Syntax::Rule { name, syntax } => self.cost(self.rule(name, syntax)),
+      Syntax::Lazy { key, .. } => self.costs.get(key).map_or(UNBOUNDED, |cost| cost.saturating_add(1)),
+    }
+  }
+
+  /// Returns whether the syntax can be generated within the maximum depth.
+  fn fits(&self, syntax: &Syntax<'a, I>, depth: usize) -> bool {
+    depth.saturating_add(self.cost(syntax)) <= self.max_depth
+  }
+
+  fn emit(
+    &self,
+    syntax: &Syntax<'a, I>,
+    depth: usize,
+    random: &mut Random,
+    input: &mut Vec<I>,
+  ) -> Result<(), GenerateError> {
+    match syntax {
+      Syntax::Empty => Ok(()),
+      Syntax::Terminal(elements) => {
+        input.extend_from_slice((**elements).as_ref());
+        Ok(())
+      }
+      Syntax::Class(class) => {
+        input.push(self.element(class, random)?);
+        Ok(())
+      }
+      Syntax::Sequence(syntaxes) => syntaxes
+        .iter()
+        .try_for_each(|syntax| self.emit(syntax, depth, random, input)),
+      Syntax::Choice(syntaxes) => {
+        let mut branches = syntaxes
+          .iter()
+          .filter(|syntax| self.fits(syntax, depth))
+          .collect::<Vec<_>>();
+        if branches.is_empty() {
+          let cost = self.cost(syntax);
+          branches = syntaxes.iter().filter(|syntax| self.cost(syntax) == cost).collect();
+        }
+        match branches.len() {
+          0 => Ok(()),
+          n => self.emit(branches[random.below(n)], depth, random, input),
+        }
+      }
+      Syntax::Repeat {
+        syntax,
+        min,
+        max,
+        separator,
+      } => {
+        let count = if self.fits(syntax, depth) {
+          let upper = max
+            .unwrap_or(usize::MAX)
+            .min(min.saturating_add(self.max_repeat))
+            .max(*min);
+          min + random.below((upper - min).saturating_add(1))
+        } else {
+          *min
+        };
+        for index in 0..count {
+          if let (Some(separator), true) = (separator, index > 0) {
+            self.emit(separator, depth, random, input)?;
+          }
+          self.emit(syntax, depth, random, input)?;
+        }
+        Ok(())
+      }
+      Syntax::Rule { name, syntax } => self.emit(self.rule(name, syntax), depth, random, input),
+      Syntax::Lazy { key, .. } => match (self.costs.get(key), self.expansions.get(key)) {
+        (Some(cost), Some(syntax)) if *cost != UNBOUNDED => self.emit(syntax, depth + 1, random, input),
+        _ => Err(GenerateError::Recursion(key.to_string())),
+      },
+      Syntax::Opaque(note) => Err(GenerateError::Opaque(note.clone())),
+    }
+  }
+
+  /// Returns a random element of the class, from the elements of its kind, the printable ASCII characters and the
+  /// fallback characters in this order.
+  fn element(&self, class: &ElementClass<'a, I>, random: &mut Random) -> Result<I, GenerateError> {
+    let candidates = |chars: &mut dyn Iterator<Item = char>| {
+      chars
+        .filter_map(I::from_char)
+        .filter(|element| class.contains(element))
+        .collect::<Vec<_>>()
+    };
+    let mut elements = match class.kind() {
+      ClassKind::Range { start, end, inclusive } => {
+        let start = start.clone().to_char() as u32;
+        let end = end.clone().to_char() as u32;
+        match if *inclusive { Some(end) } else { end.checked_sub(1) } {
+          Some(end) if start <= end && end - start < MAX_ENUMERATED_RANGE => {
+            candidates(&mut (start..=end).filter_map(char::from_u32))
+          }
+          Some(end) if start <= end => candidates(
+            &mut (0..SAMPLES).filter_map(|_| char::from_u32(start + random.below((end - start) as usize + 1) as u32)),
+          ),
+          _ => vec![],
+        }
+      }
+      ClassKind::OneOf(text) => candidates(&mut text.chars()),
+      ClassKind::Category(_) => candidates(
+        &mut FALLBACK
+          .chars()
+          .chain((0..SAMPLES * 16).filter_map(|_| char::from_u32(random.below(0x3_0000) as u32))),
+      ),
+      _ => vec![],
+    };
+    if elements.is_empty() {
+      elements = candidates(&mut " \t\n\r".chars().chain('!'..='~'));
+    }
+    if elements.is_empty() {
+      elements = candidates(&mut ('\u{80}'..='\u{FF}').chain(FALLBACK.chars()));
+    }
+    match elements.len() {
+      0 => Err(GenerateError::Class(class_notation(class.kind()))),
+      n => Ok(elements.swap_remove(random.below(n))),
+    }
+  }
+}
+
+/// A pseudo random number generator of SplitMix64.
+struct Random(u64);
+
+impl Random {
+  fn next(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+
+  /// Returns a random number less than `n`.
+  fn below(&mut self, n: usize) -> usize {
+    (self.next() % n as u64) as usize
+  }
+}
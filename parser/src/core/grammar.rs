@@ -0,0 +1,127 @@
+use crate::core::{
+  Input, ParseError, ParseResult, ParseState, Parser, ParserRunner, Shared, ThreadSafe, Tracer, UserError,
+};
+use std::marker::PhantomData;
+
+#[cfg(not(feature = "sync"))]
+type Build<I, A, E> = dyn for<'a> Fn(PhantomData<&'a ()>) -> Parser<'a, I, A, E>;
+#[cfg(feature = "sync")]
+type Build<I, A, E> = dyn for<'a> Fn(PhantomData<&'a ()>) -> Parser<'a, I, A, E> + Send + Sync;
+
+/// A handle of a grammar that builds its [Parser] once, and parses inputs of any lifetime.<br/>
+/// [Parser]を一度だけ構築し、任意のライフタイムの入力を解析する文法のハンドル。
+///
+/// A free [Parser] parses inputs of any lifetime, see [Parser::is_free]. The [Grammar] builds the parser once and
+/// keeps it if it is free, so it can be stored in a `thread_local!`, or in a `static` when the `sync` feature is
+/// enabled, and reused for every input. A bound parser is tied to the lifetime of its input, so the [Grammar] keeps
+/// the function instead, and builds the parser with the lifetime of each input.<br/>
+/// 自由な[Parser]は任意のライフタイムの入力を解析する。[Parser::is_free]を参照。[Grammar]はパーサーを一度だけ構築し、
+/// 自由であればそれを保持するため、`thread_local!`に、`sync`フィーチャーが有効な場合は`static`に格納して、
+/// すべての入力に再利用できる。束縛されたパーサーは入力のライフタイムに束縛されるため、[Grammar]は代わりに関数を保持し、
+/// 入力ごとにそのライフタイムでパーサーを構築する。
+///
+/// The function takes a `PhantomData` that names the lifetime of the input, because a closure cannot return a
+/// parser of a lifetime that does not appear in its arguments.<br/>
+/// クロージャは引数に現れないライフタイムのパーサーを返せないため、関数は入力のライフタイムを表す`PhantomData`を
+/// 引数に取る。
+///
+/// # Example
+///
+/// ```rust
+/// use oni_comb_parser_rs::prelude::*;
+///
+/// fn number<'a>() -> Parser<'a, u8, u32> {
+///   elm_digit().of_many1().map(|digits| digits.iter().fold(0, |n, d| n * 10 + (d - b'0') as u32)) - end()
+/// }
+///
+/// let grammar = Grammar::new(|_| number());
+///
+/// assert!(grammar.is_built());
+/// for text in ["1", "23", "456"] {
+///   let input = text.to_string();
+///   assert_eq!(grammar.parse_str(&input).success(), text.parse().ok());
+/// }
+/// ```
+pub struct Grammar<I: 'static, A: 'static, E: 'static = ()> {
+  build: Shared<Build<I, A, E>>,
+  built: Option<Parser<'static, I, A, E>>,
+}
+
+impl<I, A, E> Clone for Grammar<I, A, E> {
+  fn clone(&self) -> Self {
+    Self {
+      build: self.build.clone(),
+      built: self.built.clone(),
+    }
+  }
+}
+
+impl<I, A, E> Grammar<I, A, E> {
+  /// Returns a [Grammar] of the function that builds the parser, which is built once if it is free.<br/>
+  /// パーサーを構築する関数の[Grammar]を返す。パーサーは自由であれば一度だけ構築される。
+  pub fn new<F>(build: F) -> Self
+  where
+    F: for<'a> Fn(PhantomData<&'a ()>) -> Parser<'a, I, A, E> + ThreadSafe + 'static, {
+    let parser = build(PhantomData);
+    Self {
+      build: Shared::new(build),
+      built: parser.is_free().then_some(parser),
+    }
+  }
+
+  /// Returns whether the parser is built once and kept, that is, whether it is free.<br/>
+  /// パーサーが一度だけ構築されて保持されているか、つまり自由であるかどうかを返す。
+  pub fn is_built(&self) -> bool {
+    self.built.is_some()
+  }
+
+  /// Returns the parser for inputs of the lifetime `'a`, which is built again unless it is kept.<br/>
+  /// ライフタイム`'a`の入力に対するパーサーを返す。保持されていない場合は再び構築される。
+  ///
+  /// The parser can be combined with other parsers.<br/>
+  /// パーサーは他のパーサーと組み合わせることができる。
+  pub fn parser<'a>(&self) -> Parser<'a, I, A, E> {
+    match self.built.as_ref().and_then(Parser::shorten) {
+      Some(parser) => parser,
+      None => (self.build)(PhantomData),
+    }
+  }
+}
+
+impl<'a, I, A, E: UserError> ParserRunner<'a> for Grammar<I, A, E> {
+  type Error = E;
+  type Input = I;
+  type Output = A;
+  type P<'m, X, Y>
+    = Parser<'m, X, Y, E>
+  where
+    X: 'm;
+
+  fn parse(&self, input: impl Input<'a, Self::Input>) -> ParseResult<'a, Self::Input, Self::Output, Self::Error> {
+    self.parser().parse(input)
+  }
+
+  fn parse_with_errors(
+    &self,
+    input: impl Input<'a, Self::Input>,
+  ) -> (
+    ParseResult<'a, Self::Input, Self::Output, Self::Error>,
+    Vec<ParseError<'a, Self::Input, Self::Error>>,
+  ) {
+    self.parser().parse_with_errors(input)
+  }
+
+  fn parse_with_tracer<T>(
+    &self,
+    input: impl Input<'a, Self::Input>,
+    tracer: T,
+  ) -> (ParseResult<'a, Self::Input, Self::Output, Self::Error>, T)
+  where
+    T: Tracer + 'a, {
+    self.parser().parse_with_tracer(input, tracer)
+  }
+
+  fn run(&self, param: &ParseState<'a, Self::Input>) -> ParseResult<'a, Self::Input, Self::Output, Self::Error> {
+    self.parser().run(param)
+  }
+}
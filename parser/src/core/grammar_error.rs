@@ -0,0 +1,48 @@
+use crate::core::RegexError;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// An error of building a parser from invalid arguments.<br/>
+/// 不正な引数からパーサーを構築する際のエラー。
+///
+/// It is returned by the `try_` constructors, such as `try_regex`, `try_repeat` and `try_bit_fields`, instead of
+/// panicking or building a parser that never succeeds.<br/>
+/// `try_regex`や`try_repeat`などの`try_`コンストラクタが、パニックしたり決して成功しないパーサーを構築したりする代わりに返す。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrammarError {
+  /// An invalid regular expression.<br/>
+  /// 不正な正規表現。
+  Regex(RegexError),
+  /// A range of repetition that no count satisfies, such as `3..1`.<br/>
+  /// `3..1`のような、どの回数も満たさない繰り返しの範囲。
+  Range(String),
+  /// A width of a bit field out of 1 to 64.<br/>
+  /// 1から64の範囲外のビットフィールドの幅。
+  BitWidth(usize),
+}
+
+impl Display for GrammarError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      GrammarError::Regex(error) => write!(f, "{}", error),
+      GrammarError::Range(range) => write!(f, "invalid range {}: no repetition count satisfies it", range),
+      GrammarError::BitWidth(width) => write!(f, "invalid bit width {}: it must be from 1 to 64", width),
+    }
+  }
+}
+
+impl Error for GrammarError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      GrammarError::Regex(error) => Some(error),
+      GrammarError::Range(_) | GrammarError::BitWidth(_) => None,
+    }
+  }
+}
+
+impl From<RegexError> for GrammarError {
+  fn from(error: RegexError) -> Self {
+    GrammarError::Regex(error)
+  }
+}
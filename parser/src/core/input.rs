@@ -0,0 +1,335 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+/// A value that the parsers can parse, converted into the [Source] of a parse run.<br/>
+/// パーサーが解析できる値。解析の実行の[Source]に変換される。
+///
+/// It is implemented for the references to slices, arrays and `Vec`s, for `&str` and `&String` as `u8` elements, and
+/// for the references to a [ChunkedInput], such as the chunks of a rope. A memory-mapped file is parsed as the `&[u8]`
+/// it derefs to, and a token stream as a slice of the tokens, which keep their spans.<br/>
+/// スライス、配列、`Vec`への参照と、`u8`要素としての`&str`と`&String`、ロープのチャンクなどの[ChunkedInput]への参照に
+/// 実装されている。メモリマップトファイルは参照外しした`&[u8]`として、トークン列はスパンを保持するトークンのスライスとして
+/// 解析される。
+pub trait Input<'a, I> {
+  /// Converts the value into the [Source] of a parse run.<br/>
+  /// 値を解析の実行の[Source]に変換する。
+  fn into_source(self) -> Source<'a, I>;
+}
+
+impl<'a, I> Input<'a, I> for Source<'a, I> {
+  fn into_source(self) -> Source<'a, I> {
+    self
+  }
+}
+
+impl<'a, I> Input<'a, I> for &'a [I] {
+  fn into_source(self) -> Source<'a, I> {
+    Source::contiguous(self)
+  }
+}
+
+impl<'a, I, const N: usize> Input<'a, I> for &'a [I; N] {
+  fn into_source(self) -> Source<'a, I> {
+    Source::contiguous(self)
+  }
+}
+
+impl<'a, I> Input<'a, I> for &'a Vec<I> {
+  fn into_source(self) -> Source<'a, I> {
+    Source::contiguous(self)
+  }
+}
+
+impl<'a> Input<'a, u8> for &'a str {
+  fn into_source(self) -> Source<'a, u8> {
+    Source::contiguous(self.as_bytes())
+  }
+}
+
+impl<'a> Input<'a, u8> for &'a String {
+  fn into_source(self) -> Source<'a, u8> {
+    Source::contiguous(self.as_bytes())
+  }
+}
+
+impl<'a, 'c: 'a, I> Input<'a, I> for &'a ChunkedInput<'c, I> {
+  fn into_source(self) -> Source<'a, I> {
+    Source {
+      repr: Repr::Chunked(self),
+      len: self.len,
+    }
+  }
+}
+
+/// The input of a parse run, which refers to a contiguous slice or to a [ChunkedInput].<br/>
+/// 解析の実行の入力。連続したスライスか[ChunkedInput]を参照する。
+///
+/// It is a cheap copy, held by the parse states and by the errors. The elements of a contiguous input are always
+/// borrowed. The elements of a chunked input are borrowed as long as they lie in one chunk, and copied once into the
+/// input otherwise.<br/>
+/// 安価にコピーでき、解析状態とエラーが保持する。連続した入力の要素は常に借用される。チャンク化された入力の要素は、
+/// 一つのチャンクに収まる限り借用され、そうでなければ一度だけ入力にコピーされる。
+pub struct Source<'a, I> {
+  repr: Repr<'a, I>,
+  len: usize,
+}
+
+enum Repr<'a, I> {
+  Contiguous(&'a [I]),
+  Chunked(&'a ChunkedInput<'a, I>),
+}
+
+impl<I> Clone for Source<'_, I> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<I> Copy for Source<'_, I> {}
+
+impl<I> Clone for Repr<'_, I> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<I> Copy for Repr<'_, I> {}
+
+impl<'a, I> Source<'a, I> {
+  /// Returns the source of a contiguous slice.<br/>
+  /// 連続したスライスのソースを返す。
+  pub fn contiguous(input: &'a [I]) -> Self {
+    Self {
+      repr: Repr::Contiguous(input),
+      len: input.len(),
+    }
+  }
+
+  /// Returns the number of the elements.<br/>
+  /// 要素の数を返す。
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Returns whether the source has no elements.<br/>
+  /// 要素がないかどうかを返す。
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Returns the source that ends at the offset.<br/>
+  /// オフセットで終わるソースを返す。
+  pub fn truncate(&self, end: usize) -> Self {
+    let end = end.min(self.len);
+    match self.repr {
+      Repr::Contiguous(input) => Self::contiguous(&input[..end]),
+      Repr::Chunked(_) => Self { len: end, ..*self },
+    }
+  }
+
+  /// Returns the elements as a slice without copying, if the source is contiguous.<br/>
+  /// ソースが連続している場合、要素をコピーせずにスライスとして返す。
+  pub fn as_slice(&self) -> Option<&'a [I]> {
+    match self.repr {
+      Repr::Contiguous(input) => Some(input),
+      Repr::Chunked(_) => None,
+    }
+  }
+
+  /// Returns the contiguous elements from the offset up to the end of the chunk that holds it, which are empty at the
+  /// end.<br/>
+  /// オフセットから、それを含むチャンクの終わりまでの連続した要素を返す。終端では空となる。
+  pub fn chunk(&self, offset: usize) -> &'a [I] {
+    match self.repr {
+      Repr::Contiguous(input) => &input[offset.min(self.len)..],
+      Repr::Chunked(input) if offset < self.len => {
+        let chunk = input.chunk(offset);
+        &chunk[..chunk.len().min(self.len - offset)]
+      }
+      Repr::Chunked(_) => &[],
+    }
+  }
+
+  /// Returns the elements in the range as a slice.<br/>
+  /// 範囲の要素をスライスとして返す。
+  ///
+  /// The range is clamped to the end. A range of a chunked input that spans several chunks is copied into the input,
+  /// once for each range.<br/>
+  /// 範囲は終端に切り詰められる。チャンク化された入力の、複数のチャンクにまたがる範囲は、範囲ごとに一度だけ入力に
+  /// コピーされる。
+  pub fn slice(&self, start: usize, end: usize) -> &'a [I] {
+    let end = end.min(self.len);
+    let start = start.min(end);
+    match self.repr {
+      Repr::Contiguous(input) => &input[start..end],
+      Repr::Chunked(input) => input.slice(start, end),
+    }
+  }
+
+  /// Returns all the elements as a slice, copying the chunks of a chunked input once.<br/>
+  /// すべての要素をスライスとして返す。チャンク化された入力のチャンクは一度だけコピーされる。
+  pub fn to_slice(&self) -> &'a [I] {
+    self.slice(0, self.len)
+  }
+
+  /// Returns an iterator over the elements from the offset, which walks the chunks without copying them.<br/>
+  /// オフセットからの要素のイテレータを返す。チャンクをコピーせずに走査する。
+  pub fn iter(&self, offset: usize) -> Elements<'a, I> {
+    let offset = offset.min(self.len);
+    let chunk = self.chunk(offset);
+    Elements {
+      source: *self,
+      offset: offset + chunk.len(),
+      chunk: chunk.iter(),
+    }
+  }
+}
+
+impl<I: Debug> Debug for Source<'_, I> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(self.iter(0)).finish()
+  }
+}
+
+impl<I: PartialEq> PartialEq for Source<'_, I> {
+  fn eq(&self, other: &Self) -> bool {
+    self.len == other.len && self.iter(0).eq(other.iter(0))
+  }
+}
+
+impl<I: PartialOrd> PartialOrd for Source<'_, I> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.iter(0).partial_cmp(other.iter(0))
+  }
+}
+
+/// The iterator over the elements of a [Source], returned by [Source::iter].<br/>
+/// [Source::iter]が返す、[Source]の要素のイテレータ。
+pub struct Elements<'a, I> {
+  source: Source<'a, I>,
+  offset: usize,
+  chunk: std::slice::Iter<'a, I>,
+}
+
+impl<'a, I> Iterator for Elements<'a, I> {
+  type Item = &'a I;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if let Some(element) = self.chunk.next() {
+      return Some(element);
+    }
+    let chunk = self.source.chunk(self.offset);
+    self.offset += chunk.len();
+    self.chunk = chunk.iter();
+    self.chunk.next()
+  }
+}
+
+/// The copies of the ranges across the chunks, by their start and end.
+type Copies<I> = HashMap<(usize, usize), Box<[I]>>;
+
+/// An input whose elements are stored in several contiguous chunks, such as the chunks of a rope.<br/>
+/// ロープのチャンクのように、要素が複数の連続したチャンクに格納された入力。
+///
+/// The chunks are borrowed. The parsers that return a slice of the input, such as `take_while0`, borrow it from a
+/// chunk if it lies in one, and otherwise copy it into this input, which keeps the copies until it is dropped. The
+/// parsers that need the whole input, such as `regex`, `bits` and the location of an error, copy the chunks once.<br/>
+/// チャンクは借用される。`take_while0`のように入力のスライスを返すパーサーは、スライスが一つのチャンクに収まればそれを
+/// 借用し、そうでなければこの入力にコピーする。コピーは入力が破棄されるまで保持される。`regex`、`bits`、エラーの位置のように
+/// 入力全体を必要とするものは、チャンクを一度だけコピーする。
+///
+/// ```rust
+/// use oni_comb_parser_rs::prelude::*;
+///
+/// let input = ChunkedInput::new(["ke", "y = v", "alue"].map(str::as_bytes));
+/// let p = tag("key") - tag(" = ") + take_chars_while1(char::is_alphabetic) - end();
+/// let (key, value) = p.parse(&input).success().unwrap();
+/// assert_eq!((key, value), ("key", "value"));
+/// ```
+pub struct ChunkedInput<'c, I> {
+  chunks: Vec<&'c [I]>,
+  starts: Vec<usize>,
+  len: usize,
+  copies: Mutex<Copies<I>>,
+  extend: fn(&mut Vec<I>, &[I]),
+}
+
+impl<'c, I> ChunkedInput<'c, I> {
+  /// Returns the input of the chunks, in order.<br/>
+  /// チャンクを順に並べた入力を返す。
+  pub fn new(chunks: impl IntoIterator<Item = &'c [I]>) -> Self
+  where
+    I: Clone, {
+    let chunks = chunks.into_iter().filter(|chunk| !chunk.is_empty()).collect::<Vec<_>>();
+    let mut starts = Vec::with_capacity(chunks.len());
+    let mut len = 0;
+    for chunk in &chunks {
+      starts.push(len);
+      len += chunk.len();
+    }
+    Self {
+      chunks,
+      starts,
+      len,
+      copies: Mutex::new(HashMap::new()),
+      extend: Vec::extend_from_slice,
+    }
+  }
+
+  /// Returns the number of the elements.<br/>
+  /// 要素の数を返す。
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Returns whether the input has no elements.<br/>
+  /// 要素がないかどうかを返す。
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Returns the index of the chunk that holds the offset, which is less than the length.
+  fn index(&self, offset: usize) -> usize {
+    self.starts.partition_point(|start| *start <= offset) - 1
+  }
+
+  fn chunk(&self, offset: usize) -> &'c [I] {
+    let index = self.index(offset);
+    &self.chunks[index][offset - self.starts[index]..]
+  }
+
+  fn slice(&self, start: usize, end: usize) -> &[I] {
+    if start == end {
+      return &[];
+    }
+    let index = self.index(start);
+    let offset = start - self.starts[index];
+    if end - start <= self.chunks[index].len() - offset {
+      return &self.chunks[index][offset..offset + end - start];
+    }
+    let mut copies = self.copies.lock().unwrap_or_else(|e| e.into_inner());
+    let copy = copies.entry((start, end)).or_insert_with(|| {
+      let mut copy = Vec::with_capacity(end - start);
+      let mut offset = start;
+      while offset < end {
+        let chunk = self.chunk(offset);
+        let chunk = &chunk[..chunk.len().min(end - offset)];
+        (self.extend)(&mut copy, chunk);
+        offset += chunk.len();
+      }
+      copy.into_boxed_slice()
+    });
+    // SAFETY: the copy is a boxed slice whose elements do not move when the map grows, and the map only inserts the
+    // copies, so the elements live as long as this input, for which they are borrowed.
+    unsafe { &*(&**copy as *const [I]) }
+  }
+}
+
+impl<I: Debug> Debug for ChunkedInput<'_, I> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ChunkedInput").field("chunks", &self.chunks).finish()
+  }
+}
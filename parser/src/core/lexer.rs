@@ -0,0 +1,121 @@
+use crate::core::{ParseError, ParseResult, ParseState, Parser, ParserRunner, Position, Span, Token};
+use std::str;
+
+/// A lexer that splits a source into [Token]s by the rules defined with [Parser]s.<br/>
+/// [Parser]で定義した規則によって、ソースを[Token]に分割するレキサー。
+///
+/// At each position the rule of the longest match wins, and the earlier rule wins a tie.<br/>
+/// 各位置では最長一致の規則が採用され、同じ長さの場合は先に定義された規則が採用される。
+///
+/// # Example
+///
+/// ```rust
+/// use oni_comb_parser_rs::prelude::*;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Kind { Ident, Number }
+///
+/// let lexer = Lexer::new()
+///   .token(Kind::Ident, regex("[a-z]+"))
+///   .token(Kind::Number, regex("[0-9]+"))
+///   .skip(take_chars_while1(char::is_whitespace));
+///
+/// let tokens = lexer.tokenize("abc 123").unwrap();
+///
+/// assert_eq!(tokens.len(), 2);
+/// assert_eq!(tokens[1].kind, Kind::Number);
+/// assert_eq!(tokens[1].text, "123");
+/// assert_eq!(tokens[1].span.start, Position::new(4, 1, 5));
+/// ```
+pub struct Lexer<'a, K> {
+  rules: Vec<(Option<K>, Parser<'a, u8, ()>)>,
+}
+
+impl<'a, K> Default for Lexer<'a, K> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<'a, K> Lexer<'a, K> {
+  /// Returns a [Lexer] without rules.<br/>
+  /// 規則を持たない[Lexer]を返す。
+  pub fn new() -> Self {
+    Self { rules: vec![] }
+  }
+
+  /// Adds a rule that produces a token of the kind.<br/>
+  /// 指定した種類のトークンを生成する規則を追加する。
+  pub fn token<A: 'a>(mut self, kind: K, parser: Parser<'a, u8, A>) -> Self {
+    self.rules.push((Some(kind), Self::discard(parser)));
+    self
+  }
+
+  /// Adds a rule that skips the input, such as whitespaces and comments.<br/>
+  /// 空白やコメントなど、入力を読み飛ばす規則を追加する。
+  pub fn skip<A: 'a>(mut self, parser: Parser<'a, u8, A>) -> Self {
+    self.rules.push((None, Self::discard(parser)));
+    self
+  }
+
+  /// Splits the source into [Token]s.<br/>
+  /// ソースを[Token]に分割する。
+  ///
+  /// Returns the error at the position where no rule matches.<br/>
+  /// どの規則にも一致しない位置のエラーを返す。
+  pub fn tokenize(&self, source: &'a str) -> Result<Vec<Token<K>>, ParseError<'a, u8>>
+  where
+    K: Clone, {
+    let input = source.as_bytes();
+    let mut tokens = vec![];
+    let mut position = Position::default();
+    while position.offset < input.len() {
+      let parse_state = ParseState::new(input, position.offset);
+      let matched = self
+        .rules
+        .iter()
+        .filter_map(|(kind, parser)| match parser.run(&parse_state) {
+          ParseResult::Success { length, .. } if length > 0 => Some((kind, length)),
+          _ => None,
+        })
+        .fold(
+          None,
+          |longest: Option<(&Option<K>, usize)>, (kind, length)| match longest {
+            Some((_, l)) if l >= length => longest,
+            _ => Some((kind, length)),
+          },
+        );
+      let (kind, length) = match matched {
+        Some(matched) => matched,
+        None => {
+          let (actual, length) = match source.get(position.offset..).and_then(|s| s.chars().next()) {
+            Some(c) => (c.to_string(), c.len_utf8()),
+            None => (format!("{:#04x}", input[position.offset]), 1),
+          };
+          let expected = "token".to_string();
+          let found = actual.to_string();
+          return Err(ParseError::of_expected_mismatch(
+            input,
+            position.offset,
+            length,
+            expected,
+            found,
+          ));
+        }
+      };
+      let end = position.advance(input, position.offset + length);
+      if let Some(kind) = kind {
+        let text = str::from_utf8(&input[position.offset..end.offset])
+          .map(str::to_string)
+          .unwrap_or_default();
+        tokens.push(Token::new(kind.clone(), text, Span::new(position, end)));
+      }
+      position = end;
+    }
+    Ok(tokens)
+  }
+
+  fn discard<A: 'a>(parser: Parser<'a, u8, A>) -> Parser<'a, u8, ()> {
+    crate::internal::combine!((parser) => move |parse_state| parser.run(parse_state).map(|_, length| ((), length)))
+  }
+}
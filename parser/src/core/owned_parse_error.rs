@@ -0,0 +1,114 @@
+use crate::core::{Diagnostic, Locate, ParseError, Position};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// A [ParseError] that owns its data, and does not borrow the input.<br/>
+/// 入力を借用せず、データを所有する[ParseError]。
+///
+/// It implements `Error + Send + Sync + 'static`, so it can be propagated after the input is dropped.<br/>
+/// `Error + Send + Sync + 'static`を実装しているため、入力を破棄した後も伝播できる。
+///
+/// # Example
+///
+/// ```rust
+/// use oni_comb_parser_rs::prelude::*;
+///
+/// fn parse(text: &str) -> Result<u32, OwnedParseError> {
+///   let input = text.to_string();
+///   let parser = take_chars_while1(|c| c.is_ascii_digit()).map_res(|s: &str| s.parse::<u32>()) - end();
+///   Ok(parser.parse_str(&input).to_result()?)
+/// }
+///
+/// let error = parse("12\n3x").unwrap_err();
+///
+/// assert_eq!(error.position(), Some(Position::new(2, 1, 3)));
+/// assert_eq!(error.snippet(), Some("12"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedParseError {
+  diagnostic: Diagnostic,
+  snippet: Option<String>,
+}
+
+impl OwnedParseError {
+  /// Returns the offset where the error occurred.<br/>
+  /// エラーが発生したオフセットを返す。
+  pub fn offset(&self) -> Option<usize> {
+    self.position().map(|position| position.offset)
+  }
+
+  /// Returns the [Position] where the error occurred.<br/>
+  /// エラーが発生した[Position]を返す。
+  pub fn position(&self) -> Option<Position> {
+    self.diagnostic.position()
+  }
+
+  /// Returns the primary message.<br/>
+  /// 主メッセージを返す。
+  pub fn message(&self) -> &str {
+    self.diagnostic.message()
+  }
+
+  /// Returns the notes of the enclosing errors.<br/>
+  /// 包んでいるエラーの注記を返す。
+  pub fn notes(&self) -> &[String] {
+    self.diagnostic.notes()
+  }
+
+  /// Returns the text of the line where the error occurred.<br/>
+  /// エラーが発生した行のテキストを返す。
+  pub fn snippet(&self) -> Option<&str> {
+    self.snippet.as_deref()
+  }
+
+  /// Returns the [Diagnostic] of the error.<br/>
+  /// エラーの[Diagnostic]を返す。
+  pub fn diagnostic(&self) -> &Diagnostic {
+    &self.diagnostic
+  }
+}
+
+impl Display for OwnedParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.message())?;
+    if let Some(position) = self.position() {
+      write!(f, " at {}", position)?;
+    }
+    if let (Some(position), Some(snippet)) = (self.position(), self.snippet()) {
+      let rest = snippet.chars().count().saturating_sub(position.column - 1);
+      let width = self.diagnostic.width().min(rest).max(1);
+      write!(f, "\n  | {}", snippet)?;
+      write!(f, "\n  | {}{}", " ".repeat(position.column - 1), "^".repeat(width))?;
+    }
+    for note in self.notes() {
+      write!(f, "\nnote: {}", note)?;
+    }
+    Ok(())
+  }
+}
+
+impl Error for OwnedParseError {}
+
+impl<'a, I: Locate, E> ParseError<'a, I, E> {
+  /// Converts the error into an [OwnedParseError], which does not borrow the input.<br/>
+  /// 入力を借用しない[OwnedParseError]に変換する。
+  ///
+  /// The user errors held as payloads are kept only as their messages.<br/>
+  /// ペイロードとして保持されたユーザーエラーは、そのメッセージのみが保持される。
+  pub fn into_owned(self) -> OwnedParseError {
+    let diagnostic = self.to_diagnostic();
+    let innermost = self.innermost();
+    let snippet = match (innermost.original_input(), innermost.offset()) {
+      (Some(input), Some(offset)) => Some(I::line_text(input, offset)),
+      _ => None,
+    };
+    OwnedParseError { diagnostic, snippet }
+  }
+}
+
+impl<'a, I: Locate, E> From<ParseError<'a, I, E>> for OwnedParseError {
+  fn from(error: ParseError<'a, I, E>) -> Self {
+    error.into_owned()
+  }
+}
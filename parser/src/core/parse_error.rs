@@ -1,80 +1,180 @@
+use crate::core::{Input, Locate, Position, Source, ThreadSafe};
 use std::fmt;
-use std::fmt::Display;
+use std::fmt::{Debug, Display};
+
+/// A user error type that a [ParseError] can carry, which is `()` unless given to `custom` or `with_error_type`.<br/>
+/// [ParseError]が保持できるユーザーエラーの型。`custom`や`with_error_type`で与えない限り`()`となる。
+///
+/// It is implemented for every type that is `Clone + Debug + 'static`, and `Send + Sync` with the `sync`
+/// feature.<br/>
+/// `Clone + Debug + 'static`(`sync`フィーチャーでは`Send + Sync`も)であるすべての型に実装される。
+pub trait UserError: Clone + Debug + ThreadSafe + 'static {}
+
+impl<E: Clone + Debug + ThreadSafe + 'static> UserError for E {}
+
+/// A user error that is converted from the error of a conversion, such as `map_res`.<br/>
+/// `map_res`などの変換のエラーから変換されるユーザーエラー。
+///
+/// `()` drops every conversion error, so that the parsers without a user error type keep only the messages. A user
+/// error type implements it for the conversion errors that it keeps.<br/>
+/// `()`はすべての変換エラーを捨てるため、ユーザーエラー型を持たないパーサーはメッセージのみを保持する。ユーザーエラー型は、
+/// 保持する変換エラーに対してこれを実装する。
+pub trait FromConversionError<R>: Sized {
+  /// Converts the error of a conversion, or returns `None` to keep only its message.<br/>
+  /// 変換のエラーを変換する。メッセージのみを保持する場合は`None`を返す。
+  fn from_conversion_error(error: R) -> Option<Self>;
+}
+
+impl<R> FromConversionError<R> for () {
+  fn from_conversion_error(_: R) -> Option<Self> {
+    None
+  }
+}
 
 /// The enum type representing the parsing error.<br/>
 /// 解析エラーを示す列挙型。
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
-pub enum ParseError<'a, I> {
+pub enum ParseError<'a, I, E = ()> {
   /// パーサの条件にマッチしなかった場合のエラー
+  ///
+  /// `input` holds the [Source] of the whole input and `offset` is absolute. `expected` holds the label of what was
+  /// expected, if the parser has one.<br/>
+  /// `input`は入力全体の[Source]を保持し、`offset`は絶対位置を示す。`expected`はパーサーが持つ場合、期待していたもののラベルを保持する。
   Mismatch {
-    input: &'a [I],
+    input: Source<'a, I>,
     offset: usize,
     length: usize,
     message: String,
+    expected: Option<String>,
   },
   /// 変換に失敗した際のエラー
+  ///
+  /// `payload` holds the error returned by the conversion of `map_res`, converted by [FromConversionError].<br/>
+  /// `payload`は`map_res`の変換が返したエラーを[FromConversionError]で変換したものを保持する。
   Conversion {
-    input: &'a [I],
+    input: Source<'a, I>,
     offset: usize,
     length: usize,
     message: String,
+    payload: Option<E>,
   },
   /// 解析中断のエラー
   Incomplete,
   /// 期待から逸れた際のエラー
+  ///
+  /// `message` holds what was expected.<br/>
+  /// `message`は期待していたものを保持する。
   Expect {
     offset: usize,
-    inner: Box<ParseError<'a, I>>,
+    inner: Box<ParseError<'a, I, E>>,
     message: String,
   },
+  /// 期待する候補のいずれにもマッチしなかった際のエラー
+  ///
+  /// `expected` holds the labels of the candidates, and `errors` holds the failures of them.<br/>
+  /// `expected`は候補のラベルを、`errors`はそれらの失敗を保持する。
+  Expected {
+    offset: usize,
+    expected: Vec<String>,
+    errors: Vec<ParseError<'a, I, E>>,
+  },
   /// カスタムエラー
+  ///
+  /// `payload` holds the user error given to `custom`.<br/>
+  /// `payload`は`custom`に与えたユーザーエラーを保持する。
   Custom {
     offset: usize,
-    inner: Option<Box<ParseError<'a, I>>>,
+    inner: Option<Box<ParseError<'a, I, E>>>,
     message: String,
+    payload: Option<E>,
   },
 }
 
-impl<'a, I> Display for ParseError<'a, I> {
+impl<'a, I, E> Display for ParseError<'a, I, E> {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    match self {
+    let location = |error: &ParseError<'a, I, E>| format!("offset {}", error.offset().unwrap_or(0));
+    LocatedWith {
+      error: self,
+      location: &location,
+    }
+    .fmt(f)
+  }
+}
+
+impl<'a, I: Debug, E: Debug> std::error::Error for ParseError<'a, I, E> {}
+
+/// A [ParseError] displayed with the line and the column instead of the offset, returned by [ParseError::located].<br/>
+/// [ParseError::located]が返す、オフセットの代わりに行とカラムで表示される[ParseError]。
+pub struct Located<'e, 'a, I, E = ()>(&'e ParseError<'a, I, E>);
+
+impl<'e, 'a, I: Locate, E> Display for Located<'e, 'a, I, E> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let location = |error: &ParseError<'a, I, E>| match error.position() {
+      Some(position) => position.to_string(),
+      None => format!("offset {}", error.offset().unwrap_or(0)),
+    };
+    LocatedWith {
+      error: self.0,
+      location: &location,
+    }
+    .fmt(f)
+  }
+}
+
+/// Displays an error and its inner errors with the location returned by the function.
+struct LocatedWith<'e, 'a, I, E> {
+  error: &'e ParseError<'a, I, E>,
+  location: &'e dyn Fn(&ParseError<'a, I, E>) -> String,
+}
+
+impl<'e, 'a, I, E> LocatedWith<'e, 'a, I, E> {
+  fn inner(&self, error: &'e ParseError<'a, I, E>) -> Self {
+    Self {
+      error,
+      location: self.location,
+    }
+  }
+}
+
+impl<'e, 'a, I, E> Display for LocatedWith<'e, 'a, I, E> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let location = (self.location)(self.error);
+    match self.error {
       ParseError::Incomplete => write!(f, "Incomplete"),
-      ParseError::Mismatch {
-        ref message,
-        ref offset,
-        ..
-      } => write!(f, "Mismatch at {}: {}", offset, message),
-      ParseError::Conversion {
-        ref message,
-        ref offset,
-        ..
-      } => write!(f, "Conversion failed at {}: {}", offset, message),
+      ParseError::Mismatch { ref message, .. } => write!(f, "Mismatch at {}: {}", location, message),
+      ParseError::Conversion { ref message, .. } => write!(f, "Conversion failed at {}: {}", location, message),
       ParseError::Expect {
-        ref message,
-        ref offset,
-        ref inner,
-      } => write!(f, "{} at {}: {}", message, offset, inner),
+        ref message, ref inner, ..
+      } => write!(f, "Expect {} at {}: {}", message, location, self.inner(inner)),
+      ParseError::Expected { ref errors, .. } if errors.len() == 1 => write!(
+        f,
+        "{} at {}, (inner: {})",
+        self.error.expected_message(),
+        location,
+        self.inner(&errors[0])
+      ),
+      ParseError::Expected { .. } => write!(f, "{} at {}", self.error.expected_message(), location),
       ParseError::Custom {
         ref message,
-        ref offset,
         inner: Some(ref inner),
-      } => write!(f, "{} at {}, (inner: {})", message, offset, inner),
+        ..
+      } => write!(f, "{} at {}, (inner: {})", message, location, self.inner(inner)),
       ParseError::Custom {
         ref message,
-        ref offset,
         inner: None,
-      } => write!(f, "{} at {}", message, offset),
+        ..
+      } => write!(f, "{} at {}", message, location),
     }
   }
 }
 
-impl<'a> ParseError<'a, char> {
+impl<'a, E> ParseError<'a, char, E> {
   pub fn input_string(&self) -> Option<String> {
-    self.input().map(|chars| String::from_iter(chars))
+    self.input().map(String::from_iter)
   }
 }
 
-impl<'a> ParseError<'a, u8> {
+impl<'a, E> ParseError<'a, u8, E> {
   pub fn input_string(&self) -> Option<String> {
     match self.input() {
       Some(bytes) => match std::str::from_utf8(bytes) {
@@ -87,16 +187,25 @@ impl<'a> ParseError<'a, u8> {
 }
 
 impl<'a, I> ParseError<'a, I> {
+  /// Returns the error with the user error type `E`, for a parser that fails with `E`.<br/>
+  /// `E`で失敗するパーサーのために、ユーザーエラー型を`E`としたエラーを返す。
+  pub fn with_error_type<E>(self) -> ParseError<'a, I, E> {
+    self.map_payload(|_| None)
+  }
+}
+
+impl<'a, I, E> ParseError<'a, I, E> {
   pub fn input(&self) -> Option<&'a [I]> {
     match self {
       ParseError::Incomplete => None,
       ParseError::Mismatch {
         input, offset, length, ..
-      } => Some(&input[*offset..(*offset + length)]),
-      ParseError::Conversion {
+      }
+      | ParseError::Conversion {
         input, offset, length, ..
-      } => Some(&input[*offset..(*offset + length)]),
+      } => Some(input.slice(*offset, *offset + length)),
       ParseError::Expect { ref inner, .. } => inner.input(),
+      ParseError::Expected { ref errors, .. } => errors.iter().find_map(|e| e.input()),
       ParseError::Custom {
         inner: Some(ref inner), ..
       } => inner.input(),
@@ -104,64 +213,305 @@ impl<'a, I> ParseError<'a, I> {
     }
   }
 
-  pub fn is_expect(&self) -> bool {
+  /// Returns the whole input that the error refers to.<br/>
+  /// エラーが参照する入力全体を返す。
+  ///
+  /// The chunks of a [ChunkedInput](crate::core::ChunkedInput) are copied once.<br/>
+  /// [ChunkedInput](crate::core::ChunkedInput)のチャンクは一度だけコピーされる。
+  pub fn original_input(&self) -> Option<&'a [I]> {
+    self.source().map(|source| source.to_slice())
+  }
+
+  /// Returns the [Source] of the whole input that the error refers to.<br/>
+  /// エラーが参照する入力全体の[Source]を返す。
+  pub fn source(&self) -> Option<Source<'a, I>> {
     match self {
-      ParseError::Expect { .. } => true,
-      _ => false,
+      ParseError::Incomplete => None,
+      ParseError::Mismatch { input, .. } | ParseError::Conversion { input, .. } => Some(*input),
+      ParseError::Expect { ref inner, .. } => inner.source(),
+      ParseError::Expected { ref errors, .. } => errors.iter().find_map(|e| e.source()),
+      ParseError::Custom {
+        inner: Some(ref inner), ..
+      } => inner.source(),
+      ParseError::Custom { inner: None, .. } => None,
     }
   }
 
-  pub fn is_custom(&self) -> bool {
+  /// Returns the offset where the error occurred.<br/>
+  /// エラーが発生したオフセットを返す。
+  pub fn offset(&self) -> Option<usize> {
     match self {
-      ParseError::Custom { .. } => true,
-      _ => false,
+      ParseError::Incomplete => None,
+      ParseError::Mismatch { offset, .. }
+      | ParseError::Conversion { offset, .. }
+      | ParseError::Expect { offset, .. }
+      | ParseError::Expected { offset, .. }
+      | ParseError::Custom { offset, .. } => Some(*offset),
     }
   }
 
-  pub fn is_mismatch(&self) -> bool {
+  /// Returns the furthest offset reached, including the inner errors.<br/>
+  /// 内側のエラーを含めて、到達した最も先のオフセットを返す。
+  pub fn furthest_offset(&self) -> Option<usize> {
     match self {
-      ParseError::Mismatch { .. } => true,
-      _ => false,
+      ParseError::Incomplete => None,
+      ParseError::Mismatch { offset, .. }
+      | ParseError::Conversion { offset, .. }
+      | ParseError::Custom {
+        offset, inner: None, ..
+      } => Some(*offset),
+      ParseError::Expect { offset, inner, .. }
+      | ParseError::Custom {
+        offset,
+        inner: Some(inner),
+        ..
+      } => Some(inner.furthest_offset().map_or(*offset, |o| o.max(*offset))),
+      ParseError::Expected { offset, errors, .. } => Some(
+        errors
+          .iter()
+          .filter_map(|e| e.furthest_offset())
+          .fold(*offset, usize::max),
+      ),
     }
   }
 
-  pub fn is_conversion(&self) -> bool {
+  /// Returns the labels of what was expected.<br/>
+  /// 期待していたもののラベルを返す。
+  ///
+  /// - Expected: the labels of the candidates
+  /// - Expect: the expected name
+  /// - Mismatch: the label given by [ParseError::of_expected_mismatch], otherwise an empty list
+  /// - Custom: the message
+  /// - Conversion, Incomplete: an empty list
+  pub fn expected(&self) -> Vec<String> {
     match self {
-      ParseError::Conversion { .. } => true,
-      _ => false,
+      ParseError::Incomplete | ParseError::Conversion { .. } => vec![],
+      ParseError::Expected { expected, .. } => expected.clone(),
+      ParseError::Mismatch { expected, .. } => expected.iter().cloned().collect(),
+      ParseError::Expect { message, .. } | ParseError::Custom { message, .. } => vec![message.clone()],
     }
   }
 
-  pub fn is_in_complete(&self) -> bool {
+  /// Merges the failures of two alternatives.<br/>
+  /// 二つの選択肢の失敗を併合する。
+  ///
+  /// The error that reached the further offset wins. If both stopped at the same offset, their expected labels are
+  /// merged into an `Expected` error. An `Incomplete` error, which ran out of input, wins in either order.<br/>
+  /// より先のオフセットまで到達したエラーを採用する。同じオフセットで止まった場合は、期待するラベルを`Expected`エラーに併合する。
+  /// 入力が尽きた`Incomplete`エラーは、順序に関わらず採用される。
+  pub fn merge(self, other: Self) -> Self {
+    match (self.furthest_offset(), other.furthest_offset()) {
+      (None, _) => self,
+      (_, None) => other,
+      (Some(o1), Some(o2)) if o1 > o2 => self,
+      (Some(o1), Some(o2)) if o1 == o2 => {
+        let mut expected = self.expected();
+        for label in other.expected() {
+          if !expected.contains(&label) {
+            expected.push(label);
+          }
+        }
+        let mut errors = match self {
+          ParseError::Expected { errors, .. } if errors.len() > 1 => errors,
+          e => vec![e],
+        };
+        errors.push(other);
+        ParseError::Expected {
+          offset: o1,
+          expected,
+          errors,
+        }
+      }
+      _ => other,
+    }
+  }
+
+  /// Returns the user error, including the inner errors.<br/>
+  /// 内側のエラーを含めて、ユーザーエラーを返す。
+  pub fn payload(&self) -> Option<&E> {
     match self {
-      ParseError::Incomplete => true,
-      _ => false,
+      ParseError::Incomplete | ParseError::Mismatch { .. } => None,
+      ParseError::Conversion { payload, .. } => payload.as_ref(),
+      ParseError::Expect { inner, .. } => inner.payload(),
+      ParseError::Expected { errors, .. } => errors.iter().find_map(|e| e.payload()),
+      ParseError::Custom { payload, inner, .. } => {
+        payload.as_ref().or_else(|| inner.as_ref().and_then(|e| e.payload()))
+      }
     }
   }
 
-  pub fn of_expect(offset: usize, inner: Box<ParseError<'a, I>>, message: String) -> Self {
+  /// Attaches a user error to a `Conversion` or `Custom` error. The other errors are returned as they are.<br/>
+  /// `Conversion`または`Custom`エラーにユーザーエラーを付加する。その他のエラーはそのまま返す。
+  pub fn with_payload(mut self, error: E) -> Self {
+    if let ParseError::Conversion { payload, .. } | ParseError::Custom { payload, .. } = &mut self {
+      *payload = Some(error);
+    }
+    self
+  }
+
+  /// Returns the error whose user errors are converted by the function, which drops a user error by `None`.<br/>
+  /// ユーザーエラーを関数で変換したエラーを返す。関数が`None`を返したユーザーエラーは捨てられる。
+  pub fn map_payload<E2, F>(self, f: F) -> ParseError<'a, I, E2>
+  where
+    F: Fn(E) -> Option<E2>, {
+    self.map_payload_with(&f)
+  }
+
+  fn map_payload_with<E2>(self, f: &dyn Fn(E) -> Option<E2>) -> ParseError<'a, I, E2> {
+    match self {
+      ParseError::Incomplete => ParseError::Incomplete,
+      ParseError::Mismatch {
+        input,
+        offset,
+        length,
+        message,
+        expected,
+      } => ParseError::Mismatch {
+        input,
+        offset,
+        length,
+        message,
+        expected,
+      },
+      ParseError::Conversion {
+        input,
+        offset,
+        length,
+        message,
+        payload,
+      } => ParseError::Conversion {
+        input,
+        offset,
+        length,
+        message,
+        payload: payload.and_then(f),
+      },
+      ParseError::Expect { offset, inner, message } => ParseError::Expect {
+        offset,
+        inner: Box::new(inner.map_payload_with(f)),
+        message,
+      },
+      ParseError::Expected {
+        offset,
+        expected,
+        errors,
+      } => ParseError::Expected {
+        offset,
+        expected,
+        errors: errors.into_iter().map(|e| e.map_payload_with(f)).collect(),
+      },
+      ParseError::Custom {
+        offset,
+        inner,
+        message,
+        payload,
+      } => ParseError::Custom {
+        offset,
+        inner: inner.map(|e| Box::new(e.map_payload_with(f))),
+        message,
+        payload: payload.and_then(f),
+      },
+    }
+  }
+
+  pub(crate) fn expected_message(&self) -> String {
+    let labels = self
+      .expected()
+      .iter()
+      .map(|label| format!("`{}`", label))
+      .collect::<Vec<_>>();
+    if labels.is_empty() {
+      "unexpected input".to_string()
+    } else if labels.len() == 1 {
+      format!("expected {}", labels[0])
+    } else {
+      format!("expected one of: {}", labels.join(", "))
+    }
+  }
+
+  pub fn is_expect(&self) -> bool {
+    matches!(self, ParseError::Expect { .. })
+  }
+
+  pub fn is_expected(&self) -> bool {
+    matches!(self, ParseError::Expected { .. })
+  }
+
+  pub fn is_custom(&self) -> bool {
+    matches!(self, ParseError::Custom { .. })
+  }
+
+  pub fn is_mismatch(&self) -> bool {
+    matches!(self, ParseError::Mismatch { .. })
+  }
+
+  pub fn is_conversion(&self) -> bool {
+    matches!(self, ParseError::Conversion { .. })
+  }
+
+  pub fn is_in_complete(&self) -> bool {
+    matches!(self, ParseError::Incomplete)
+  }
+
+  pub fn of_expect(offset: usize, inner: Box<ParseError<'a, I, E>>, message: String) -> Self {
     ParseError::Expect { offset, inner, message }
   }
 
-  pub fn of_custom(offset: usize, inner: Option<Box<ParseError<'a, I>>>, message: String) -> Self {
-    ParseError::Custom { offset, inner, message }
+  pub fn of_expected(offset: usize, expected: Vec<String>, errors: Vec<ParseError<'a, I, E>>) -> Self {
+    ParseError::Expected {
+      offset,
+      expected,
+      errors,
+    }
+  }
+
+  pub fn of_custom(offset: usize, inner: Option<Box<ParseError<'a, I, E>>>, message: String) -> Self {
+    ParseError::Custom {
+      offset,
+      inner,
+      message,
+      payload: None,
+    }
   }
 
-  pub fn of_mismatch(input: &'a [I], offset: usize, length: usize, message: String) -> Self {
+  pub fn of_mismatch(input: impl Input<'a, I>, offset: usize, length: usize, message: String) -> Self {
     ParseError::Mismatch {
-      input,
+      input: input.into_source(),
       offset,
       length,
       message,
+      expected: None,
+    }
+  }
+
+  /// Returns a `Mismatch` error that records the label of what was expected.<br/>
+  /// 期待していたもののラベルを記録する`Mismatch`エラーを返す。
+  ///
+  /// The message is `expect {expected}, found: {found}`.<br/>
+  /// メッセージは`expect {expected}, found: {found}`となる。
+  pub fn of_expected_mismatch(
+    input: impl Input<'a, I>,
+    offset: usize,
+    length: usize,
+    expected: String,
+    found: String,
+  ) -> Self {
+    ParseError::Mismatch {
+      input: input.into_source(),
+      offset,
+      length,
+      message: format!("expect {}, found: {}", expected, found),
+      expected: Some(expected),
     }
   }
 
-  pub fn of_conversion(input: &'a [I], offset: usize, length: usize, message: String) -> Self {
+  pub fn of_conversion(input: impl Input<'a, I>, offset: usize, length: usize, message: String) -> Self {
     ParseError::Conversion {
-      input,
+      input: input.into_source(),
       offset,
       length,
       message,
+      payload: None,
     }
   }
 
@@ -169,3 +519,24 @@ impl<'a, I> ParseError<'a, I> {
     ParseError::Incomplete
   }
 }
+
+impl<'a, I: Locate, E> ParseError<'a, I, E> {
+  /// Returns the [Position] where the error occurred.<br/>
+  /// エラーが発生した[Position]を返す。
+  ///
+  /// Returns `None` if the error does not refer to the input.<br/>
+  /// エラーが入力を参照していない場合は`None`を返す。
+  pub fn position(&self) -> Option<Position> {
+    let offset = self.offset()?;
+    self.original_input().map(|input| I::locate(input, offset))
+  }
+
+  /// Returns the error displayed with the line and the column instead of the offset.<br/>
+  /// オフセットの代わりに行とカラムで表示されるエラーを返す。
+  ///
+  /// The `Display` of [ParseError] itself prints the offset, since not every element type can be located.<br/>
+  /// すべての要素型の位置を特定できるわけではないため、[ParseError]自体の`Display`はオフセットを表示する。
+  pub fn located(&self) -> Located<'_, 'a, I, E> {
+    Located(self)
+  }
+}
@@ -4,7 +4,7 @@ use crate::core::CommittedStatus;
 /// The enum type representing the parse result.<br/>
 /// 解析結果を示す列挙型。
 #[derive(Debug, Clone)]
-pub enum ParseResult<'a, I, A> {
+pub enum ParseResult<'a, I, A, E = ()> {
   /// Success.<br/>
   /// 成功
   Success {
@@ -20,14 +20,14 @@ pub enum ParseResult<'a, I, A> {
   Failure {
     /// The cause when failure.<br/>
     /// 失敗の原因
-    error: ParseError<'a, I>,
+    error: ParseError<'a, I, E>,
     /// The commit status.<br/>
     /// コミット状態
     committed_status: CommittedStatus,
   },
 }
 
-impl<'a, I, A> ParseResult<'a, I, A> {
+impl<'a, I, A, E> ParseResult<'a, I, A, E> {
   /// Returns the parse result of success.<br/>
   /// 成功の解析結果を返します。
   ///
@@ -45,7 +45,7 @@ impl<'a, I, A> ParseResult<'a, I, A> {
   ///
   /// - error: a [ParsedError]
   /// - committed_status: a [CommittedStatus]
-  pub fn failed(error: ParseError<'a, I>, committed_status: CommittedStatus) -> Self {
+  pub fn failed(error: ParseError<'a, I, E>, committed_status: CommittedStatus) -> Self {
     ParseResult::Failure {
       error,
       committed_status,
@@ -56,17 +56,17 @@ impl<'a, I, A> ParseResult<'a, I, A> {
   /// 失敗の解析結果を返します。
   ///
   /// - error: a [ParsedError]
-  pub fn failed_with_uncommitted(error: ParseError<'a, I>) -> Self {
+  pub fn failed_with_uncommitted(error: ParseError<'a, I, E>) -> Self {
     Self::failed(error, CommittedStatus::Uncommitted)
   }
 
-  pub fn failed_with_commit(error: ParseError<'a, I>) -> Self {
+  pub fn failed_with_commit(error: ParseError<'a, I, E>) -> Self {
     Self::failed(error, CommittedStatus::Committed)
   }
 
   /// Convert [ParsedResult] to [Result].<br/>
   /// [ParsedResult]を[Result]に変換する。
-  pub fn to_result(self) -> Result<A, ParseError<'a, I>> {
+  pub fn to_result(self) -> Result<A, ParseError<'a, I, E>> {
     match self {
       ParseResult::Failure { error, .. } => Err(error),
       ParseResult::Success { value, .. } => Ok(value),
@@ -102,7 +102,7 @@ impl<'a, I, A> ParseResult<'a, I, A> {
 
   /// Return the result of the failed parsing.<br/>
   /// 失敗した解析結果を返す。
-  pub fn failure(self) -> Option<ParseError<'a, I>> {
+  pub fn failure(self) -> Option<ParseError<'a, I, E>> {
     match self {
       ParseResult::Failure { error, .. } => Some(error),
       ParseResult::Success { .. } => None,
@@ -148,9 +148,9 @@ impl<'a, I, A> ParseResult<'a, I, A> {
     }
   }
 
-  pub fn flat_map<B, F>(self, f: F) -> ParseResult<'a, I, B>
+  pub fn flat_map<B, F>(self, f: F) -> ParseResult<'a, I, B, E>
   where
-    F: Fn(A, usize) -> ParseResult<'a, I, B>, {
+    F: Fn(A, usize) -> ParseResult<'a, I, B, E>, {
     match self {
       ParseResult::Success { value, length } => f(value, length),
       ParseResult::Failure {
@@ -163,7 +163,7 @@ impl<'a, I, A> ParseResult<'a, I, A> {
     }
   }
 
-  pub fn map<B, F>(self, f: F) -> ParseResult<'a, I, B>
+  pub fn map<B, F>(self, f: F) -> ParseResult<'a, I, B, E>
   where
     F: Fn(A, usize) -> (B, usize), {
     self.flat_map(|value, length| {
@@ -174,7 +174,7 @@ impl<'a, I, A> ParseResult<'a, I, A> {
 
   pub fn map_err<F>(self, f: F) -> Self
   where
-    F: Fn(ParseError<'a, I>) -> ParseError<'a, I>, {
+    F: Fn(ParseError<'a, I, E>) -> ParseError<'a, I, E>, {
     match self {
       ParseResult::Failure {
         error: e,
@@ -1,13 +1,113 @@
+use crate::core::{Elements, Input, Locate, ParseError, Position, Source, TraceEvent, TraceOutcome, Tracer, UserError};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+static NEXT_MEMO_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// The user error of a failure shared in a run, whose type is given back when the failure is taken out.
+type SharedPayload = Rc<dyn Any>;
+
+/// The memo table of a parser, keyed by the offset and the end of the input.
+///
+/// The end is a part of the key because a parser can run on a view limited by `ParseState::limit` at the same
+/// offset, where it sees less input.
+pub(crate) type MemoTable<T> = HashMap<(usize, usize), T>;
+
+/// A memo table whose type of the entries is erased. It is given back by the [MemoKey] that created it.
+trait ErasedMemoTable {}
+
+impl<T> ErasedMemoTable for MemoTable<T> {}
+
+/// The key of the memo tables of a parser, whose entries are of the type `T`.<br/>
+/// エントリが型`T`であるパーサーのメモ表のキー。
+///
+/// Every key has its own id and is neither `Clone` nor `Copy`, so the table of an id always holds the entries of the
+/// type of its key.<br/>
+/// キーはそれぞれ固有のIDを持ち、`Clone`でも`Copy`でもないため、IDの表は常にキーの型のエントリを保持する。
+pub(crate) struct MemoKey<T> {
+  id: usize,
+  marker: PhantomData<fn() -> T>,
+}
+
+impl<T> MemoKey<T> {
+  pub(crate) fn new() -> Self {
+    // SAFETY: The id is new, so this is the only key of the id.
+    unsafe { Self::of_id(next_memo_id()) }
+  }
+
+  /// Returns the key of an id given by [next_memo_id].
+  ///
+  /// # Safety
+  ///
+  /// Every key of the id must be of the same type `T` but for its lifetimes. They are fixed in a run, as the lifetime of
+  /// the input is that of the state, and a lent value is borrowed only while it is lent.
+  pub(crate) unsafe fn of_id(id: usize) -> Self {
+    Self {
+      id,
+      marker: PhantomData,
+    }
+  }
+}
+
+/// Returns a new id of memo tables and lent values.
+pub(crate) fn next_memo_id() -> usize {
+  NEXT_MEMO_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// 一回の解析の実行全体で共有される状態。
+struct ParseContext<'a, I> {
+  farthest_offset: Cell<Option<usize>>,
+  failure_count: Cell<usize>,
+  end_reached: Cell<bool>,
+  farthest_failure: RefCell<Option<ParseError<'a, I, SharedPayload>>>,
+  recovered_errors: RefCell<Vec<ParseError<'a, I, SharedPayload>>>,
+  tracer: RefCell<Option<Rc<RefCell<dyn Tracer + 'a>>>>,
+  memo_tables: RefCell<HashMap<usize, Box<dyn ErasedMemoTable + 'a>>>,
+  lent_values: RefCell<HashMap<usize, *const ()>>,
+}
+
+/// Shares the user error of a failure, to keep it in the context of the run.
+fn share<'a, I, E: UserError>(error: ParseError<'a, I, E>) -> ParseError<'a, I, SharedPayload> {
+  error.map_payload(|payload| Some(Rc::new(payload) as SharedPayload))
+}
+
+/// Gives back the type of a shared user error. A user error of another type, which comes from a parser of another
+/// user error type, is dropped.
+fn restore<'a, I, E: UserError>(error: ParseError<'a, I, SharedPayload>) -> ParseError<'a, I, E> {
+  error.map_payload(|payload| payload.downcast_ref::<E>().cloned())
+}
+
 /// 現在の解析状態を示す構造体。
 #[derive(Clone)]
 pub struct ParseState<'a, I> {
-  input: &'a [I],
+  input: Source<'a, I>,
   offset: usize,
+  limited: bool,
+  context: Rc<ParseContext<'a, I>>,
 }
 
 impl<'a, I> ParseState<'a, I> {
-  pub fn new(input: &'a [I], offset: usize) -> Self {
-    Self { input, offset }
+  pub fn new(input: impl Input<'a, I>, offset: usize) -> Self {
+    Self {
+      input: input.into_source(),
+      offset,
+      limited: false,
+      context: Rc::new(ParseContext {
+        farthest_offset: Cell::new(None),
+        failure_count: Cell::new(0),
+        end_reached: Cell::new(false),
+        farthest_failure: RefCell::new(None),
+        recovered_errors: RefCell::new(vec![]),
+        tracer: RefCell::new(None),
+        memo_tables: RefCell::new(HashMap::new()),
+        lent_values: RefCell::new(HashMap::new()),
+      }),
+    }
   }
 
   pub fn last_offset(&self) -> Option<usize> {
@@ -23,14 +123,310 @@ impl<'a, I> ParseState<'a, I> {
   }
 
   pub fn add_offset(&self, num_chars: usize) -> ParseState<'a, I> {
-    Self::new(self.input, self.offset + num_chars)
+    Self {
+      input: self.input,
+      offset: self.offset + num_chars,
+      limited: self.limited,
+      context: self.context.clone(),
+    }
+  }
+
+  /// Returns the state that sees only the next `n` elements of the input.<br/>
+  /// 入力の次の`n`要素だけを参照する状態を返す。
+  ///
+  /// The offsets stay absolute, so the errors in the limited state refer to the same positions. The end of the
+  /// limited state is not the end of the input, so looking at it is not recorded by
+  /// [ParseState::mark_end_reached].<br/>
+  /// オフセットは絶対位置のままなので、制限された状態でのエラーは同じ位置を参照する。制限された状態の終端は入力の終端
+  /// ではないため、それを参照しても[ParseState::mark_end_reached]で記録されない。
+  pub fn limit(&self, n: usize) -> ParseState<'a, I> {
+    Self {
+      input: self.input.truncate(self.offset + n),
+      offset: self.offset,
+      limited: true,
+      context: self.context.clone(),
+    }
   }
 
+  /// Returns the rest of the input as a slice.<br/>
+  /// 入力の残りをスライスとして返す。
+  ///
+  /// The chunks of a [ChunkedInput](crate::core::ChunkedInput) are copied once, so the parsers look at the input by
+  /// [ParseState::chunk] or [ParseState::elements] instead.<br/>
+  /// [ChunkedInput](crate::core::ChunkedInput)のチャンクは一度コピーされるため、パーサーは代わりに[ParseState::chunk]や
+  /// [ParseState::elements]で入力を参照する。
   pub fn input(&self) -> &'a [I] {
-    &self.input[self.offset..]
+    &self.original_input()[self.offset.min(self.input.len())..]
+  }
+
+  /// Returns the contiguous elements from the next offset up to the end of the chunk that holds it, which is the rest
+  /// of a contiguous input.<br/>
+  /// 次のオフセットから、それを含むチャンクの終わりまでの連続した要素を返す。連続した入力では入力の残りとなる。
+  pub fn chunk(&self) -> &'a [I] {
+    self.input.chunk(self.offset)
+  }
+
+  /// Returns an iterator over the rest of the input, which walks the chunks without copying them.<br/>
+  /// 入力の残りのイテレータを返す。チャンクをコピーせずに走査する。
+  pub fn elements(&self) -> Elements<'a, I> {
+    self.input.iter(self.offset)
+  }
+
+  /// Returns the number of the elements from the next offset to the end.<br/>
+  /// 次のオフセットから終端までの要素の数を返す。
+  pub fn remaining_len(&self) -> usize {
+    self.input.len().saturating_sub(self.offset)
+  }
+
+  /// Returns the [Source] of the whole input, including the part already consumed, which the errors refer to.<br/>
+  /// エラーが参照する、解析済みの部分を含む入力全体の[Source]を返す。
+  pub fn source(&self) -> Source<'a, I> {
+    self.input
+  }
+
+  /// Returns the whole input, including the part already consumed.<br/>
+  /// 解析済みの部分を含む入力全体を返す。
+  ///
+  /// The chunks of a [ChunkedInput](crate::core::ChunkedInput) are copied once.<br/>
+  /// [ChunkedInput](crate::core::ChunkedInput)のチャンクは一度だけコピーされる。
+  pub fn original_input(&self) -> &'a [I] {
+    self.input.to_slice()
   }
 
   pub fn slice_with_len(&self, n: usize) -> &'a [I] {
-    &self.input[self.offset..self.offset + n]
+    self.input.slice(self.offset, self.offset + n)
+  }
+
+  /// Records that a parser looked at the end of the input.<br/>
+  /// パーサーが入力の終端を参照したことを記録する。
+  ///
+  /// An `Incomplete` failure and a failure at the end of the input are recorded by
+  /// [ParserRunner::run](crate::core::ParserRunner::run). The parsers that succeed after scanning up to the end, such
+  /// as `take_while0` or `end`, record it by themselves.<br/>
+  /// `Incomplete`の失敗と入力の終端での失敗は[ParserRunner::run](crate::core::ParserRunner::run)が記録する。
+  /// `take_while0`や`end`のように終端まで走査して成功するパーサーは自身で記録する。
+  pub fn mark_end_reached(&self) {
+    if !self.limited {
+      self.context.end_reached.set(true);
+    }
+  }
+
+  /// Returns whether a parser looked at the end of the input in the run.<br/>
+  /// 実行中にパーサーが入力の終端を参照したかどうかを返す。
+  pub fn is_end_reached(&self) -> bool {
+    self.context.end_reached.get()
+  }
+
+  /// Records a failure discarded by backtracking.<br/>
+  /// バックトラックによって捨てられた失敗を記録する。
+  ///
+  /// Only the failures that reached the farthest offset are kept, and the ones at the same offset are merged. The
+  /// failures behind the farthest offset are dropped after comparing their offsets only.<br/>
+  /// 最も先のオフセットに到達した失敗だけを保持し、同じオフセットの失敗は併合する。最も先のオフセットより手前の失敗は、
+  /// オフセットを比較するだけで破棄する。
+  pub fn record_failure<E: UserError>(&self, error: ParseError<'a, I, E>) {
+    self.record_shared_failure(share(error));
+  }
+
+  fn record_shared_failure(&self, error: ParseError<'a, I, SharedPayload>) {
+    let offset = match error.furthest_offset() {
+      Some(offset) => offset,
+      None => return,
+    };
+    let farthest_offset = self.context.farthest_offset.get();
+    if farthest_offset.is_some_and(|farthest_offset| offset < farthest_offset) {
+      return;
+    }
+    let mut farthest_failure = self.context.farthest_failure.borrow_mut();
+    let recorded = match farthest_failure.take() {
+      Some(current) if farthest_offset == Some(offset) => current.merge(error),
+      _ => error,
+    };
+    *farthest_failure = Some(recorded);
+    self.context.farthest_offset.set(Some(offset));
+    self.context.failure_count.set(self.context.failure_count.get() + 1);
+  }
+
+  /// Returns the number of the failures recorded so far, to tell whether a failure was recorded in between.<br/>
+  /// これまでに記録された失敗の数を返す。間に失敗が記録されたかどうかを判定するために使う。
+  pub fn recorded_failure_count(&self) -> usize {
+    self.context.failure_count.get()
+  }
+
+  /// Returns the farthest failure recorded so far.<br/>
+  /// これまでに記録された最も先の失敗を返す。
+  pub fn recorded_failure<E: UserError>(&self) -> Option<ParseError<'a, I, E>>
+  where
+    I: Clone, {
+    self.context.farthest_failure.borrow().clone().map(restore)
+  }
+
+  /// Records again a failure returned by [ParseState::recorded_failure], such as when a memoized result is reused.<br/>
+  /// メモ化された結果を再利用する場合などに、[ParseState::recorded_failure]が返した失敗を再び記録する。
+  ///
+  /// In a run, the farthest failure only moves forward and merges the failures at the same offset, so a failure that
+  /// does not reach beyond the farthest offset is already reflected, and is ignored.<br/>
+  /// 実行中、最も先の失敗は前方にのみ進み、同じオフセットの失敗を併合するため、最も先のオフセットを越えない失敗は既に
+  /// 反映されており、無視する。
+  pub fn replay_failure<E: UserError>(&self, error: ParseError<'a, I, E>) {
+    if error.furthest_offset() > self.context.farthest_offset.get() {
+      self.record_failure(error);
+    }
+  }
+
+  /// Returns the error that reached the farthest offset, out of the given error and the recorded failures.<br/>
+  /// 与えられたエラーと記録された失敗のうち、最も先のオフセットに到達したエラーを返す。
+  pub fn farthest_failure<E: UserError>(&self, error: ParseError<'a, I, E>) -> ParseError<'a, I, E> {
+    self.context.farthest_offset.set(None);
+    match (
+      error.furthest_offset(),
+      self.context.farthest_failure.borrow_mut().take(),
+    ) {
+      (Some(_), Some(recorded)) => restore(recorded).merge(error),
+      _ => error,
+    }
+  }
+
+  /// Records an error from which the parsing recovered.<br/>
+  /// 解析が回復したエラーを記録する。
+  pub fn record_recovered_error<E: UserError>(&self, error: ParseError<'a, I, E>) {
+    self.context.recovered_errors.borrow_mut().push(share(error));
+  }
+
+  /// Returns the number of the recorded recovered errors.<br/>
+  /// 記録された回復済みエラーの数を返す。
+  pub fn recovered_error_count(&self) -> usize {
+    self.context.recovered_errors.borrow().len()
+  }
+
+  /// Discards the recovered errors recorded after the given count, when the parsing backtracks.<br/>
+  /// バックトラックした際に、指定した数より後に記録された回復済みエラーを破棄する。
+  pub fn truncate_recovered_errors(&self, count: usize) {
+    self.context.recovered_errors.borrow_mut().truncate(count);
+  }
+
+  /// Returns the recovered errors recorded after the given count.<br/>
+  /// 指定した数より後に記録された回復済みエラーを返す。
+  pub fn recovered_errors_since<E: UserError>(&self, count: usize) -> Vec<ParseError<'a, I, E>>
+  where
+    I: Clone, {
+    self.context.recovered_errors.borrow()[count..]
+      .iter()
+      .cloned()
+      .map(restore)
+      .collect()
+  }
+
+  /// Takes the recorded recovered errors.<br/>
+  /// 記録された回復済みエラーを取り出す。
+  pub fn take_recovered_errors<E: UserError>(&self) -> Vec<ParseError<'a, I, E>> {
+    self.context.recovered_errors.take().into_iter().map(restore).collect()
+  }
+
+  /// Returns the entry memoized with the key at the next offset in the run.<br/>
+  /// 実行中に次のオフセットでキーによりメモ化されたエントリを返す。
+  pub(crate) fn memo<T: Clone + 'a>(&self, key: &MemoKey<T>) -> Option<T> {
+    let memo_tables = self.context.memo_tables.borrow();
+    let table = memo_tables.get(&key.id)?;
+    // SAFETY: The table of the id was inserted by `memoize` with the key of the id, whose entries are of the type `T`.
+    let table = unsafe { &*(table.as_ref() as *const dyn ErasedMemoTable as *const MemoTable<T>) };
+    table.get(&self.memo_offsets()).cloned()
+  }
+
+  /// Memoizes the entry with the key at the next offset, until the run finishes.<br/>
+  /// 実行が終了するまで、次のオフセットでキーによりエントリをメモ化する。
+  pub(crate) fn memoize<T: 'a>(&self, key: &MemoKey<T>, entry: T) {
+    let mut memo_tables = self.context.memo_tables.borrow_mut();
+    let table = memo_tables
+      .entry(key.id)
+      .or_insert_with(|| Box::new(MemoTable::<T>::new()));
+    // SAFETY: The table of the id was inserted just above or by an earlier call with the key of the id, whose entries
+    // are of the type `T`.
+    let table = unsafe { &mut *(table.as_mut() as *mut dyn ErasedMemoTable as *mut MemoTable<T>) };
+    table.insert(self.memo_offsets(), entry);
+  }
+
+  /// Returns the key of the memo tables at the next offset.
+  pub(crate) fn memo_offsets(&self) -> (usize, usize) {
+    (self.offset, self.input.len())
+  }
+
+  /// Lends the value with the key to the run while `f` runs, and returns the result of `f`.<br/>
+  /// `f`の実行中にキーで値を実行へ貸し出し、`f`の結果を返す。
+  pub(crate) fn lend<T, R>(&self, key: &MemoKey<T>, value: &T, f: impl FnOnce() -> R) -> R {
+    struct GiveBack<'c> {
+      lent_values: &'c RefCell<HashMap<usize, *const ()>>,
+      id: usize,
+      previous: Option<*const ()>,
+    }
+    impl Drop for GiveBack<'_> {
+      fn drop(&mut self) {
+        let mut lent_values = self.lent_values.borrow_mut();
+        match self.previous {
+          Some(previous) => lent_values.insert(self.id, previous),
+          None => lent_values.remove(&self.id),
+        };
+      }
+    }
+    let previous = self
+      .context
+      .lent_values
+      .borrow_mut()
+      .insert(key.id, value as *const T as *const ());
+    let _give_back = GiveBack {
+      lent_values: &self.context.lent_values,
+      id: key.id,
+      previous,
+    };
+    f()
+  }
+
+  /// Runs `f` with the value lent with the key by a running [ParseState::lend], if any, and returns the result.<br/>
+  /// 実行中の[ParseState::lend]がキーで貸し出した値があればそれで`f`を実行し、結果を返す。
+  pub(crate) fn with_lent<T, R>(&self, key: &MemoKey<T>, f: impl FnOnce(Option<&T>) -> R) -> R {
+    let value = self.context.lent_values.borrow().get(&key.id).copied();
+    // SAFETY: The value was lent by `lend` with the key of the id, so it is of the type `T`. `lend` gives it back when
+    // its frame ends, which is below this frame in the stack of the thread of the run, so the value outlives `f`.
+    f(value.map(|value| unsafe { &*(value as *const T) }))
+  }
+
+  /// Sets the [Tracer] of the parse run.<br/>
+  /// 解析の実行の[Tracer]を設定する。
+  pub fn set_tracer(&self, tracer: Option<Rc<RefCell<dyn Tracer + 'a>>>) {
+    *self.context.tracer.borrow_mut() = tracer;
+  }
+
+  /// Reports the entry of a named parser to the [Tracer], and returns the time of the entry if it is traced.<br/>
+  /// 名前付きパーサーの開始を[Tracer]に報告し、トレースされている場合は開始時刻を返す。
+  pub fn trace_enter(&self, name: &str) -> Option<Instant> {
+    let tracer = self.context.tracer.borrow();
+    let tracer = tracer.as_ref()?;
+    tracer.borrow_mut().trace(TraceEvent::Enter {
+      name,
+      offset: self.offset,
+    });
+    Some(Instant::now())
+  }
+
+  /// Reports the exit of a named parser entered at the time to the [Tracer].<br/>
+  /// 指定時刻に開始した名前付きパーサーの終了を[Tracer]に報告する。
+  pub fn trace_exit(&self, name: &str, entered: Instant, outcome: TraceOutcome) {
+    let elapsed = entered.elapsed();
+    if let Some(tracer) = self.context.tracer.borrow().as_ref() {
+      tracer.borrow_mut().trace(TraceEvent::Exit {
+        name,
+        offset: self.offset,
+        outcome,
+        elapsed,
+      });
+    }
+  }
+}
+
+impl<'a, I: Locate> ParseState<'a, I> {
+  /// Returns the [Position] of the next offset.<br/>
+  /// 次のオフセットの[Position]を返す。
+  pub fn position(&self) -> Position {
+    I::locate(self.input.to_slice(), self.offset)
   }
 }
@@ -1,24 +1,257 @@
-use crate::core::{ParseResult, ParseState};
-use std::rc::Rc;
+use crate::core::{ParseResult, ParseState, ParserRunner, Syntax};
 
-type Parse<'a, I, A> = dyn Fn(&ParseState<'a, I>) -> ParseResult<'a, I, A> + 'a;
+#[cfg(not(feature = "sync"))]
+type Parse<'a, I, A, E> = dyn Fn(&ParseState<'a, I>) -> ParseResult<'a, I, A, E> + 'a;
+#[cfg(feature = "sync")]
+type Parse<'a, I, A, E> = dyn Fn(&ParseState<'a, I>) -> ParseResult<'a, I, A, E> + Send + Sync + 'a;
 
-pub struct Parser<'a, I, A> {
-  pub(crate) method: Rc<Parse<'a, I, A>>,
+#[cfg(not(feature = "sync"))]
+type ParseFree<'a, I, A, E> = dyn for<'i> Fn(&ParseState<'i, I>) -> ParseResult<'i, I, A, E> + 'a;
+#[cfg(feature = "sync")]
+type ParseFree<'a, I, A, E> = dyn for<'i> Fn(&ParseState<'i, I>) -> ParseResult<'i, I, A, E> + Send + Sync + 'a;
+
+/// The pointer that shares the closure of a [Parser], which is `Arc` when the `sync` feature is enabled.<br/>
+/// [Parser]のクロージャを共有するポインタ。`sync`フィーチャーが有効な場合は`Arc`となる。
+#[cfg(not(feature = "sync"))]
+pub(crate) type Shared<T> = std::rc::Rc<T>;
+#[cfg(feature = "sync")]
+pub(crate) type Shared<T> = std::sync::Arc<T>;
+
+/// A marker trait of the values captured by a [Parser].<br/>
+/// [Parser]が捕捉する値のマーカートレイト。
+///
+/// It requires `Send + Sync` when the `sync` feature is enabled, so that a [Parser] can be shared across threads
+/// or stored in a `static`. Otherwise it is implemented for every type.<br/>
+/// `sync`フィーチャーが有効な場合は`Send + Sync`を要求し、[Parser]をスレッド間で共有したり`static`に格納したり
+/// できるようにする。それ以外の場合はすべての型に実装される。
+#[cfg(not(feature = "sync"))]
+pub trait ThreadSafe {}
+#[cfg(not(feature = "sync"))]
+impl<T: ?Sized> ThreadSafe for T {}
+#[cfg(feature = "sync")]
+pub trait ThreadSafe: Send + Sync {}
+#[cfg(feature = "sync")]
+impl<T: ?Sized + Send + Sync> ThreadSafe for T {}
+
+/// The closure of a [Parser], which parses the input of the lifetime `'a`, or of any lifetime.
+pub(crate) enum Method<'a, I, A, E> {
+  Bound(Shared<Parse<'a, I, A, E>>),
+  Free(Shared<ParseFree<'a, I, A, E>>),
 }
 
-impl<'a, I, A> Clone for Parser<'a, I, A> {
+impl<'a, I, A, E> Clone for Method<'a, I, A, E> {
+  fn clone(&self) -> Self {
+    match self {
+      Method::Bound(parse) => Method::Bound(parse.clone()),
+      Method::Free(parse) => Method::Free(parse.clone()),
+    }
+  }
+}
+
+/// A parser of the input `&'a [I]` that returns a value of `A`, or fails with the user error type `E`.<br/>
+/// 入力`&'a [I]`を解析し、`A`の値を返すか、ユーザーエラー型`E`で失敗するパーサー。
+///
+/// `E` is `()` for the parsers of the prelude. A parser given a user error by `custom` or `map_res` fails with its
+/// type, and [Parser::with_error_type] makes the other parsers combinable with it.<br/>
+/// プレリュードのパーサーの`E`は`()`となる。`custom`や`map_res`でユーザーエラーを与えたパーサーはその型で失敗し、
+/// [Parser::with_error_type]によって他のパーサーをそれと組み合わせられるようにする。
+///
+/// The lifetime `'a` is of the values captured by the closure. A parser whose output may borrow the input, for
+/// example `&'a [I]` returned by `take`, is bound to inputs of `'a`. The other parsers are free: they are generic
+/// over the lifetime of the input at call time, and parse inputs of any lifetime. A combinator is free when the
+/// parsers that it combines are free, see [Parser::is_free]. A free parser can be built once and stored, see
+/// [Parser::shorten] and [crate::core::Grammar].<br/>
+/// ライフタイム`'a`はクロージャが捕捉する値のライフタイムである。出力が入力を借用しうるパーサー、
+/// 例えば`take`が`&'a [I]`を返すパーサーは、`'a`の入力に束縛される。それ以外のパーサーは自由であり、
+/// 呼び出し時の入力のライフタイムについて総称的で、任意のライフタイムの入力を解析する。
+/// コンビネータは組み合わせるパーサーが自由であれば自由となる。[Parser::is_free]を参照。
+/// 自由なパーサーは一度だけ構築して保持できる。[Parser::shorten]と[crate::core::Grammar]を参照。
+pub struct Parser<'a, I, A, E = ()> {
+  pub(crate) method: Method<'a, I, A, E>,
+  pub(crate) syntax: Option<Shared<Syntax<'a, I>>>,
+}
+
+impl<'a, I, A, E> Clone for Parser<'a, I, A, E> {
   fn clone(&self) -> Self {
     Self {
       method: self.method.clone(),
+      syntax: self.syntax.clone(),
     }
   }
 }
 
-impl<'a, I, A> Parser<'a, I, A> {
-  pub fn new<F>(parse: F) -> Parser<'a, I, A>
+impl<'a, I, A, E> Parser<'a, I, A, E> {
+  /// Returns the parser of the closure, which is bound to inputs of the lifetime `'a`.<br/>
+  /// ライフタイム`'a`の入力に束縛される、クロージャのパーサーを返す。
+  pub fn new<F>(parse: F) -> Parser<'a, I, A, E>
+  where
+    F: Fn(&ParseState<'a, I>) -> ParseResult<'a, I, A, E> + ThreadSafe + 'a, {
+    Parser {
+      method: Method::Bound(Shared::new(parse)),
+      syntax: None,
+    }
+  }
+
+  /// Returns the free parser of the closure, which parses inputs of any lifetime.<br/>
+  /// 任意のライフタイムの入力を解析する、クロージャの自由なパーサーを返す。
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let length: Parser<u8, usize> = Parser::free(|parse_state| {
+  ///   let length = parse_state.remaining_len();
+  ///   ParseResult::successful(length, length)
+  /// });
+  ///
+  /// assert!(length.is_free());
+  /// assert_eq!(length.parse(b"abc").success(), Some(3));
+  /// ```
+  pub fn free<F>(parse: F) -> Parser<'a, I, A, E>
+  where
+    F: for<'i> Fn(&ParseState<'i, I>) -> ParseResult<'i, I, A, E> + ThreadSafe + 'a, {
+    Parser {
+      method: Method::Free(Shared::new(parse)),
+      syntax: None,
+    }
+  }
+
+  /// Returns whether the parser is free, that is, parses inputs of any lifetime.<br/>
+  /// パーサーが自由であるか、つまり任意のライフタイムの入力を解析するかどうかを返す。
+  pub fn is_free(&self) -> bool {
+    matches!(self.method, Method::Free(_))
+  }
+
+  /// Returns the parser described by the syntax.<br/>
+  /// 構文で記述したパーサーを返す。
+  ///
+  /// A parser made by [Parser::new] is [Syntax::Opaque] unless described.<br/>
+  /// [Parser::new]で作成したパーサーは、記述しない限り[Syntax::Opaque]となる。
+  pub fn with_syntax(self, syntax: Syntax<'a, I>) -> Self {
+    Self {
+      syntax: Some(Shared::new(syntax)),
+      ..self
+    }
+  }
+
+  /// Returns the syntax of the parser, which can be exported as EBNF or railroad diagrams.<br/>
+  /// EBNFや構文図として出力できる、パーサーの構文を返す。
+  pub fn syntax(&self) -> Syntax<'a, I> {
+    Syntax::of(&self.syntax).as_ref().clone()
+  }
+
+  pub(crate) fn with_syntax_node(self, syntax: Option<Shared<Syntax<'a, I>>>) -> Self {
+    Self { syntax, ..self }
+  }
+
+  /// Returns the closure of the parser if it is free.
+  pub(crate) fn as_free(&self) -> Option<FreeParser<'a, I, A, E>> {
+    match &self.method {
+      Method::Free(parse) => Some(FreeParser(parse.clone())),
+      Method::Bound(_) => None,
+    }
+  }
+
+  /// Returns the parser for inputs of a shorter lifetime if it is free, or `None` if it is bound.<br/>
+  /// 自由であればより短いライフタイムの入力に対するパーサーを返し、束縛されていれば`None`を返す。
+  ///
+  /// A parser is invariant in `'a`, so a free parser stored as `Parser<'static, ..>` is shortened to parse the inputs
+  /// of a shorter lifetime.<br/>
+  /// パーサーは`'a`について不変であるため、`Parser<'static, ..>`として保持した自由なパーサーは、
+  /// より短いライフタイムの入力を解析するために短縮される。
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// struct Digits {
+  ///   parser: Parser<'static, u8, Vec<u8>>,
+  /// }
+  ///
+  /// let digits = Digits {
+  ///   parser: elm_digit().of_many1(),
+  /// };
+  ///
+  /// let input = b"123".to_vec();
+  /// assert_eq!(digits.parser.shorten().unwrap().parse(&input).success(), Some(b"123".to_vec()));
+  /// ```
+  pub fn shorten<'b>(&self) -> Option<Parser<'b, I, A, E>>
   where
-    F: Fn(&ParseState<'a, I>) -> ParseResult<'a, I, A> + 'a, {
-    Parser { method: Rc::new(parse) }
+    'a: 'b, {
+    let parse: Shared<ParseFree<'b, I, A, E>> = match &self.method {
+      Method::Free(parse) => parse.clone(),
+      Method::Bound(_) => return None,
+    };
+    // SAFETY: `Syntax<'a, I>` only holds values that live for `'a`, and functions returning `Syntax<'a, I>`, so it
+    // is covariant in `'a` although the compiler cannot see it through the trait objects. The layouts of the two
+    // types differ only in lifetimes, which are erased.
+    let syntax = unsafe {
+      std::mem::transmute::<Option<Shared<Syntax<'a, I>>>, Option<Shared<Syntax<'b, I>>>>(self.syntax.clone())
+    };
+    Some(Parser {
+      method: Method::Free(parse),
+      syntax,
+    })
+  }
+}
+
+/// The closure of a free [Parser], which runs on inputs of any lifetime.
+pub(crate) struct FreeParser<'a, I, A, E>(Shared<ParseFree<'a, I, A, E>>);
+
+impl<'a, I, A, E> FreeParser<'a, I, A, E> {
+  /// Runs the parser on the input of the parse state, as [ParserRunner::run] does.
+  pub(crate) fn run<'i>(&self, parse_state: &ParseState<'i, I>) -> ParseResult<'i, I, A, E> {
+    mark_end_reached(parse_state, (self.0)(parse_state))
+  }
+}
+
+/// Marks the end of the input as reached if the parser failed there, and returns the result.
+pub(crate) fn mark_end_reached<'i, I, A, E>(
+  parse_state: &ParseState<'i, I>,
+  result: ParseResult<'i, I, A, E>,
+) -> ParseResult<'i, I, A, E> {
+  if let ParseResult::Failure { error, .. } = &result {
+    if error.offset().is_none_or(|offset| offset >= parse_state.source().len()) {
+      parse_state.mark_end_reached();
+    }
+  }
+  result
+}
+
+impl<'a, I: 'a, A: 'a> Parser<'a, I, A> {
+  /// Returns the parser that fails with the user error type `E`, to combine it with the parsers failing with `E`.<br/>
+  /// `E`で失敗するパーサーと組み合わせるために、ユーザーエラー型`E`で失敗するパーサーを返す。
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// #[derive(Debug, Clone, PartialEq)]
+  /// struct OutOfRange(u32);
+  ///
+  /// let digit = elm_digit().map(|c| (c - b'0') as u32);
+  /// let parser: Parser<u8, u32, OutOfRange> = digit.with_error_type().flat_map(|n| match n {
+  ///   0..=5 => successful(n).with_error_type(),
+  ///   _ => custom(OutOfRange(n)),
+  /// });
+  ///
+  /// let result: ParseResult<u8, u32, OutOfRange> = parser.parse(b"7");
+  ///
+  /// assert_eq!(result.failure().unwrap().payload(), Some(&OutOfRange(7)));
+  /// ```
+  pub fn with_error_type<E>(self) -> Parser<'a, I, A, E> {
+    let syntax = self.syntax.clone();
+    let parser = self;
+    crate::internal::combine!((parser) => move |parse_state| match parser.run(parse_state) {
+      ParseResult::Success { value, length } => ParseResult::successful(value, length),
+      ParseResult::Failure {
+        error,
+        committed_status,
+      } => ParseResult::failed(error.with_error_type(), committed_status),
+    })
+    .with_syntax_node(syntax)
   }
 }
@@ -1,10 +1,11 @@
+use crate::core::parser::ThreadSafe;
 use crate::core::parser_runner::ParserRunner;
 
 pub trait ParserFilter<'a>: ParserRunner<'a> {
   /// 解析結果をフィルターする[Parser]を返す。
   fn with_filter<F>(self, f: F) -> Self
   where
-    F: Fn(&Self::Output) -> bool + 'a,
+    F: Fn(&Self::Output) -> bool + ThreadSafe + 'a,
     Self::Input: 'a,
     Self::Output: 'a,
     Self: Sized;
@@ -12,7 +13,7 @@ pub trait ParserFilter<'a>: ParserRunner<'a> {
   /// 解析結果をフィルターする[Parser]を返す。
   fn with_filter_not<F>(self, f: F) -> Self
   where
-    F: Fn(&Self::Output) -> bool + 'a,
+    F: Fn(&Self::Output) -> bool + ThreadSafe + 'a,
     Self::Input: 'a,
     Self::Output: 'a,
     Self: Sized, {
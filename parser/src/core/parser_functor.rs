@@ -1,3 +1,4 @@
+use crate::core::parser::ThreadSafe;
 use crate::core::parser_pure::ParserPure;
 
 pub trait ParserFunctor<'a>: ParserPure<'a> {
@@ -5,7 +6,7 @@ pub trait ParserFunctor<'a>: ParserPure<'a> {
   /// 解析結果を変換する[Parser]を返す。
   fn map<B, F>(self, f: F) -> Self::P<'a, Self::Input, B>
   where
-    F: Fn(Self::Output) -> B + 'a,
+    F: Fn(Self::Output) -> B + ThreadSafe + 'a,
     Self::Input: 'a,
     Self::Output: Clone + 'a,
     B: Clone + 'a;
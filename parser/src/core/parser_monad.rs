@@ -1,3 +1,4 @@
+use crate::core::parser::ThreadSafe;
 use crate::core::parser_filter::ParserFilter;
 use crate::core::parser_functor::ParserFunctor;
 
@@ -6,7 +7,7 @@ pub trait ParserMonad<'a>: ParserFunctor<'a> + ParserFilter<'a> {
   /// Parserどうしの計算を何らかの形で結合したParserを返す。
   fn flat_map<B, F>(self, f: F) -> Self::P<'a, Self::Input, B>
   where
-    F: Fn(Self::Output) -> Self::P<'a, Self::Input, B> + 'a,
+    F: Fn(Self::Output) -> Self::P<'a, Self::Input, B> + ThreadSafe + 'a,
     Self::Input: 'a,
     Self::Output: 'a,
     B: 'a;
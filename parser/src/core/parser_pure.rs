@@ -1,3 +1,4 @@
+use crate::core::parser::ThreadSafe;
 use crate::core::parser_runner::ParserRunner;
 
 pub trait ParserPure<'a>: ParserRunner<'a> {
@@ -5,7 +6,7 @@ pub trait ParserPure<'a>: ParserRunner<'a> {
   /// 指定した値を返す[Parser]を返す。
   fn pure<F>(value: F) -> Self::P<'a, Self::Input, Self::Output>
   where
-    F: Fn() -> Self::Output + 'a,
+    F: Fn() -> Self::Output + ThreadSafe + 'a,
     Self::Input: 'a,
     Self::Output: 'a;
 }
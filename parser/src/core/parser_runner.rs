@@ -1,27 +1,72 @@
 use crate::core::ParserMonad;
-use crate::core::{ParseError, ParseResult, ParseState};
+use crate::core::{Input, ParseError, ParseResult, ParseState, Tracer};
 
 pub trait ParserRunner<'a> {
   type Input;
   type Output;
+  type Error;
   type P<'m, X, Y>: ParserMonad<'m, Input = X, Output = Y>
   where
     X: 'm;
 
   /// Analyze input value(for [ParseResult]).<br/>
   /// 入力を解析する。
-  fn parse(&self, input: &'a [Self::Input]) -> ParseResult<'a, Self::Input, Self::Output>;
+  fn parse(&self, input: impl Input<'a, Self::Input>) -> ParseResult<'a, Self::Input, Self::Output, Self::Error>;
 
   /// Analyze input value(for [Result]).<br/>
   /// 入力を解析する。
-  fn parse_as_result(&self, input: &'a [Self::Input]) -> Result<Self::Output, ParseError<'a, Self::Input>> {
+  fn parse_as_result(
+    &self,
+    input: impl Input<'a, Self::Input>,
+  ) -> Result<Self::Output, ParseError<'a, Self::Input, Self::Error>> {
     self.parse(input).to_result()
   }
 
+  /// Analyze the UTF-8 bytes of a string(for [ParseResult]).<br/>
+  /// 文字列のUTF-8バイト列を解析する。
+  ///
+  /// The offsets of the errors are byte offsets.<br/>
+  /// エラーのオフセットはバイトオフセットとなる。
+  fn parse_str(&self, input: &'a str) -> ParseResult<'a, Self::Input, Self::Output, Self::Error>
+  where
+    Self: ParserRunner<'a, Input = u8>, {
+    self.parse(input.as_bytes())
+  }
+
+  /// Analyze input value(for [ParseResult]), and returns it with the errors recovered by `recover_with`.<br/>
+  /// 入力を解析し、`recover_with`によって回復したエラーとともに返す。
+  #[allow(clippy::type_complexity)]
+  fn parse_with_errors(
+    &self,
+    input: impl Input<'a, Self::Input>,
+  ) -> (
+    ParseResult<'a, Self::Input, Self::Output, Self::Error>,
+    Vec<ParseError<'a, Self::Input, Self::Error>>,
+  );
+
+  /// Analyze input value(for [ParseResult]), reporting the events of the named parsers to the [Tracer].<br/>
+  /// 入力を解析し、名前付きパーサーのイベントを[Tracer]に報告する。
+  ///
+  /// The tracer is returned with the result, so that it can be inspected, as [crate::core::Trace].<br/>
+  /// トレーサーは結果とともに返されるため、[crate::core::Trace]のように検査できる。
+  ///
+  /// A `cache` hit returns the memoized result without running the parser again, so the named parsers inside it
+  /// report no events on the hit. Name the cached parser itself to trace the hits.<br/>
+  /// `cache`のヒットはパーサーを再実行せずにメモ化された結果を返すため、その内部の名前付きパーサーはヒット時にイベントを
+  /// 報告しない。ヒットをトレースするには、キャッシュしたパーサー自体に名前を付ける。
+  #[allow(clippy::type_complexity)]
+  fn parse_with_tracer<T>(
+    &self,
+    input: impl Input<'a, Self::Input>,
+    tracer: T,
+  ) -> (ParseResult<'a, Self::Input, Self::Output, Self::Error>, T)
+  where
+    T: Tracer + 'a;
+
   /// Analyze input value(for [ParseResult]).<br/>
   /// 入力を解析する。
   ///
   /// Requires [ParseState] argument.<br/>
   /// 引数に[ParseState]が必要です。
-  fn run(&self, param: &ParseState<'a, Self::Input>) -> ParseResult<'a, Self::Input, Self::Output>;
+  fn run(&self, param: &ParseState<'a, Self::Input>) -> ParseResult<'a, Self::Input, Self::Output, Self::Error>;
 }
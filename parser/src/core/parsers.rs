@@ -1,13 +1,14 @@
 use crate::core::parser_monad::ParserMonad;
-use crate::core::{CommittedStatus, ParseError};
+use crate::core::{CommittedStatus, ParseError, ThreadSafe, UserError};
 
 pub trait Parsers {
+  type Error: UserError;
   type P<'p, I, A>: ParserMonad<'p, Input = I, Output = A>
   where
     I: 'p,
     A: 'p;
 
-  fn parse<'a, 'b, I, A>(parser: &Self::P<'a, I, A>, input: &'b [I]) -> Result<A, ParseError<'a, I>>
+  fn parse<'a, 'b, I, A>(parser: &Self::P<'a, I, A>, input: &'b [I]) -> Result<A, ParseError<'a, I, Self::Error>>
   where
     A: 'a,
     'b: 'a;
@@ -18,39 +19,44 @@ pub trait Parsers {
 
   fn successful<'a, I, A>(value: A) -> Self::P<'a, I, A>
   where
-    A: Clone + 'a;
+    A: Clone + ThreadSafe + 'a;
 
   fn successful_lazy<'a, I, A, F>(value: F) -> Self::P<'a, I, A>
   where
-    F: Fn() -> A + 'a,
+    F: Fn() -> A + ThreadSafe + 'a,
     A: 'a;
 
-  fn failed<'a, I, A>(value: ParseError<'a, I>, committed: CommittedStatus) -> Self::P<'a, I, A>
+  fn failed<'a, I, A>(value: ParseError<'a, I, Self::Error>, committed: CommittedStatus) -> Self::P<'a, I, A>
   where
-    I: Clone + 'a,
+    I: Clone + ThreadSafe + 'a,
     A: 'a;
 
   fn failed_lazy<'a, I, A, F>(f: F) -> Self::P<'a, I, A>
   where
-    F: Fn() -> (ParseError<'a, I>, CommittedStatus) + 'a,
+    F: Fn() -> (ParseError<'a, I, Self::Error>, CommittedStatus) + ThreadSafe + 'a,
+    I: 'a,
+    A: 'a;
+
+  fn custom<'a, I, A>(error: Self::Error) -> Self::P<'a, I, A>
+  where
     I: 'a,
     A: 'a;
 
   fn filter<'a, I, A, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, A>
   where
-    F: Fn(&A) -> bool + 'a,
+    F: Fn(&A) -> bool + ThreadSafe + 'a,
     I: 'a,
     A: 'a;
 
   fn flat_map<'a, I, A, B, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
   where
-    F: Fn(A) -> Self::P<'a, I, B> + 'a,
+    F: Fn(A) -> Self::P<'a, I, B> + ThreadSafe + 'a,
     A: 'a,
     B: 'a;
 
   fn map<'a, I, A, B, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
   where
-    F: Fn(A) -> B + 'a,
+    F: Fn(A) -> B + ThreadSafe + 'a,
     A: 'a,
     B: Clone + 'a;
 }
@@ -0,0 +1,127 @@
+use crate::core::Element;
+use std::fmt;
+use std::fmt::Display;
+
+/// A structure representing a position in the input.<br/>
+/// 入力中の位置を表す構造体。
+///
+/// `line` and `column` are 1-based. The column counts characters, so a multi-byte UTF-8 character in a `u8` input
+/// occupies a single column.<br/>
+/// `line`と`column`は1始まり。カラムは文字単位で数えるため、`u8`入力中のUTF-8の複数バイト文字は1カラムとして扱われる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+  /// The offset of the element.<br/>
+  /// 要素のオフセット
+  pub offset: usize,
+  /// The line number.<br/>
+  /// 行番号
+  pub line: usize,
+  /// The column number.<br/>
+  /// カラム番号
+  pub column: usize,
+}
+
+impl Default for Position {
+  fn default() -> Self {
+    Self::new(0, 1, 1)
+  }
+}
+
+impl Display for Position {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "line {}, column {}", self.line, self.column)
+  }
+}
+
+impl Position {
+  /// Returns a [Position].<br/>
+  /// [Position]を返します。
+  pub fn new(offset: usize, line: usize, column: usize) -> Self {
+    Self { offset, line, column }
+  }
+
+  /// Returns the [Position] of the offset in the input.<br/>
+  /// 入力中のオフセットの[Position]を返します。
+  ///
+  /// `\n`, `\r\n` and a lone `\r` are treated as line breaks. An offset beyond the end of the input is clamped to
+  /// the end.<br/>
+  /// `\n`、`\r\n`、単独の`\r`を改行として扱う。入力の終端を越えるオフセットは終端に切り詰められる。
+  pub fn of<I>(input: &[I], offset: usize) -> Self
+  where
+    I: Element + Copy, {
+    Self::default().advance(input, offset)
+  }
+
+  /// Returns the [Position] of the offset in the input, counting from this position of the same input.<br/>
+  /// 同じ入力のこの位置から数えて、入力中のオフセットの[Position]を返します。
+  pub fn advance<I>(self, input: &[I], offset: usize) -> Self
+  where
+    I: Element + Copy, {
+    let end = offset.min(input.len());
+    let mut line = self.line;
+    let mut column = self.column;
+    let mut index = self.offset;
+    while index < end {
+      let e = input[index];
+      match e.to_char() {
+        '\n' => {
+          line += 1;
+          column = 1;
+        }
+        '\r' => {
+          if input.get(index + 1).map(|n| n.to_char()) != Some('\n') {
+            line += 1;
+            column = 1;
+          }
+        }
+        _ if e.is_char_boundary() => column += 1,
+        _ => (),
+      }
+      index += 1;
+    }
+    Self::new(offset, line, column)
+  }
+}
+
+/// A trait to locate the elements of the input in the source.<br/>
+/// 入力の要素をソース中で特定するためのトレイト。
+pub trait Locate: Sized {
+  /// Returns the [Position] of the offset in the input.<br/>
+  /// 入力中のオフセットの[Position]を返す。
+  fn locate(input: &[Self], offset: usize) -> Position;
+
+  /// Returns the number of columns of the elements in the input, up to the end of the line.<br/>
+  /// 入力中の要素の、行末までのカラム数を返す。
+  fn width(input: &[Self], offset: usize, length: usize) -> usize;
+
+  /// Returns the text of the line that contains the offset in the input.<br/>
+  /// 入力中のオフセットを含む行のテキストを返す。
+  fn line_text(input: &[Self], offset: usize) -> String;
+}
+
+impl<I: Element + Copy> Locate for I {
+  fn locate(input: &[Self], offset: usize) -> Position {
+    Position::of(input, offset)
+  }
+
+  fn width(input: &[Self], offset: usize, length: usize) -> usize {
+    let start = offset.min(input.len());
+    let end = (offset + length).min(input.len());
+    input[start..end]
+      .iter()
+      .take_while(|e| !matches!(e.to_char(), '\n' | '\r'))
+      .filter(|e| e.is_char_boundary())
+      .count()
+  }
+
+  fn line_text(input: &[Self], offset: usize) -> String {
+    let is_line_break = |e: &I| matches!(e.to_char(), '\n' | '\r');
+    let offset = offset.min(input.len());
+    let start = input[..offset].iter().rposition(is_line_break).map_or(0, |i| i + 1);
+    let end = input[offset..]
+      .iter()
+      .position(is_line_break)
+      .map_or(input.len(), |i| offset + i);
+    I::to_string_lossy(&input[start..end])
+  }
+}
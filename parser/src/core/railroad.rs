@@ -0,0 +1,343 @@
+use crate::core::syntax::{repetition, Node};
+use crate::core::{Element, Syntax};
+use std::fmt::Write;
+
+/// The radius of the curves, which is also the gap between the items of a sequence.
+const RADIUS: i64 = 10;
+/// The width of a char of the monospace font.
+const CHAR_WIDTH: i64 = 9;
+/// The half of the height of a box.
+const HALF_HEIGHT: i64 = 11;
+/// The vertical gap between the branches of a choice, or between an item and its loop.
+const GAP: i64 = 10;
+/// The margin around a diagram.
+const MARGIN: i64 = 20;
+/// The height of the title of a diagram.
+const TITLE_HEIGHT: i64 = 30;
+
+const STYLE: &str = "path { stroke: #333; stroke-width: 1.5; fill: none; } \
+rect { stroke: #333; stroke-width: 1.5; fill: #fff; } \
+rect.terminal { fill: #ffe; } rect.class { fill: #eef; } rect.opaque { stroke-dasharray: 4 2; } \
+text { font: 14px monospace; text-anchor: middle; } text.rule { font-weight: bold; text-anchor: start; } \
+text.label { font-size: 11px; }";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BoxKind {
+  Terminal,
+  NonTerminal,
+  Class,
+  Opaque,
+}
+
+/// An item of a railroad diagram, drawn from the left end of its baseline to the right end.
+enum Item {
+  Box(String, BoxKind),
+  Skip,
+  Sequence(Vec<Item>),
+  Choice(Vec<Item>),
+  Loop {
+    item: Box<Item>,
+    separator: Option<Box<Item>>,
+    label: Option<String>,
+  },
+}
+
+impl Item {
+  fn of(node: &Node) -> Self {
+    match node {
+      Node::Empty => Item::Skip,
+      Node::Terminal(text) => Item::Box(text.clone(), BoxKind::Terminal),
+      Node::Class(text) => Item::Box(text.clone(), BoxKind::Class),
+      Node::Rule(name) => Item::Box(name.clone(), BoxKind::NonTerminal),
+      Node::Opaque(note) if note.is_empty() => Item::Box("?".to_string(), BoxKind::Opaque),
+      Node::Opaque(note) => Item::Box(note.clone(), BoxKind::Opaque),
+      Node::Sequence(nodes) => Item::Sequence(nodes.iter().map(Item::of).collect()),
+      Node::Choice(nodes) => Item::Choice(nodes.iter().map(Item::of).collect()),
+      Node::Repeat { max: Some(0), .. } => Item::Skip,
+      Node::Repeat {
+        node,
+        min: 1,
+        max: Some(1),
+        ..
+      } => Item::of(node),
+      Node::Repeat {
+        node,
+        min: 0,
+        max: Some(1),
+        separator: None,
+      } => Item::Choice(vec![Item::of(node), Item::Skip]),
+      Node::Repeat {
+        node,
+        min,
+        max,
+        separator,
+      } => {
+        let label = match (min, max) {
+          (0 | 1, None) => None,
+          _ => Some(repetition(*min, *max)),
+        };
+        let item = Item::Loop {
+          item: Box::new(Item::of(node)),
+          separator: separator.as_ref().map(|separator| Box::new(Item::of(separator))),
+          label,
+        };
+        if *min == 0 {
+          Item::Choice(vec![item, Item::Skip])
+        } else {
+          item
+        }
+      }
+    }
+  }
+
+  fn width(&self) -> i64 {
+    match self {
+      Item::Box(text, _) => text.chars().count() as i64 * CHAR_WIDTH + 2 * RADIUS,
+      Item::Skip => 0,
+      Item::Sequence(items) => items.iter().map(Item::width).sum::<i64>() + RADIUS * (items.len() as i64 - 1).max(0),
+      Item::Choice(items) => items.iter().map(Item::width).max().unwrap_or(0) + 4 * RADIUS,
+      Item::Loop { item, separator, .. } => {
+        item
+          .width()
+          .max(separator.as_ref().map_or(0, |separator| separator.width()))
+          + 2 * RADIUS
+      }
+    }
+  }
+
+  fn up(&self) -> i64 {
+    match self {
+      Item::Box(..) => HALF_HEIGHT,
+      Item::Skip => 0,
+      Item::Sequence(items) => items.iter().map(Item::up).max().unwrap_or(0),
+      Item::Choice(items) => items.first().map_or(0, Item::up),
+      Item::Loop { item, .. } => item.up(),
+    }
+  }
+
+  fn down(&self) -> i64 {
+    match self {
+      Item::Box(..) => HALF_HEIGHT,
+      Item::Skip => 0,
+      Item::Sequence(items) => items.iter().map(Item::down).max().unwrap_or(0),
+      Item::Choice(items) => match (self.branch_offsets().last(), items.last()) {
+        (Some(offset), Some(item)) => offset + item.down(),
+        _ => 0,
+      },
+      Item::Loop { separator, label, .. } => {
+        let separator = separator.as_ref().map_or(0, |separator| separator.down());
+        self.loop_offset() + separator + if label.is_some() { 2 * GAP } else { 0 }
+      }
+    }
+  }
+
+  /// Returns the offsets of the baselines of the branches of a choice from the baseline of the choice.
+  fn branch_offsets(&self) -> Vec<i64> {
+    let mut offsets: Vec<i64> = vec![];
+    if let Item::Choice(items) = self {
+      for (index, item) in items.iter().enumerate() {
+        let offset = match index {
+          0 => 0,
+          _ => (offsets[index - 1] + items[index - 1].down() + GAP + item.up()).max(offsets[index - 1] + 2 * RADIUS),
+        };
+        offsets.push(offset);
+      }
+    }
+    offsets
+  }
+
+  /// Returns the offset of the line of a loop from the baseline of the loop.
+  fn loop_offset(&self) -> i64 {
+    match self {
+      Item::Loop { item, separator, .. } => {
+        let separator = separator.as_ref().map_or(0, |separator| separator.up());
+        (item.down() + GAP + separator).max(2 * RADIUS)
+      }
+      _ => 0,
+    }
+  }
+
+  fn draw(&self, x: i64, y: i64, out: &mut String) {
+    match self {
+      Item::Box(text, kind) => {
+        let width = self.width();
+        let (class, rx) = match kind {
+          BoxKind::Terminal => ("terminal", RADIUS),
+          BoxKind::NonTerminal => ("nonterminal", 0),
+          BoxKind::Class => ("class", 0),
+          BoxKind::Opaque => ("opaque", 0),
+        };
+        let _ = writeln!(
+          out,
+          r#"<rect class="{}" x="{}" y="{}" width="{}" height="{}" rx="{}"/>"#,
+          class,
+          x,
+          y - HALF_HEIGHT,
+          width,
+          2 * HALF_HEIGHT,
+          rx
+        );
+        let _ = writeln!(
+          out,
+          r#"<text x="{}" y="{}">{}</text>"#,
+          x + width / 2,
+          y + 4,
+          escape(text)
+        );
+      }
+      Item::Skip => (),
+      Item::Sequence(items) => {
+        let mut x = x;
+        for (index, item) in items.iter().enumerate() {
+          if index > 0 {
+            path(out, format!("M{} {} h{}", x, y, RADIUS));
+            x += RADIUS;
+          }
+          item.draw(x, y, out);
+          x += item.width();
+        }
+      }
+      Item::Choice(items) => {
+        let width = self.width();
+        for (item, offset) in items.iter().zip(self.branch_offsets()) {
+          let (left, right) = (x + 2 * RADIUS, x + 2 * RADIUS + item.width());
+          if offset == 0 {
+            path(out, format!("M{} {} h{}", x, y, 2 * RADIUS));
+            path(out, format!("M{} {} H{}", right, y, x + width));
+          } else {
+            let y1 = y + offset;
+            path(
+              out,
+              format!(
+                "M{} {} a{r} {r} 0 0 1 {r} {r} V{} a{r} {r} 0 0 0 {r} {r}",
+                x,
+                y,
+                y1 - RADIUS,
+                r = RADIUS
+              ),
+            );
+            path(
+              out,
+              format!(
+                "M{} {} H{} a{r} {r} 0 0 0 {r} -{r} V{} a{r} {r} 0 0 1 {r} -{r}",
+                right,
+                y1,
+                x + width - 2 * RADIUS,
+                y + RADIUS,
+                r = RADIUS
+              ),
+            );
+          }
+          item.draw(left, y + offset, out);
+        }
+      }
+      Item::Loop { item, separator, label } => {
+        let width = self.width();
+        let y1 = y + self.loop_offset();
+        path(out, format!("M{} {} h{}", x, y, RADIUS));
+        item.draw(x + RADIUS, y, out);
+        path(out, format!("M{} {} H{}", x + RADIUS + item.width(), y, x + width));
+        let separator_width = separator.as_ref().map_or(0, |separator| separator.width());
+        path(
+          out,
+          format!(
+            "M{} {} a{r} {r} 0 0 1 {r} {r} V{} a{r} {r} 0 0 1 -{r} {r} H{}",
+            x + width - RADIUS,
+            y,
+            y1 - RADIUS,
+            x + RADIUS + separator_width,
+            r = RADIUS
+          ),
+        );
+        if let Some(separator) = separator {
+          separator.draw(x + RADIUS, y1, out);
+        }
+        path(
+          out,
+          format!(
+            "M{} {} a{r} {r} 0 0 1 -{r} -{r} V{} a{r} {r} 0 0 1 {r} -{r}",
+            x + RADIUS,
+            y1,
+            y + RADIUS,
+            r = RADIUS
+          ),
+        );
+        if let Some(label) = label {
+          let below = separator.as_ref().map_or(0, |separator| separator.down());
+          let _ = writeln!(
+            out,
+            r#"<text class="label" x="{}" y="{}">{}</text>"#,
+            x + width / 2,
+            y1 + below + 3 * GAP / 2,
+            escape(label)
+          );
+        }
+      }
+    }
+  }
+}
+
+fn path(out: &mut String, d: String) {
+  let _ = writeln!(out, r#"<path d="{}"/>"#, d);
+}
+
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+impl<'a, I: Element + Clone> Syntax<'a, I> {
+  /// Returns the grammar as an SVG image of the railroad diagrams, a diagram per rule.<br/>
+  /// 文法を、規則ごとの構文図(レールロードダイアグラム)のSVG画像として返す。
+  ///
+  /// The terminals are drawn in rounded boxes, the references to the rules in square boxes, and the syntaxes that
+  /// are not described in dashed boxes. A bounded repetition is labeled with its range.<br/>
+  /// 終端記号は角の丸い箱、規則の参照は四角い箱、記述されていない構文は破線の箱で描く。
+  /// 範囲のある繰り返しには範囲を表示する。
+  pub fn to_railroad_svg(&self) -> String {
+    let mut body = String::new();
+    let mut width = 0;
+    let mut y = 0;
+    for (name, node) in self.rules() {
+      let item = Item::of(&node);
+      let _ = writeln!(
+        body,
+        r#"<text class="rule" x="{}" y="{}">{}</text>"#,
+        MARGIN,
+        y + MARGIN + 4,
+        escape(&name)
+      );
+      let baseline = y + TITLE_HEIGHT + MARGIN + item.up();
+      let end = MARGIN + 2 * RADIUS + item.width();
+      path(
+        &mut body,
+        format!(
+          "M{} {} v{} m0 -{} h{}",
+          MARGIN,
+          baseline - RADIUS,
+          2 * RADIUS,
+          RADIUS,
+          2 * RADIUS
+        ),
+      );
+      item.draw(MARGIN + 2 * RADIUS, baseline, &mut body);
+      path(
+        &mut body,
+        format!("M{} {} h{} m0 -{} v{}", end, baseline, 2 * RADIUS, RADIUS, 2 * RADIUS),
+      );
+      width = width.max(end + 2 * RADIUS + MARGIN);
+      y = baseline + item.down() + MARGIN;
+    }
+    format!(
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+       <style>{}</style>\n{}</svg>\n",
+      STYLE,
+      body,
+      w = width,
+      h = y
+    )
+  }
+}
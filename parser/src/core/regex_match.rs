@@ -0,0 +1,272 @@
+use crate::core::Element;
+use regex_automata::hybrid::dfa::{Cache, DFA};
+use regex_automata::util::captures::{Captures, GroupInfo};
+use regex_automata::util::pool::Pool;
+use regex_automata::util::primitives::PatternID;
+use regex_automata::util::start;
+use regex_automata::{meta, Anchored, Input};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Debug, Display};
+use std::ops::Range;
+use std::sync::Arc;
+
+type CachePool = Pool<Cache, Box<dyn Fn() -> Cache + Send + Sync>>;
+
+/// An error of compiling a regular expression.<br/>
+/// 正規表現のコンパイルのエラー。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegexError {
+  pattern: String,
+  message: String,
+}
+
+impl RegexError {
+  /// Returns the pattern that failed to compile.<br/>
+  /// コンパイルに失敗したパターンを返す。
+  pub fn pattern(&self) -> &str {
+    &self.pattern
+  }
+
+  /// Returns the message of the error.<br/>
+  /// エラーのメッセージを返す。
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+}
+
+impl Display for RegexError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "invalid regex {:?}: {}", self.pattern, self.message)
+  }
+}
+
+impl Error for RegexError {}
+
+/// A compiled regular expression, that matches anchored at the current offset of the input.<br/>
+/// 入力の現在のオフセットに固定して照合する、コンパイル済みの正規表現。
+///
+/// The syntax is the one of the `regex` crate. The current offset is the start of the text, so `^` and `\b` do not
+/// see the input already consumed. It is cheap to clone.<br/>
+/// 構文は`regex`クレートのものと同じ。現在のオフセットがテキストの先頭となるため、`^`や`\b`は解析済みの入力を参照しない。
+/// 複製は軽量である。
+#[derive(Clone)]
+pub struct Regex {
+  pattern: Arc<str>,
+  regex: meta::Regex,
+  dfa: Arc<DFA>,
+  caches: Arc<CachePool>,
+}
+
+impl Debug for Regex {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_tuple("Regex").field(&self.as_str()).finish()
+  }
+}
+
+impl Display for Regex {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
+
+impl Regex {
+  /// Compiles a regular expression.<br/>
+  /// 正規表現をコンパイルする。
+  pub fn new(pattern: &str) -> Result<Self, RegexError> {
+    let error = |message: String| RegexError {
+      pattern: pattern.to_string(),
+      message,
+    };
+    let regex = meta::Regex::new(pattern).map_err(|e| error(e.to_string()))?;
+    let dfa = DFA::builder()
+      .configure(DFA::config().unicode_word_boundary(true))
+      .build(pattern)
+      .map_err(|e| error(e.to_string()))?;
+    let dfa = Arc::new(dfa);
+    let caches: CachePool = Pool::new({
+      let dfa = dfa.clone();
+      Box::new(move || Cache::new(&dfa))
+    });
+    Ok(Self {
+      pattern: Arc::from(pattern),
+      regex,
+      dfa,
+      caches: Arc::new(caches),
+    })
+  }
+
+  /// Returns the pattern of the regular expression.<br/>
+  /// 正規表現のパターンを返す。
+  pub fn as_str(&self) -> &str {
+    &self.pattern
+  }
+
+  /// Returns the number of the capture groups, including the whole match.<br/>
+  /// 一致全体を含む、キャプチャグループの数を返す。
+  pub fn group_count(&self) -> usize {
+    self.regex.group_info().group_len(PatternID::ZERO)
+  }
+
+  fn captures(&self, input: &Input) -> Option<Vec<Option<Range<usize>>>> {
+    let mut captures = self.regex.create_captures();
+    self.regex.search_captures(input, &mut captures);
+    groups(&captures)
+  }
+
+  /// Returns the length of the match in chars, feeding the DFA with the UTF-8 of the chars one by one.
+  /// Returns `None` if the DFA gives up, which happens for a Unicode word boundary on non-ASCII text.
+  fn match_len(&self, input: &[char]) -> Option<Option<usize>> {
+    let mut cache = self.caches.get();
+    let config = start::Config::new().anchored(Anchored::Yes);
+    let mut sid = self.dfa.start_state(&mut cache, &config).ok()?;
+    let mut end = None;
+    let mut buf = [0u8; 4];
+    for (index, c) in input.iter().enumerate() {
+      for (i, b) in c.encode_utf8(&mut buf).bytes().enumerate() {
+        sid = self.dfa.next_state(&mut cache, sid, b).ok()?;
+        if sid.is_tagged() {
+          // A match is reported one byte late, so it ends just before this byte.
+          if sid.is_match() {
+            if i == 0 {
+              end = Some(index);
+            }
+          } else if sid.is_dead() {
+            return Some(end);
+          } else if sid.is_quit() {
+            return None;
+          }
+        }
+      }
+    }
+    sid = self.dfa.next_eoi_state(&mut cache, sid).ok()?;
+    if sid.is_match() {
+      end = Some(input.len());
+    }
+    Some(end)
+  }
+}
+
+fn groups(captures: &Captures) -> Option<Vec<Option<Range<usize>>>> {
+  if !captures.is_match() {
+    return None;
+  }
+  Some(captures.iter().map(|span| span.map(|span| span.range())).collect())
+}
+
+/// An element of the input that a [Regex] can match.<br/>
+/// [Regex]が照合できる入力の要素。
+pub trait RegexInput: Element + Sized {
+  /// Matches the regular expression anchored at the start of the input, and returns the ranges of the capture
+  /// groups.<br/>
+  /// 入力の先頭に固定して正規表現を照合し、キャプチャグループの範囲を返す。
+  fn captures(regex: &Regex, input: &[Self]) -> Option<Vec<Option<Range<usize>>>>;
+}
+
+impl RegexInput for u8 {
+  fn captures(regex: &Regex, input: &[Self]) -> Option<Vec<Option<Range<usize>>>> {
+    regex.captures(&Input::new(input).anchored(Anchored::Yes))
+  }
+}
+
+impl RegexInput for char {
+  fn captures(regex: &Regex, input: &[Self]) -> Option<Vec<Option<Range<usize>>>> {
+    // Only the match and the next char, for the look-ahead assertions, are encoded.
+    let (end, span_end) = match regex.match_len(input) {
+      Some(Some(len)) => ((len + 1).min(input.len()), len),
+      Some(None) => return None,
+      None => (input.len(), input.len()),
+    };
+    let mut text = String::new();
+    let mut bytes = Vec::with_capacity(end + 1);
+    for c in &input[..end] {
+      bytes.push(text.len());
+      text.push(*c);
+    }
+    bytes.push(text.len());
+    let to_char = |byte: usize| {
+      bytes
+        .binary_search(&byte)
+        .expect("a span of a UTF-8 regex lies on char boundaries")
+    };
+    let search = Input::new(&text).range(..bytes[span_end]).anchored(Anchored::Yes);
+    let groups = regex.captures(&search)?;
+    Some(
+      groups
+        .into_iter()
+        .map(|group| group.map(|range| to_char(range.start)..to_char(range.end)))
+        .collect(),
+    )
+  }
+}
+
+/// A match of a [Regex], holding the ranges of the capture groups in the input.<br/>
+/// 入力中のキャプチャグループの範囲を保持する、[Regex]の一致。
+///
+/// The ranges are the offsets in the whole input, and the group 0 is the whole match.<br/>
+/// 範囲は入力全体のオフセットで、グループ0は一致全体である。
+#[derive(Debug, Clone)]
+pub struct RegexMatch<'a, I> {
+  input: &'a [I],
+  groups: Vec<Option<Range<usize>>>,
+  group_info: GroupInfo,
+}
+
+impl<'a, I> RegexMatch<'a, I> {
+  pub(crate) fn new(regex: &Regex, input: &'a [I], groups: Vec<Option<Range<usize>>>) -> Self {
+    Self {
+      input,
+      groups,
+      group_info: regex.regex.group_info().clone(),
+    }
+  }
+
+  /// Returns the range of the whole match.<br/>
+  /// 一致全体の範囲を返す。
+  pub fn range(&self) -> Range<usize> {
+    self.groups[0]
+      .clone()
+      .expect("the group 0 of a match is the whole match")
+  }
+
+  /// Returns the elements of the whole match.<br/>
+  /// 一致全体の要素を返す。
+  pub fn as_slice(&self) -> &'a [I] {
+    &self.input[self.range()]
+  }
+
+  /// Returns the number of the capture groups, including the whole match.<br/>
+  /// 一致全体を含む、キャプチャグループの数を返す。
+  pub fn group_count(&self) -> usize {
+    self.groups.len()
+  }
+
+  /// Returns the range of the capture group, or `None` if the group did not participate in the match.<br/>
+  /// キャプチャグループの範囲を返す。グループが一致に関与しなかった場合は`None`を返す。
+  pub fn get_range(&self, index: usize) -> Option<Range<usize>> {
+    self.groups.get(index).cloned().flatten()
+  }
+
+  /// Returns the elements of the capture group.<br/>
+  /// キャプチャグループの要素を返す。
+  pub fn get(&self, index: usize) -> Option<&'a [I]> {
+    self.get_range(index).map(|range| &self.input[range])
+  }
+
+  /// Returns the elements of the named capture group.<br/>
+  /// 名前付きキャプチャグループの要素を返す。
+  pub fn name(&self, name: &str) -> Option<&'a [I]> {
+    self
+      .group_info
+      .to_index(PatternID::ZERO, name)
+      .and_then(|index| self.get(index))
+  }
+}
+
+impl<'a, I: Element + Copy> RegexMatch<'a, I> {
+  /// Returns the string of the capture group.<br/>
+  /// キャプチャグループの文字列を返す。
+  pub fn get_string(&self, index: usize) -> Option<String> {
+    self.get(index).map(I::to_string_lossy)
+  }
+}
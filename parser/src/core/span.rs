@@ -0,0 +1,79 @@
+use crate::core::{Locate, Position};
+use std::fmt;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// A structure representing a range in the source.<br/>
+/// ソース中の範囲を表す構造体。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+  /// The start position, inclusive.<br/>
+  /// 開始位置(含む)
+  pub start: Position,
+  /// The end position, exclusive.<br/>
+  /// 終了位置(含まない)
+  pub end: Position,
+}
+
+impl Display for Span {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} to {}", self.start, self.end)
+  }
+}
+
+impl Span {
+  /// Returns a [Span].<br/>
+  /// [Span]を返します。
+  pub fn new(start: Position, end: Position) -> Self {
+    Self { start, end }
+  }
+
+  /// Returns the length of the span in elements.<br/>
+  /// 範囲の要素数を返します。
+  pub fn len(&self) -> usize {
+    self.end.offset - self.start.offset
+  }
+
+  /// Returns whether the span is empty.<br/>
+  /// 範囲が空かどうかを返します。
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
+
+/// A structure representing a value with the range of the input it was parsed from.<br/>
+/// 値と、それを解析した入力の範囲を表す構造体。
+///
+/// The range holds the offsets of the elements, and [Spanned::span] resolves it into a [Span].<br/>
+/// 範囲は要素のオフセットを保持し、[Spanned::span]で[Span]に解決する。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Spanned<A> {
+  /// The value.<br/>
+  /// 値
+  pub value: A,
+  /// The range of the offsets in the input.<br/>
+  /// 入力中のオフセットの範囲
+  pub range: Range<usize>,
+}
+
+impl<A> Spanned<A> {
+  /// Returns a [Spanned].<br/>
+  /// [Spanned]を返します。
+  pub fn new(value: A, range: Range<usize>) -> Self {
+    Self { value, range }
+  }
+
+  /// Returns a [Spanned] of the value converted by the function, with the same range.<br/>
+  /// 関数で変換した値の[Spanned]を、同じ範囲で返します。
+  pub fn map<B, F>(self, f: F) -> Spanned<B>
+  where
+    F: FnOnce(A) -> B, {
+    Spanned::new(f(self.value), self.range)
+  }
+
+  /// Returns the [Span] of the range in the input.<br/>
+  /// 入力中の範囲の[Span]を返します。
+  pub fn span<I: Locate>(&self, input: &[I]) -> Span {
+    Span::new(I::locate(input, self.range.start), I::locate(input, self.range.end))
+  }
+}
@@ -0,0 +1,157 @@
+use crate::core::{Grammar, ParseError, ParseResult, ParseState, ParserRunner, UserError};
+use std::io;
+use std::io::Read;
+
+/// The enum type representing the result of [StreamRunner::parse_next].<br/>
+/// [StreamRunner::parse_next]の結果を示す列挙型。
+#[derive(Debug, Clone)]
+pub enum StreamResult<'a, I, A, E = ()> {
+  /// The value was parsed, and its elements were consumed.<br/>
+  /// 値が解析され、その要素が消費された。
+  Parsed(A),
+  /// The result depends on the input not yet fed.<br/>
+  /// 結果がまだ供給されていない入力に依存する。
+  NeedMoreInput,
+  /// The input does not match, regardless of the input not yet fed.<br/>
+  /// まだ供給されていない入力に関わらず、入力が一致しない。
+  Failed(ParseError<'a, I, E>),
+}
+
+/// A runner that parses the input fed in chunks.<br/>
+/// チャンク単位で供給される入力を解析するランナー。
+///
+/// A result for which a parser looked at the end of the buffered input is provisional until more input is fed or the
+/// end of the stream is marked by [StreamRunner::finish], because a greedy parser might continue or an `Incomplete`
+/// failure might succeed. Such results are reported as [StreamResult::NeedMoreInput], and the elements are not
+/// consumed. The end is looked at when a parser fails with `Incomplete` or at the end, or when it records so by
+/// [ParseState::mark_end_reached], also in an alternative that was not taken.<br/>
+/// パーサーがバッファされた入力の終端を参照した結果は、さらに入力が供給されるか[StreamRunner::finish]でストリームの
+/// 終端が示されるまで確定しない。貪欲なパーサーが続きを解析したり、`Incomplete`の失敗が成功したりする可能性があるため。
+/// そのような結果は[StreamResult::NeedMoreInput]として報告され、要素は消費されない。終端は、パーサーが`Incomplete`または
+/// 終端で失敗した場合、または[ParseState::mark_end_reached]で記録した場合に、採用されなかった選択肢の中であっても
+/// 参照されたものとする。
+///
+/// The offsets of the errors are relative to the start of the buffered input, that is [StreamRunner::offset].<br/>
+/// エラーのオフセットはバッファされた入力の先頭、つまり[StreamRunner::offset]からの相対値となる。
+#[derive(Debug, Clone)]
+pub struct StreamRunner<I> {
+  buffer: Vec<I>,
+  consumed: usize,
+  offset: usize,
+  finished: bool,
+}
+
+impl<I> Default for StreamRunner<I> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<I> StreamRunner<I> {
+  /// Returns a [StreamRunner].<br/>
+  /// [StreamRunner]を返す。
+  pub fn new() -> Self {
+    Self {
+      buffer: vec![],
+      consumed: 0,
+      offset: 0,
+      finished: false,
+    }
+  }
+
+  /// Feeds a chunk of the input.<br/>
+  /// 入力のチャンクを供給する。
+  pub fn feed(&mut self, chunk: &[I])
+  where
+    I: Clone, {
+    self.compact();
+    self.buffer.extend_from_slice(chunk);
+  }
+
+  /// Marks the end of the stream.<br/>
+  /// ストリームの終端を示す。
+  pub fn finish(&mut self) {
+    self.finished = true;
+  }
+
+  /// Returns whether the end of the stream was marked and all the input was consumed.<br/>
+  /// ストリームの終端が示され、すべての入力が消費されたかどうかを返す。
+  pub fn is_finished(&self) -> bool {
+    self.finished && self.buffered().is_empty()
+  }
+
+  /// Returns the input not yet consumed.<br/>
+  /// まだ消費されていない入力を返す。
+  pub fn buffered(&self) -> &[I] {
+    &self.buffer[self.consumed..]
+  }
+
+  /// Returns the offset of the input not yet consumed, from the start of the stream.<br/>
+  /// ストリームの先頭から、まだ消費されていない入力までのオフセットを返す。
+  pub fn offset(&self) -> usize {
+    self.offset
+  }
+
+  /// Parses the next value from the buffered input.<br/>
+  /// バッファされた入力から次の値を解析する。
+  ///
+  /// The parser of the [Grammar] is built once if it is free, and is built for each call otherwise, since it is bound
+  /// to the buffer.<br/>
+  /// [Grammar]のパーサーは、自由であれば一度だけ構築され、そうでなければバッファに束縛されるため呼び出しごとに構築される。
+  ///
+  /// - grammar: the [Grammar] of the values
+  /// - grammar: 値の[Grammar]
+  pub fn parse_next<'a, A, E>(&'a mut self, grammar: &Grammar<I, A, E>) -> StreamResult<'a, I, A, E>
+  where
+    I: 'static,
+    A: 'static,
+    E: UserError + 'static, {
+    let Self {
+      buffer,
+      consumed,
+      offset,
+      finished,
+    } = self;
+    let parse_state = ParseState::new(&buffer[*consumed..], 0);
+    let result = grammar.parser().run(&parse_state);
+    if parse_state.is_end_reached() && !*finished {
+      return StreamResult::NeedMoreInput;
+    }
+    match result {
+      ParseResult::Success { value, length } => {
+        *consumed += length;
+        *offset += length;
+        StreamResult::Parsed(value)
+      }
+      ParseResult::Failure { error, .. } => StreamResult::Failed(parse_state.farthest_failure(error)),
+    }
+  }
+
+  fn compact(&mut self) {
+    self.buffer.drain(..self.consumed);
+    self.consumed = 0;
+  }
+}
+
+impl StreamRunner<u8> {
+  /// Reads a chunk of the input from the reader, and marks the end of the stream when the reader reaches its end.<br/>
+  /// リーダーから入力のチャンクを読み込み、リーダーが終端に達したらストリームの終端を示す。
+  ///
+  /// - reader: a reader
+  /// - size: the maximum size of the chunk
+  ///
+  /// Returns the number of the bytes read.<br/>
+  /// 読み込んだバイト数を返す。
+  pub fn read_from<R: Read>(&mut self, reader: &mut R, size: usize) -> io::Result<usize> {
+    self.compact();
+    let len = self.buffer.len();
+    self.buffer.resize(len + size, 0);
+    let result = reader.read(&mut self.buffer[len..]);
+    let n = *result.as_ref().unwrap_or(&0);
+    self.buffer.truncate(len + n);
+    if n == 0 && size > 0 && result.is_ok() {
+      self.finish();
+    }
+    result
+  }
+}
@@ -0,0 +1,646 @@
+use crate::core::{Element, GeneralCategory, Shared};
+use std::any::type_name;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::{Display, Write};
+use std::panic::Location;
+
+#[cfg(not(feature = "sync"))]
+type ElementTest<'a, I> = dyn Fn(&I) -> bool + 'a;
+#[cfg(feature = "sync")]
+type ElementTest<'a, I> = dyn Fn(&I) -> bool + Send + Sync + 'a;
+
+#[cfg(not(feature = "sync"))]
+type Elements<'a, I> = dyn AsRef<[I]> + 'a;
+#[cfg(feature = "sync")]
+type Elements<'a, I> = dyn AsRef<[I]> + Send + Sync + 'a;
+
+#[cfg(not(feature = "sync"))]
+type Kind<'a, I> = dyn AsRef<ClassKind<I>> + 'a;
+#[cfg(feature = "sync")]
+type Kind<'a, I> = dyn AsRef<ClassKind<I>> + Send + Sync + 'a;
+
+#[cfg(not(feature = "sync"))]
+type Expand<'a, I> = dyn Fn() -> Shared<Syntax<'a, I>> + 'a;
+#[cfg(feature = "sync")]
+type Expand<'a, I> = dyn Fn() -> Shared<Syntax<'a, I>> + Send + Sync + 'a;
+
+/// The description of the structure of a [crate::core::Parser], the grammar that it parses.<br/>
+/// [crate::core::Parser]の構造、つまりパーサーが解析する文法の記述。
+///
+/// The parsers of `ElementParsers`, `ElementsParsers`, `RepeatParsers`, `OperatorParsers` and `LoggingParsers`, and
+/// the `take_while` and `take_till` parsers describe themselves, and the other parsers are [Syntax::Opaque]. Name the
+/// rules with `name`, and refer to the recursive rules with `lazy`. An unnamed recursive rule is named after the function
+/// given to `lazy`.<br/>
+/// `ElementParsers`、`ElementsParsers`、`RepeatParsers`、`OperatorParsers`、`LoggingParsers`のパーサーと、
+/// `take_while`、`take_till`のパーサーは自身を記述し、
+/// それ以外のパーサーは[Syntax::Opaque]となる。規則には`name`で名前を付け、再帰的な規則は`lazy`で参照する。
+/// 名前のない再帰的な規則には、`lazy`に渡した関数の名前が付けられる。
+///
+/// # Example
+///
+/// ```rust
+/// use oni_comb_parser_rs::prelude::*;
+///
+/// fn value<'a>() -> Parser<'a, char, ()> {
+///   let number = elm_digit().of_many1().name("number");
+///   let list = surround(elm('['), lazy(value).of_many0_sep(elm(',')), elm(']')).name("list");
+///   (number.discard() | list.discard()).name("value")
+/// }
+///
+/// assert_eq!(
+///   value().syntax().to_ebnf(),
+///   "value  ::= number | list\n\
+///    number ::= [0-9]+\n\
+///    list   ::= \"[\" (value (\",\" value)*)? \"]\"\n"
+/// );
+/// assert!(value().syntax().to_railroad_svg().starts_with("<svg"));
+/// ```
+pub enum Syntax<'a, I> {
+  /// Matches the empty input.<br/>
+  /// 空の入力に一致する。
+  Empty,
+  /// Matches the elements.<br/>
+  /// 要素の列に一致する。
+  Terminal(Shared<Elements<'a, I>>),
+  /// Matches an element of the class.<br/>
+  /// クラスの要素に一致する。
+  Class(Shared<ElementClass<'a, I>>),
+  /// Matches the syntaxes in order.<br/>
+  /// 構文に順番に一致する。
+  Sequence(Vec<Shared<Syntax<'a, I>>>),
+  /// Matches one of the syntaxes, tried in order.<br/>
+  /// 順番に試行した構文のいずれかに一致する。
+  Choice(Vec<Shared<Syntax<'a, I>>>),
+  /// Matches the syntax repeatedly, `min` to `max` times, separated by the separator.<br/>
+  /// 区切りで区切られた構文に`min`回から`max`回繰り返し一致する。
+  Repeat {
+    syntax: Shared<Syntax<'a, I>>,
+    min: usize,
+    max: Option<usize>,
+    separator: Option<Shared<Syntax<'a, I>>>,
+  },
+  /// The syntax named as a rule.<br/>
+  /// 規則として名前を付けた構文。
+  Rule {
+    name: String,
+    syntax: Shared<Syntax<'a, I>>,
+  },
+  /// The syntax built on demand, for the recursive rules. The key identifies where and by which function it is
+  /// built.<br/>
+  /// 再帰的な規則のために、必要になった時点で構築する構文。キーはどこでどの関数が構築するかを識別する。
+  Lazy {
+    key: LazyKey,
+    expand: Shared<Expand<'a, I>>,
+  },
+  /// The syntax that is not described, with a note.<br/>
+  /// 注記のみを持つ、記述されていない構文。
+  Opaque(String),
+}
+
+impl<'a, I> Clone for Syntax<'a, I> {
+  fn clone(&self) -> Self {
+    match self {
+      Syntax::Empty => Syntax::Empty,
+      Syntax::Terminal(elements) => Syntax::Terminal(elements.clone()),
+      Syntax::Class(class) => Syntax::Class(class.clone()),
+      Syntax::Sequence(syntaxes) => Syntax::Sequence(syntaxes.clone()),
+      Syntax::Choice(syntaxes) => Syntax::Choice(syntaxes.clone()),
+      Syntax::Repeat {
+        syntax,
+        min,
+        max,
+        separator,
+      } => Syntax::Repeat {
+        syntax: syntax.clone(),
+        min: *min,
+        max: *max,
+        separator: separator.clone(),
+      },
+      Syntax::Rule { name, syntax } => Syntax::Rule {
+        name: name.clone(),
+        syntax: syntax.clone(),
+      },
+      Syntax::Lazy { key, expand } => Syntax::Lazy {
+        key: *key,
+        expand: expand.clone(),
+      },
+      Syntax::Opaque(note) => Syntax::Opaque(note.clone()),
+    }
+  }
+}
+
+/// The identity of a [Syntax::Lazy], the type of the function that builds it and the place where `lazy` is
+/// called.<br/>
+/// [Syntax::Lazy]の識別子。構築する関数の型と、`lazy`を呼び出した場所からなる。
+///
+/// The closures of a function share their type name, so the place tells them apart. A recursive rule is built again
+/// at the same place while it is expanded, so its key stays the same and the recursion is found.<br/>
+/// 関数内のクロージャは型名を共有するため、場所で区別する。再帰的な規則は展開中に同じ場所で再び構築されるため、
+/// キーが変わらず再帰を検出できる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LazyKey {
+  name: &'static str,
+  location: &'static Location<'static>,
+}
+
+impl LazyKey {
+  /// Returns the key of the function, created at the place of the caller.
+  #[track_caller]
+  pub(crate) fn of<F>() -> Self {
+    Self {
+      name: type_name::<F>(),
+      location: Location::caller(),
+    }
+  }
+
+  /// Returns the type name of the function.<br/>
+  /// 関数の型名を返す。
+  pub fn name(&self) -> &'static str {
+    self.name
+  }
+
+  /// Returns the place where `lazy` is called.<br/>
+  /// `lazy`を呼び出した場所を返す。
+  pub fn location(&self) -> &'static Location<'static> {
+    self.location
+  }
+}
+
+impl Display for LazyKey {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} at {}", self.name, self.location)
+  }
+}
+
+/// The kind of an [ElementClass], that determines how it is shown.<br/>
+/// 表示方法を決める、[ElementClass]の種類。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassKind<I> {
+  /// Any element.<br/>
+  /// 任意の要素。
+  Any,
+  /// A class of the notation, for example `[0-9]`.<br/>
+  /// 表記を持つクラス。例えば`[0-9]`。
+  Named(&'static str),
+  /// The elements in the range.<br/>
+  /// 範囲内の要素。
+  Range { start: I, end: I, inclusive: bool },
+  /// The elements in the text.<br/>
+  /// テキスト中の要素。
+  OneOf(String),
+  /// The elements not in the text.<br/>
+  /// テキスト中にない要素。
+  NoneOf(String),
+  /// The elements in the Unicode general categories.<br/>
+  /// Unicodeの一般カテゴリに含まれる要素。
+  Category(Vec<GeneralCategory>),
+  /// The elements satisfying a predicate.<br/>
+  /// 述語を満たす要素。
+  Predicate,
+}
+
+impl<I> AsRef<ClassKind<I>> for ClassKind<I> {
+  fn as_ref(&self) -> &ClassKind<I> {
+    self
+  }
+}
+
+/// A class of the elements, matched by a single element parser.<br/>
+/// 単一の要素のパーサーが一致する要素のクラス。
+pub struct ElementClass<'a, I> {
+  kind: Box<Kind<'a, I>>,
+  test: Box<ElementTest<'a, I>>,
+}
+
+impl<'a, I> ElementClass<'a, I> {
+  /// Returns the class of the elements satisfying the test.<br/>
+  /// テストを満たす要素のクラスを返す。
+  #[cfg(not(feature = "sync"))]
+  pub fn new<F>(kind: ClassKind<I>, test: F) -> Self
+  where
+    I: 'a,
+    F: Fn(&I) -> bool + 'a, {
+    Self {
+      kind: Box::new(kind),
+      test: Box::new(test),
+    }
+  }
+
+  /// Returns the class of the elements satisfying the test.<br/>
+  /// テストを満たす要素のクラスを返す。
+  #[cfg(feature = "sync")]
+  pub fn new<F>(kind: ClassKind<I>, test: F) -> Self
+  where
+    I: Send + Sync + 'a,
+    F: Fn(&I) -> bool + Send + Sync + 'a, {
+    Self {
+      kind: Box::new(kind),
+      test: Box::new(test),
+    }
+  }
+
+  /// Returns the kind of the class.<br/>
+  /// クラスの種類を返す。
+  pub fn kind(&self) -> &ClassKind<I> {
+    (*self.kind).as_ref()
+  }
+
+  /// Returns whether the class contains the element.<br/>
+  /// クラスが要素を含むかどうかを返す。
+  pub fn contains(&self, element: &I) -> bool {
+    (self.test)(element)
+  }
+}
+
+impl<'a, I> Syntax<'a, I> {
+  /// Returns the syntax built on demand by the function.<br/>
+  /// 関数で必要になった時点で構築する構文を返す。
+  #[cfg(not(feature = "sync"))]
+  #[track_caller]
+  pub fn lazy<F>(f: F) -> Self
+  where
+    F: Fn() -> Syntax<'a, I> + 'a, {
+    Syntax::Lazy {
+      key: LazyKey::of::<F>(),
+      expand: Shared::new(move || Shared::new(f())),
+    }
+  }
+
+  /// Returns the syntax built on demand by the function.<br/>
+  /// 関数で必要になった時点で構築する構文を返す。
+  #[cfg(feature = "sync")]
+  #[track_caller]
+  pub fn lazy<F>(f: F) -> Self
+  where
+    F: Fn() -> Syntax<'a, I> + Send + Sync + 'a, {
+    Syntax::Lazy {
+      key: LazyKey::of::<F>(),
+      expand: Shared::new(move || Shared::new(f())),
+    }
+  }
+
+  pub(crate) fn of(syntax: &Option<Shared<Self>>) -> Shared<Self> {
+    syntax
+      .clone()
+      .unwrap_or_else(|| Shared::new(Syntax::Opaque(String::new())))
+  }
+
+  /// Returns the sequence, or `None` if none of the syntaxes is described.
+  pub(crate) fn sequence(syntaxes: &[&Option<Shared<Self>>]) -> Option<Shared<Self>> {
+    if syntaxes.iter().all(|syntax| syntax.is_none()) {
+      return None;
+    }
+    Some(Shared::new(Syntax::Sequence(
+      syntaxes.iter().map(|syntax| Self::of(syntax)).collect(),
+    )))
+  }
+
+  /// Returns the choice, or `None` if none of the syntaxes is described.
+  pub(crate) fn choice(syntaxes: &[&Option<Shared<Self>>]) -> Option<Shared<Self>> {
+    if syntaxes.iter().all(|syntax| syntax.is_none()) {
+      return None;
+    }
+    Some(Shared::new(Syntax::Choice(
+      syntaxes.iter().map(|syntax| Self::of(syntax)).collect(),
+    )))
+  }
+
+  /// Returns the repetition, or `None` if the syntax is not described.
+  pub(crate) fn repeat(
+    syntax: &Option<Shared<Self>>,
+    min: usize,
+    max: Option<usize>,
+    separator: Option<&Option<Shared<Self>>>,
+  ) -> Option<Shared<Self>> {
+    syntax.as_ref()?;
+    Some(Shared::new(Syntax::Repeat {
+      syntax: Self::of(syntax),
+      min,
+      max,
+      separator: separator.map(Self::of),
+    }))
+  }
+}
+
+impl<'a, I: Element + Clone> Syntax<'a, I> {
+  /// Returns the grammar as EBNF text, a line of `name ::= expression` per rule.<br/>
+  /// 文法を、規則ごとに`name ::= expression`の行からなるEBNFのテキストとして返す。
+  ///
+  /// The notation is the one of the XML specification, with `{n,m}` for the bounded repetitions and `<...>` for the
+  /// syntaxes that are not described. An unnamed syntax is shown as the rule `grammar`.<br/>
+  /// 表記はXML仕様のものに、範囲のある繰り返しの`{n,m}`と、記述されていない構文の`<...>`を加えたもの。
+  /// 名前のない構文は規則`grammar`として示す。
+  pub fn to_ebnf(&self) -> String {
+    let rules = self.rules();
+    let width = rules.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    let mut out = String::new();
+    for (name, node) in &rules {
+      let _ = writeln!(out, "{:width$} ::= {}", name, ebnf(node, 0), width = width);
+    }
+    out
+  }
+
+  /// Returns the rules in order of appearance, the first one being the syntax itself.
+  pub(crate) fn rules(&self) -> Vec<(String, Node)> {
+    let mut resolver = Resolver::default();
+    let root = resolver.resolve(self);
+    let mut rules = resolver.rules;
+    if !matches!(root, Node::Rule(_)) {
+      // The root built by the function of an unnamed recursive rule is the rule itself.
+      match rules.iter().position(|(_, node)| *node == root) {
+        Some(index) => {
+          let rule = rules.remove(index);
+          rules.insert(0, rule);
+        }
+        None => rules.insert(0, ("grammar".to_string(), root)),
+      }
+    }
+    rules
+  }
+}
+
+/// A syntax resolved for display, where the rules are referred to by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Node {
+  Empty,
+  Terminal(String),
+  Class(String),
+  Rule(String),
+  Opaque(String),
+  Sequence(Vec<Node>),
+  Choice(Vec<Node>),
+  Repeat {
+    node: Box<Node>,
+    min: usize,
+    max: Option<usize>,
+    separator: Option<Box<Node>>,
+  },
+}
+
+#[derive(Default)]
+struct Resolver {
+  names: HashSet<String>,
+  rules: Vec<(String, Node)>,
+  /// The keys of the lazy syntaxes being expanded.
+  expanding: Vec<LazyKey>,
+  /// The names of the lazy syntaxes that turned out to be recursive.
+  recursive: HashMap<LazyKey, String>,
+}
+
+impl Resolver {
+  fn resolve<I: Element + Clone>(&mut self, syntax: &Syntax<I>) -> Node {
+    match syntax {
+      Syntax::Empty => Node::Empty,
+      Syntax::Terminal(elements) => terminal((**elements).as_ref().iter().map(|e| e.clone().to_char())),
+      Syntax::Class(class) => Node::Class(class_notation(class.kind())),
+      Syntax::Sequence(syntaxes) => {
+        let mut nodes = vec![];
+        for syntax in syntaxes {
+          match self.resolve(syntax) {
+            Node::Empty => (),
+            Node::Sequence(children) => nodes.extend(children),
+            node => nodes.push(node),
+          }
+        }
+        match nodes.len() {
+          0 => Node::Empty,
+          1 => nodes.pop().unwrap(),
+          _ => Node::Sequence(nodes),
+        }
+      }
+      Syntax::Choice(syntaxes) => {
+        let mut nodes = vec![];
+        let mut optional = false;
+        for syntax in syntaxes {
+          match self.resolve(syntax) {
+            Node::Empty => optional = true,
+            Node::Choice(children) => nodes.extend(children),
+            node => nodes.push(node),
+          }
+        }
+        let node = match nodes.len() {
+          0 => return Node::Empty,
+          1 => nodes.pop().unwrap(),
+          _ => Node::Choice(nodes),
+        };
+        if optional {
+          Node::Repeat {
+            node: Box::new(node),
+            min: 0,
+            max: Some(1),
+            separator: None,
+          }
+        } else {
+          node
+        }
+      }
+      Syntax::Repeat {
+        syntax,
+        min,
+        max,
+        separator,
+      } => Node::Repeat {
+        node: Box::new(self.resolve(syntax)),
+        min: *min,
+        max: *max,
+        separator: separator.as_ref().map(|separator| Box::new(self.resolve(separator))),
+      },
+      Syntax::Rule { name, syntax } => {
+        if self.names.insert(name.clone()) {
+          let index = self.rules.len();
+          self.rules.push((name.clone(), Node::Empty));
+          self.rules[index].1 = self.resolve(syntax);
+        }
+        Node::Rule(name.clone())
+      }
+      Syntax::Lazy { key, expand } => {
+        if let Some(name) = self.recursive.get(key) {
+          return Node::Rule(name.clone());
+        }
+        if self.expanding.contains(key) {
+          let name = self.fresh_name(key.name());
+          self.recursive.insert(*key, name.clone());
+          return Node::Rule(name);
+        }
+        self.expanding.push(*key);
+        let node = self.resolve(&expand());
+        self.expanding.pop();
+        match self.recursive.get(key) {
+          Some(name) => {
+            self.rules.push((name.clone(), node));
+            Node::Rule(name.clone())
+          }
+          None => node,
+        }
+      }
+      Syntax::Opaque(note) => Node::Opaque(note.clone()),
+    }
+  }
+}
+
+impl Resolver {
+  /// Returns an unused rule name from the type name of the function of a lazy syntax, without the path and the
+  /// generic arguments.
+  fn fresh_name(&mut self, function_name: &str) -> String {
+    let mut path = String::new();
+    let mut depth = 0;
+    for c in function_name.chars() {
+      match c {
+        '<' => depth += 1,
+        '>' => depth -= 1,
+        c if depth == 0 => path.push(c),
+        _ => (),
+      }
+    }
+    let base = path
+      .split("::")
+      .filter(|segment| !segment.is_empty() && !segment.starts_with('{'))
+      .last()
+      .unwrap_or("rule")
+      .to_string();
+    let mut name = base.clone();
+    let mut suffix = 1;
+    while self.names.contains(&name) {
+      suffix += 1;
+      name = format!("{}{}", base, suffix);
+    }
+    self.names.insert(name.clone());
+    name
+  }
+}
+
+/// Returns the terminal of the chars, quoting the printable ones and showing the others as `#xN`.
+fn terminal(chars: impl Iterator<Item = char>) -> Node {
+  let mut nodes = vec![];
+  let mut text = String::new();
+  for c in chars {
+    if !c.is_control() && c != '"' {
+      text.push(c);
+      continue;
+    }
+    if !text.is_empty() {
+      nodes.push(Node::Terminal(format!("\"{}\"", text)));
+      text.clear();
+    }
+    if c == '"' {
+      nodes.push(Node::Terminal("'\"'".to_string()));
+    } else {
+      nodes.push(Node::Terminal(format!("#x{:X}", c as u32)));
+    }
+  }
+  if !text.is_empty() {
+    nodes.push(Node::Terminal(format!("\"{}\"", text)));
+  }
+  match nodes.len() {
+    0 => Node::Empty,
+    1 => nodes.pop().unwrap(),
+    _ => Node::Sequence(nodes),
+  }
+}
+
+pub(crate) fn class_notation<I: Element + Clone>(kind: &ClassKind<I>) -> String {
+  fn class_char(c: char) -> String {
+    if c.is_ascii_graphic() && !matches!(c, '[' | ']' | '^' | '-' | '\\') || !c.is_ascii() && !c.is_control() {
+      c.to_string()
+    } else {
+      format!("#x{:X}", c as u32)
+    }
+  }
+  match kind {
+    ClassKind::Any => "<any>".to_string(),
+    ClassKind::Named(notation) => notation.to_string(),
+    ClassKind::Range { start, end, inclusive } => {
+      let start = start.clone().to_char();
+      let mut end = end.clone().to_char();
+      if !inclusive {
+        match (end as u32).checked_sub(1).and_then(char::from_u32) {
+          Some(c) => end = c,
+          None => return "<empty>".to_string(),
+        }
+      }
+      format!("[{}-{}]", class_char(start), class_char(end))
+    }
+    ClassKind::OneOf(text) => format!("[{}]", text.chars().map(class_char).collect::<String>()),
+    ClassKind::NoneOf(text) => format!("[^{}]", text.chars().map(class_char).collect::<String>()),
+    ClassKind::Category(categories) => format!(
+      "<{}>",
+      categories
+        .iter()
+        .map(|c| c.abbreviation())
+        .collect::<Vec<_>>()
+        .join("|")
+    ),
+    ClassKind::Predicate => "<predicate>".to_string(),
+  }
+}
+
+/// Returns the EBNF of the node, parenthesized if the precedence of the context is higher: 0 for a choice, 1 for a
+/// sequence and 2 for an operand of a repetition.
+fn ebnf(node: &Node, precedence: usize) -> String {
+  let parenthesize = |text: String, parenthesized: bool| {
+    if parenthesized {
+      format!("({})", text)
+    } else {
+      text
+    }
+  };
+  match node {
+    Node::Empty => "()".to_string(),
+    Node::Terminal(text) | Node::Class(text) | Node::Rule(text) => text.clone(),
+    Node::Opaque(note) if note.is_empty() => "<?>".to_string(),
+    Node::Opaque(note) => format!("<{}>", note),
+    Node::Sequence(nodes) => parenthesize(
+      nodes.iter().map(|node| ebnf(node, 1)).collect::<Vec<_>>().join(" "),
+      precedence > 1,
+    ),
+    Node::Choice(nodes) => parenthesize(
+      nodes.iter().map(|node| ebnf(node, 0)).collect::<Vec<_>>().join(" | "),
+      precedence > 0,
+    ),
+    Node::Repeat { max: Some(0), .. } => "()".to_string(),
+    Node::Repeat {
+      node,
+      min: 1,
+      max: Some(1),
+      ..
+    } => ebnf(node, precedence),
+    Node::Repeat {
+      node,
+      min,
+      max,
+      separator: None,
+    } => format!("{}{}", ebnf(node, 2), repetition(*min, *max)),
+    Node::Repeat {
+      node,
+      min,
+      max,
+      separator: Some(separator),
+    } => {
+      let rest = Node::Repeat {
+        node: Box::new(Node::Sequence(vec![(**separator).clone(), (**node).clone()])),
+        min: min.saturating_sub(1),
+        max: max.map(|max| max - 1),
+        separator: None,
+      };
+      let text = format!("{} {}", ebnf(node, 1), ebnf(&rest, 2));
+      if *min == 0 {
+        format!("({})?", text)
+      } else {
+        parenthesize(text, precedence > 1)
+      }
+    }
+  }
+}
+
+/// Returns the suffix of the repetition, `*`, `+`, `?`, `{n}`, `{n,}` or `{n,m}`.
+pub(crate) fn repetition(min: usize, max: Option<usize>) -> String {
+  match (min, max) {
+    (0, None) => "*".to_string(),
+    (1, None) => "+".to_string(),
+    (0, Some(1)) => "?".to_string(),
+    (min, None) => format!("{{{},}}", min),
+    (min, Some(max)) if min == max => format!("{{{}}}", min),
+    (min, Some(max)) => format!("{{{},{}}}", min, max),
+  }
+}
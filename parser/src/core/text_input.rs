@@ -0,0 +1,75 @@
+use crate::core::RegexInput;
+use std::str;
+
+/// The element of a text, on which the text parsers such as `tag` and `regex` work.<br/>
+/// `tag`や`regex`などのテキストのパーサーが解析する、テキストの要素。
+///
+/// It is implemented for `char` and for `u8`, the UTF-8 bytes of a `str`. The bytes are parsed without copying, and
+/// their texts are the string slices of the input.<br/>
+/// `char`と、`str`のUTF-8バイト列である`u8`に実装されている。バイト列はコピーせずに解析され、そのテキストは入力の
+/// 文字列スライスとなる。
+pub trait TextInput: RegexInput + Copy + PartialEq {
+  /// The text of the elements, `String` for `char` and `&str` for `u8`.<br/>
+  /// 要素のテキスト。`char`では`String`、`u8`では`&str`となる。
+  type Text<'a>
+  where
+    Self: 'a;
+
+  /// Encodes the string into the elements.<br/>
+  /// 文字列を要素に符号化する。
+  fn encode(text: &str) -> Vec<Self>;
+
+  /// Decodes the first character of the elements, and returns it with its length in elements.<br/>
+  /// 要素の先頭の文字を復号し、要素数での長さとともに返す。
+  ///
+  /// Returns `None` if the elements are empty or do not start with a valid character.<br/>
+  /// 要素が空か、正しい文字で始まらない場合は`None`を返す。
+  fn decode(elements: &[Self]) -> Option<(char, usize)>;
+
+  /// Returns the text of the elements, or `None` if they are not a valid text.<br/>
+  /// 要素のテキストを返す。正しいテキストでない場合は`None`を返す。
+  fn to_text(elements: &[Self]) -> Option<Self::Text<'_>>;
+}
+
+impl TextInput for char {
+  type Text<'a> = String;
+
+  fn encode(text: &str) -> Vec<Self> {
+    text.chars().collect()
+  }
+
+  fn decode(elements: &[Self]) -> Option<(char, usize)> {
+    elements.first().map(|c| (*c, 1))
+  }
+
+  fn to_text(elements: &[Self]) -> Option<Self::Text<'_>> {
+    Some(String::from_iter(elements))
+  }
+}
+
+impl TextInput for u8 {
+  type Text<'a> = &'a str;
+
+  fn encode(text: &str) -> Vec<Self> {
+    text.as_bytes().to_vec()
+  }
+
+  fn decode(elements: &[Self]) -> Option<(char, usize)> {
+    let length = match *elements.first()? {
+      b if b < 0x80 => 1,
+      b if b >= 0xF0 => 4,
+      b if b >= 0xE0 => 3,
+      b if b >= 0xC0 => 2,
+      _ => return None,
+    };
+    str::from_utf8(elements.get(..length)?)
+      .ok()?
+      .chars()
+      .next()
+      .map(|c| (c, length))
+  }
+
+  fn to_text(elements: &[Self]) -> Option<Self::Text<'_>> {
+    str::from_utf8(elements).ok()
+  }
+}
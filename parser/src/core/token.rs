@@ -0,0 +1,72 @@
+use crate::core::{Locate, Position, Span};
+use std::fmt;
+use std::fmt::{Debug, Display};
+
+/// A structure representing a token produced by a [crate::core::Lexer].<br/>
+/// [crate::core::Lexer]が生成するトークンを表す構造体。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token<K> {
+  /// The kind of the token.<br/>
+  /// トークンの種類
+  pub kind: K,
+  /// The text of the token.<br/>
+  /// トークンのテキスト
+  pub text: String,
+  /// The span of the token in the source.<br/>
+  /// ソース中のトークンの範囲
+  pub span: Span,
+}
+
+impl<K: Debug> Display for Token<K> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:?} {:?}", self.kind, self.text)
+  }
+}
+
+impl<K> Token<K> {
+  /// Returns a [Token].<br/>
+  /// [Token]を返します。
+  pub fn new(kind: K, text: String, span: Span) -> Self {
+    Self { kind, text, span }
+  }
+}
+
+/// The position of a token is the start of its span in the source, and the end of the input is the end of the last
+/// token.<br/>
+/// トークンの位置はソース中のその範囲の開始位置で、入力の終端は最後のトークンの終了位置となる。
+impl<K> Locate for Token<K> {
+  fn locate(input: &[Self], offset: usize) -> Position {
+    match input.get(offset) {
+      Some(token) => token.span.start,
+      None => input.last().map(|token| token.span.end).unwrap_or_default(),
+    }
+  }
+
+  fn width(input: &[Self], offset: usize, length: usize) -> usize {
+    let start = offset.min(input.len());
+    let end = (offset + length).min(input.len());
+    match (input[start..end].first(), input[start..end].last()) {
+      (Some(first), Some(last)) if first.span.start.line == last.span.end.line => {
+        last.span.end.column - first.span.start.column
+      }
+      (Some(first), _) if first.span.start.line == first.span.end.line => {
+        first.span.end.column - first.span.start.column
+      }
+      _ => 1,
+    }
+  }
+
+  /// The line is reconstructed from the texts of the tokens on it, since the tokens do not hold the skipped
+  /// source.<br/>
+  /// トークンは読み飛ばされたソースを保持しないため、行はその行のトークンのテキストから再構成される。
+  fn line_text(input: &[Self], offset: usize) -> String {
+    let line = Self::locate(input, offset).line;
+    let mut text = String::new();
+    for token in input.iter().filter(|token| token.span.start.line == line) {
+      let column = text.chars().count() + 1;
+      text.push_str(&" ".repeat(token.span.start.column.saturating_sub(column)));
+      text.push_str(token.text.lines().next().unwrap_or_default());
+    }
+    text
+  }
+}
@@ -0,0 +1,271 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// An event of a named parser, reported to a [Tracer].<br/>
+/// [Tracer]に報告される、名前付きパーサーのイベント。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceEvent<'t> {
+  /// The parser is entered at the offset.<br/>
+  /// パーサーがオフセットで開始された。
+  Enter { name: &'t str, offset: usize },
+  /// The parser is exited with the outcome, after the elapsed time.<br/>
+  /// パーサーが経過時間の後に結果とともに終了した。
+  Exit {
+    name: &'t str,
+    offset: usize,
+    outcome: TraceOutcome,
+    elapsed: Duration,
+  },
+}
+
+/// The outcome of a named parser.<br/>
+/// 名前付きパーサーの結果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceOutcome {
+  /// Succeeded, consuming the length.<br/>
+  /// 長さを消費して成功した。
+  Success { length: usize },
+  /// Failed. An uncommitted failure lets the enclosing parsers backtrack.<br/>
+  /// 失敗した。コミットされていない失敗では、外側のパーサーがバックトラックする。
+  Failure { committed: bool },
+}
+
+impl TraceOutcome {
+  /// Returns whether the outcome is an uncommitted failure.<br/>
+  /// 結果がコミットされていない失敗かどうかを返す。
+  pub fn is_backtrack(&self) -> bool {
+    matches!(self, TraceOutcome::Failure { committed: false })
+  }
+}
+
+/// A hook that receives the events of the named parsers in a parse run.<br/>
+/// 解析の実行における名前付きパーサーのイベントを受け取るフック。
+///
+/// The events are reported by the parsers named with `name`, when the input is parsed by `parse_with_tracer`.
+/// A closure taking a [TraceEvent] is also a [Tracer].<br/>
+/// イベントは、`parse_with_tracer`で入力を解析した際に、`name`で名前を付けたパーサーが報告する。
+/// [TraceEvent]を受け取るクロージャも[Tracer]である。
+pub trait Tracer {
+  fn trace(&mut self, event: TraceEvent);
+}
+
+impl<F: FnMut(TraceEvent)> Tracer for F {
+  fn trace(&mut self, event: TraceEvent) {
+    self(event)
+  }
+}
+
+/// A node of the call tree recorded by [Trace].<br/>
+/// [Trace]が記録する呼び出しツリーのノード。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceNode {
+  /// The name of the parser.<br/>
+  /// パーサーの名前
+  pub name: String,
+  /// The offset where the parser was entered.<br/>
+  /// パーサーが開始されたオフセット
+  pub offset: usize,
+  /// The outcome, or `None` if the parser has not exited.<br/>
+  /// 結果。パーサーが終了していない場合は`None`
+  pub outcome: Option<TraceOutcome>,
+  /// The elapsed time, including the children.<br/>
+  /// 子を含む経過時間
+  pub elapsed: Duration,
+  /// The named parsers run inside the parser.<br/>
+  /// パーサーの内部で実行された名前付きパーサー
+  pub children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+  /// Returns the elapsed time, excluding the children.<br/>
+  /// 子を除いた経過時間を返す。
+  pub fn self_time(&self) -> Duration {
+    let children = self.children.iter().map(|child| child.elapsed).sum();
+    self.elapsed.saturating_sub(children)
+  }
+}
+
+/// The profile of a rule, that is the parsers of a name.<br/>
+/// 規則、つまり同じ名前のパーサーのプロファイル。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleProfile {
+  /// The name of the rule.<br/>
+  /// 規則の名前
+  pub name: String,
+  /// The number of the invocations.<br/>
+  /// 呼び出し回数
+  pub invocations: usize,
+  /// The number of the successes.<br/>
+  /// 成功回数
+  pub successes: usize,
+  /// The number of the uncommitted failures, from which the enclosing parsers backtrack.<br/>
+  /// 外側のパーサーがバックトラックする、コミットされていない失敗の回数
+  pub backtracks: usize,
+  /// The elapsed time, including the children. A recursive invocation is counted once.<br/>
+  /// 子を含む経過時間。再帰的な呼び出しは一度だけ数える。
+  pub total_time: Duration,
+  /// The elapsed time, excluding the children.<br/>
+  /// 子を除いた経過時間
+  pub self_time: Duration,
+}
+
+/// A [Tracer] that records the call tree of the named parsers.<br/>
+/// 名前付きパーサーの呼び出しツリーを記録する[Tracer]。
+///
+/// # Example
+///
+/// ```rust
+/// use oni_comb_parser_rs::prelude::*;
+///
+/// let digit = elm_digit().name("digit");
+/// let number = digit.of_many1().name("number");
+/// let parser = (number.clone() - elm(b'+') + number).name("sum");
+///
+/// let (result, trace) = parser.parse_with_tracer(b"1+23", Trace::new());
+///
+/// assert!(result.is_success());
+/// assert_eq!(trace.roots()[0].children.len(), 2);
+///
+/// let profile = trace.profile();
+/// let digit = profile.iter().find(|rule| rule.name == "digit").unwrap();
+/// // `of_many1` backtracks from the digit that fails to match `+` and the end of input.
+/// assert_eq!((digit.invocations, digit.successes, digit.backtracks), (5, 3, 2));
+///
+/// println!("{}", trace.call_tree());
+/// println!("{}", trace.folded_stacks());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+  roots: Vec<TraceNode>,
+  stack: Vec<TraceNode>,
+}
+
+impl Tracer for Trace {
+  fn trace(&mut self, event: TraceEvent) {
+    match event {
+      TraceEvent::Enter { name, offset } => self.stack.push(TraceNode {
+        name: name.to_string(),
+        offset,
+        outcome: None,
+        elapsed: Duration::ZERO,
+        children: vec![],
+      }),
+      TraceEvent::Exit { outcome, elapsed, .. } => {
+        if let Some(mut node) = self.stack.pop() {
+          node.outcome = Some(outcome);
+          node.elapsed = elapsed;
+          match self.stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => self.roots.push(node),
+          }
+        }
+      }
+    }
+  }
+}
+
+impl Trace {
+  /// Returns an empty [Trace].<br/>
+  /// 空の[Trace]を返す。
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the named parsers run at the top level.<br/>
+  /// 最上位で実行された名前付きパーサーを返す。
+  pub fn roots(&self) -> &[TraceNode] {
+    &self.roots
+  }
+
+  /// Returns the call tree as text, a line per invocation indented by the depth.<br/>
+  /// 呼び出しツリーを、呼び出しごとに深さでインデントした行のテキストとして返す。
+  pub fn call_tree(&self) -> String {
+    fn write_node(out: &mut String, node: &TraceNode, depth: usize) {
+      let outcome = match node.outcome {
+        Some(TraceOutcome::Success { length }) => format!("ok +{}", length),
+        Some(TraceOutcome::Failure { committed: true }) => "failed (committed)".to_string(),
+        Some(TraceOutcome::Failure { committed: false }) => "failed".to_string(),
+        None => "unfinished".to_string(),
+      };
+      let indent = "  ".repeat(depth);
+      let _ = writeln!(
+        out,
+        "{}{} @{} {} {:?}",
+        indent, node.name, node.offset, outcome, node.elapsed
+      );
+      for child in &node.children {
+        write_node(out, child, depth + 1);
+      }
+    }
+    let mut out = String::new();
+    for root in &self.roots {
+      write_node(&mut out, root, 0);
+    }
+    out
+  }
+
+  /// Returns the profiles of the rules, the hot spots first: in descending order of the self time.<br/>
+  /// 規則のプロファイルを、ホットスポットから順に、つまり自己時間の降順で返す。
+  pub fn profile(&self) -> Vec<RuleProfile> {
+    fn visit<'t>(node: &'t TraceNode, stack: &mut Vec<&'t str>, profiles: &mut HashMap<&'t str, RuleProfile>) {
+      let recursive = stack.contains(&node.name.as_str());
+      let profile = profiles.entry(&node.name).or_default();
+      profile.invocations += 1;
+      match node.outcome {
+        Some(TraceOutcome::Success { .. }) => profile.successes += 1,
+        Some(outcome) if outcome.is_backtrack() => profile.backtracks += 1,
+        _ => (),
+      }
+      if !recursive {
+        profile.total_time += node.elapsed;
+      }
+      profile.self_time += node.self_time();
+      stack.push(&node.name);
+      for child in &node.children {
+        visit(child, stack, profiles);
+      }
+      stack.pop();
+    }
+    let mut profiles = HashMap::new();
+    for root in &self.roots {
+      visit(root, &mut vec![], &mut profiles);
+    }
+    let mut profiles = profiles
+      .into_iter()
+      .map(|(name, profile)| RuleProfile {
+        name: name.to_string(),
+        ..profile
+      })
+      .collect::<Vec<_>>();
+    profiles.sort_by(|a, b| b.self_time.cmp(&a.self_time).then_with(|| a.name.cmp(&b.name)));
+    profiles
+  }
+
+  /// Returns the folded stacks for flamegraph tools, a line of `root;child;... nanoseconds` per call stack.<br/>
+  /// フレームグラフツール向けの畳み込みスタックを、呼び出しスタックごとに`root;child;... ナノ秒`の行として返す。
+  ///
+  /// The stacks are weighted by the self time, and the same stacks are merged.<br/>
+  /// スタックは自己時間で重み付けされ、同じスタックは併合される。
+  pub fn folded_stacks(&self) -> String {
+    fn visit(node: &TraceNode, path: &str, stacks: &mut BTreeMap<String, u128>) {
+      let path = if path.is_empty() {
+        node.name.clone()
+      } else {
+        format!("{};{}", path, node.name)
+      };
+      *stacks.entry(path.clone()).or_default() += node.self_time().as_nanos();
+      for child in &node.children {
+        visit(child, &path, stacks);
+      }
+    }
+    let mut stacks = BTreeMap::new();
+    for root in &self.roots {
+      visit(root, "", &mut stacks);
+    }
+    stacks
+      .into_iter()
+      .map(|(stack, nanos)| format!("{} {}\n", stack, nanos))
+      .collect()
+  }
+}
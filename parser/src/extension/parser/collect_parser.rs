@@ -5,4 +5,14 @@ pub trait CollectParser<'a>: ParserRunner<'a> {
   fn collect(self) -> Self::P<'a, Self::Input, &'a [Self::Input]>
   where
     Self::Output: Debug + 'a;
+
+  /// Returns the parser that copies the matched part of the input into a `Vec`.<br/>
+  /// 入力の一致した部分を`Vec`に複製するパーサーを返す。
+  ///
+  /// Unlike `collect`, the value does not borrow the input, so the parser is free when the parser is free.<br/>
+  /// `collect`と異なり値は入力を借用しないため、パーサーが自由であれば自由となる。
+  fn collect_vec(self) -> Self::P<'a, Self::Input, Vec<Self::Input>>
+  where
+    Self::Input: Clone,
+    Self::Output: Debug + 'a;
 }
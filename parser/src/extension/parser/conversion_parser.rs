@@ -1,11 +1,13 @@
+use crate::core::{FromConversionError, ThreadSafe};
 use crate::extension::parser::OperatorParser;
 use std::fmt::Debug;
 
 pub trait ConversionParser<'a>: OperatorParser<'a> {
-  fn map_res<B, E, F>(self, f: F) -> Self::P<'a, Self::Input, B>
+  fn map_res<B, R, F>(self, f: F) -> Self::P<'a, Self::Input, B>
   where
-    F: Fn(Self::Output) -> Result<B, E> + 'a,
-    E: Debug,
+    F: Fn(Self::Output) -> Result<B, R> + ThreadSafe + 'a,
+    R: Debug,
+    Self::Error: FromConversionError<R>,
     Self::Output: Debug + 'a,
     B: Debug + 'a;
 }
@@ -32,4 +32,9 @@ pub trait LoggingParser<'a>: ParserRunner<'a> {
   where
     Self::Input: Debug,
     Self::Output: Debug + 'a;
+
+  fn expect(self, name: &'a str) -> Self::P<'a, Self::Input, Self::Output>
+  where
+    Self::Input: Debug,
+    Self::Output: Debug + 'a;
 }
@@ -1,3 +1,4 @@
+use crate::core::Spanned;
 use crate::extension::parser::OperatorParser;
 use std::fmt::Debug;
 
@@ -9,4 +10,8 @@ pub trait OffsetParser<'a>: OperatorParser<'a> {
   fn next_offset(self) -> Self::P<'a, Self::Input, usize>
   where
     Self::Output: Debug + 'a;
+
+  fn spanned(self) -> Self::P<'a, Self::Input, Spanned<Self::Output>>
+  where
+    Self::Output: Debug + 'a;
 }
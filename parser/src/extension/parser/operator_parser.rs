@@ -1,4 +1,4 @@
-use crate::core::ParserRunner;
+use crate::core::{ParserRunner, ThreadSafe};
 use std::fmt::Debug;
 
 pub trait OperatorParser<'a>: ParserRunner<'a> {
@@ -27,10 +27,14 @@ pub trait OperatorParser<'a>: ParserRunner<'a> {
   where
     Self::Output: Debug + 'a;
 
+  fn recover_with(self, recovery: Self::P<'a, Self::Input, Self::Output>) -> Self::P<'a, Self::Input, Self::Output>
+  where
+    Self::Output: Debug + 'a;
+
   fn scan_right1<BOP>(self, op: Self::P<'a, Self::Input, BOP>) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a;
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a;
 
   fn chain_right0<BOP>(
     self,
@@ -38,8 +42,8 @@ pub trait OperatorParser<'a>: ParserRunner<'a> {
     x: Self::Output,
   ) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a;
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a;
 
   fn chain_left0<BOP>(
     self,
@@ -47,13 +51,13 @@ pub trait OperatorParser<'a>: ParserRunner<'a> {
     x: Self::Output,
   ) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a;
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a;
 
   fn chain_right1<BOP>(self, op: Self::P<'a, Self::Input, BOP>) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a;
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a;
 
   fn chain_left1<BOP>(self, op: Self::P<'a, Self::Input, BOP>) -> Self::P<'a, Self::Input, Self::Output>
   where
@@ -66,8 +70,8 @@ pub trait OperatorParser<'a>: ParserRunner<'a> {
     x: Self::Output,
   ) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a;
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a;
 
   fn rest_left1<BOP>(
     self,
@@ -75,6 +79,6 @@ pub trait OperatorParser<'a>: ParserRunner<'a> {
     x: Self::Output,
   ) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a;
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a;
 }
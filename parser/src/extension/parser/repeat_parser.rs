@@ -1,3 +1,4 @@
+use crate::core::{GrammarError, ThreadSafe};
 use crate::extension::parser::OperatorParser;
 use crate::utils::RangeArgument;
 use std::fmt::Debug;
@@ -5,7 +6,14 @@ use std::fmt::Debug;
 pub trait RepeatParser<'a>: OperatorParser<'a> {
   fn repeat<R>(self, range: R) -> Self::P<'a, Self::Input, Vec<Self::Output>>
   where
-    R: RangeArgument<usize> + Debug + 'a,
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
+    Self::Output: Debug + 'a,
+    Self: Sized;
+
+  #[allow(clippy::type_complexity)]
+  fn try_repeat<R>(self, range: R) -> Result<Self::P<'a, Self::Input, Vec<Self::Output>>, GrammarError>
+  where
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
     Self::Output: Debug + 'a,
     Self: Sized;
 
@@ -31,7 +39,7 @@ pub trait RepeatParser<'a>: OperatorParser<'a> {
     separator: Option<Self::P<'a, Self::Input, B>>,
   ) -> Self::P<'a, Self::Input, Vec<Self::Output>>
   where
-    R: RangeArgument<usize> + Debug + 'a,
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
     Self::Output: Debug + 'a,
     B: Debug + 'a;
 
@@ -1,3 +1,4 @@
+pub use binary_parsers::*;
 pub use cache_parsers::*;
 pub use collect_parsers::*;
 pub use conversion_parsers::*;
@@ -10,9 +11,11 @@ pub use offset_parsers::*;
 pub use operator_parsers::*;
 pub use peek_parsers::*;
 pub use primitive_parsers::*;
+pub use regex_parsers::*;
 pub use repeat_parsers::*;
 pub use skip_parsers::*;
 pub use taken_parsers::*;
+pub use token_parsers::*;
 
 mod collect_parsers;
 mod conversion_parsers;
@@ -23,10 +26,13 @@ mod operator_parsers;
 mod repeat_parsers;
 mod skip_parsers;
 
+mod binary_parsers;
 mod cache_parsers;
 mod element_parsers;
 mod elements_parsers;
 mod logging_parsers;
 mod peek_parsers;
 mod primitive_parsers;
+mod regex_parsers;
 mod taken_parsers;
+mod token_parsers;
@@ -0,0 +1,202 @@
+use crate::core::{GrammarError, Parsers, ThreadSafe};
+use std::fmt::Debug;
+
+/// A number that is decoded from a fixed number of bytes.<br/>
+/// 固定長のバイト列から復号される数値。
+pub trait FromBytes: Sized {
+  /// The number of bytes.<br/>
+  /// バイト数
+  const SIZE: usize;
+
+  /// Decodes the number from the bytes in big-endian order.<br/>
+  /// ビッグエンディアンのバイト列から数値を復号する。
+  fn from_be(bytes: &[u8]) -> Self;
+
+  /// Decodes the number from the bytes in little-endian order.<br/>
+  /// リトルエンディアンのバイト列から数値を復号する。
+  fn from_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_from_bytes {
+  ($($t:ty),*) => {
+    $(
+      impl FromBytes for $t {
+        const SIZE: usize = std::mem::size_of::<$t>();
+
+        fn from_be(bytes: &[u8]) -> Self {
+          <$t>::from_be_bytes(bytes.try_into().unwrap())
+        }
+
+        fn from_le(bytes: &[u8]) -> Self {
+          <$t>::from_le_bytes(bytes.try_into().unwrap())
+        }
+      }
+    )*
+  };
+}
+
+impl_from_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// A failure of reading bits by a [BitCursor].<br/>
+/// [BitCursor]によるビットの読み取りの失敗。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitError {
+  /// The input ends before the bits.<br/>
+  /// ビットの前に入力が終わった。
+  Incomplete,
+  /// The bits differ from the expected pattern.<br/>
+  /// ビットが期待するパターンと異なる。
+  Mismatch { expected: u64, found: u64, width: usize },
+}
+
+/// A cursor over the bits of the input, at a byte offset and a bit offset within that byte.<br/>
+/// 入力のビット上のカーソル。バイトオフセットとそのバイト内のビットオフセットを指す。
+///
+/// Bits are read from the most significant bit of each byte. A failed read does not move the cursor.<br/>
+/// 各バイトの最上位ビットから読み取る。読み取りに失敗した場合、カーソルは移動しない。
+#[derive(Debug, Clone)]
+pub struct BitCursor<'a> {
+  input: &'a [u8],
+  byte_offset: usize,
+  bit_offset: usize,
+}
+
+impl<'a> BitCursor<'a> {
+  /// Creates a cursor at the first bit of the input.<br/>
+  /// 入力の最初のビットを指すカーソルを生成する。
+  pub fn new(input: &'a [u8]) -> Self {
+    Self {
+      input,
+      byte_offset: 0,
+      bit_offset: 0,
+    }
+  }
+
+  /// Returns the byte offset and the bit offset within that byte.<br/>
+  /// バイトオフセットと、そのバイト内のビットオフセットを返す。
+  pub fn position(&self) -> (usize, usize) {
+    (self.byte_offset, self.bit_offset)
+  }
+
+  /// Returns the number of bytes touched so far, including a partly read byte.<br/>
+  /// 途中まで読んだバイトを含む、これまでに触れたバイト数を返す。
+  pub fn consumed_bytes(&self) -> usize {
+    self.byte_offset + usize::from(self.bit_offset != 0)
+  }
+
+  fn remaining_bits(&self) -> usize {
+    (self.input.len() - self.byte_offset) * 8 - self.bit_offset
+  }
+
+  fn advance(&mut self, n: usize) {
+    let bit = self.bit_offset + n;
+    self.byte_offset += bit / 8;
+    self.bit_offset = bit % 8;
+  }
+
+  /// Reads a field of `n` bits as an unsigned number.<br/>
+  /// `n`ビットのフィールドを符号なしの数値として読み取る。
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n` is greater than 64.<br/>
+  /// `n`が64より大きい場合はパニックする。
+  pub fn take_bits(&mut self, n: usize) -> Result<u64, BitError> {
+    assert!(n <= 64, "the width of a bit field must be up to 64: {}", n);
+    if self.remaining_bits() < n {
+      return Err(BitError::Incomplete);
+    }
+    let start = self.byte_offset * 8 + self.bit_offset;
+    let field = (start..start + n).fold(0u64, |field, bit| {
+      (field << 1) | ((self.input[bit / 8] >> (7 - bit % 8)) & 1) as u64
+    });
+    self.advance(n);
+    Ok(field)
+  }
+
+  /// Reads a bit as a flag.<br/>
+  /// 1ビットをフラグとして読み取る。
+  pub fn take_bit(&mut self) -> Result<bool, BitError> {
+    self.take_bits(1).map(|bit| bit == 1)
+  }
+
+  /// Reads a field of `n` bits, and fails unless it equals to `pattern`.<br/>
+  /// `n`ビットのフィールドを読み取り、`pattern`と等しくなければ失敗する。
+  pub fn tag_bits(&mut self, pattern: u64, n: usize) -> Result<u64, BitError> {
+    let saved = self.clone();
+    let found = self.take_bits(n)?;
+    if found == pattern {
+      Ok(found)
+    } else {
+      *self = saved;
+      Err(BitError::Mismatch {
+        expected: pattern,
+        found,
+        width: n,
+      })
+    }
+  }
+
+  /// Skips `n` bits.<br/>
+  /// `n`ビットを読み飛ばす。
+  pub fn skip_bits(&mut self, n: usize) -> Result<(), BitError> {
+    if self.remaining_bits() < n {
+      return Err(BitError::Incomplete);
+    }
+    self.advance(n);
+    Ok(())
+  }
+
+  /// Skips the rest bits of the current byte.<br/>
+  /// 現在のバイトの残りのビットを読み飛ばす。
+  pub fn align(&mut self) {
+    if self.bit_offset != 0 {
+      self.byte_offset += 1;
+      self.bit_offset = 0;
+    }
+  }
+}
+
+/// Parsers on binary formats, reading the bytes of the input.<br/>
+/// 入力のバイト列を読み取る、バイナリ形式のパーサー。
+pub trait BinaryParsers: Parsers {
+  fn be<'a, N>() -> Self::P<'a, u8, N>
+  where
+    N: FromBytes + 'a;
+
+  fn le<'a, N>() -> Self::P<'a, u8, N>
+  where
+    N: FromBytes + 'a;
+
+  fn uleb128<'a>() -> Self::P<'a, u8, u64>;
+
+  fn sleb128<'a>() -> Self::P<'a, u8, i64>;
+
+  fn length_prefixed<'a, N, A>(len: Self::P<'a, u8, N>, body: Self::P<'a, u8, A>) -> Self::P<'a, u8, A>
+  where
+    N: TryInto<usize> + Debug + 'a,
+    A: 'a;
+
+  fn bits<'a, A, F>(f: F) -> Self::P<'a, u8, A>
+  where
+    F: Fn(&mut BitCursor) -> Result<A, BitError> + ThreadSafe + 'a,
+    A: 'a;
+
+  fn bit_fields<'a>(widths: &[usize]) -> Self::P<'a, u8, Vec<u64>> {
+    assert!(
+      widths.iter().all(|w| (1..=64).contains(w)),
+      "the widths of bit fields must be from 1 to 64: {:?}",
+      widths
+    );
+    let widths = widths.to_vec();
+    Self::bits(move |bp| widths.iter().map(|&width| bp.take_bits(width)).collect())
+  }
+
+  /// `bit_fields`, that returns an error for a width out of 1 to 64
+  fn try_bit_fields<'a>(widths: &[usize]) -> Result<Self::P<'a, u8, Vec<u64>>, GrammarError> {
+    match widths.iter().find(|width| !(1..=64).contains(*width)) {
+      Some(&width) => Err(GrammarError::BitWidth(width)),
+      None => Ok(Self::bit_fields(widths)),
+    }
+  }
+}
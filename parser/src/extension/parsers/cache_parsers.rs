@@ -6,4 +6,10 @@ pub trait CacheParsers: Parsers {
   where
     I: Clone + 'a,
     A: Clone + Debug + 'a;
+
+  fn left_rec<'a, I, A, F>(f: F) -> Self::P<'a, I, A>
+  where
+    F: FnOnce(Self::P<'a, I, A>) -> Self::P<'a, I, A>,
+    I: Clone + 'a,
+    A: Clone + Debug + 'a;
 }
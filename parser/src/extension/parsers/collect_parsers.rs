@@ -5,4 +5,9 @@ pub trait CollectParsers: Parsers {
   fn collect<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, &'a [I]>
   where
     A: Debug + 'a;
+
+  fn collect_vec<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, Vec<I>>
+  where
+    I: Clone,
+    A: Debug + 'a;
 }
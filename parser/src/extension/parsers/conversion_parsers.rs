@@ -1,27 +1,33 @@
 use std::fmt::Debug;
-use std::str::FromStr;
+use std::num::ParseFloatError;
+use std::str::{FromStr, Utf8Error};
 
-use crate::core::Parsers;
+use crate::core::{FromConversionError, Parsers, ThreadSafe};
 
 pub trait ConversionParsers: Parsers {
-  fn map_res<'a, I, A, B, E, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
+  fn map_res<'a, I, A, B, R, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
   where
-    F: Fn(A) -> Result<B, E> + 'a,
-    E: Debug,
+    F: Fn(A) -> Result<B, R> + ThreadSafe + 'a,
+    R: Debug,
+    Self::Error: FromConversionError<R>,
     A: Debug + 'a,
     B: Debug + 'a;
 
-  fn map_opt<'a, I, A, B, E, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
+  fn map_opt<'a, I, A, B, R, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
   where
-    F: Fn(A) -> Option<B> + 'a,
+    F: Fn(A) -> Option<B> + ThreadSafe + 'a,
     A: Debug + 'a,
     B: Debug + 'a;
 
-  fn convert_from_bytes_to_str<'a, I>(parser: Self::P<'a, I, &'a [u8]>) -> Self::P<'a, I, &'a str> {
+  fn convert_from_bytes_to_str<'a, I>(parser: Self::P<'a, I, &'a [u8]>) -> Self::P<'a, I, &'a str>
+  where
+    Self::Error: FromConversionError<Utf8Error>, {
     Self::map_res(parser, std::str::from_utf8)
   }
 
-  fn convert_from_str_to_f64<'a, I>(parser: Self::P<'a, I, &'a str>) -> Self::P<'a, I, f64> {
+  fn convert_from_str_to_f64<'a, I>(parser: Self::P<'a, I, &'a str>) -> Self::P<'a, I, f64>
+  where
+    Self::Error: FromConversionError<ParseFloatError>, {
     Self::map_res(parser, f64::from_str)
   }
 }
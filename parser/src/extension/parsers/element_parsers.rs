@@ -1,4 +1,4 @@
-use crate::core::{Element, Parsers};
+use crate::core::{Element, GeneralCategory, Parsers, TextInput, ThreadSafe, UnicodeElement};
 use crate::utils::Set;
 use std::fmt::{Debug, Display};
 
@@ -29,12 +29,12 @@ pub trait ElementParsers: Parsers {
 
   fn elm_pred_ref<'a, I, F>(f: F) -> Self::P<'a, I, &'a I>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + PartialEq + 'a;
 
   fn elm_pred<'a, I, F>(f: F) -> Self::P<'a, I, I>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Clone + PartialEq + 'a, {
     Self::map(Self::elm_pred_ref(f), Clone::clone)
   }
@@ -109,47 +109,121 @@ pub trait ElementParsers: Parsers {
     Self::map(Self::elm_oct_digit_ref(), Clone::clone)
   }
 
+  fn elm_unicode_alpha_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a;
+
+  fn elm_unicode_alpha<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::map(Self::elm_unicode_alpha_ref(), Clone::clone)
+  }
+
+  fn elm_unicode_digit_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a;
+
+  fn elm_unicode_digit<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::map(Self::elm_unicode_digit_ref(), Clone::clone)
+  }
+
+  fn elm_unicode_alpha_digit_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a;
+
+  fn elm_unicode_alpha_digit<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::map(Self::elm_unicode_alpha_digit_ref(), Clone::clone)
+  }
+
+  fn elm_unicode_space_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a;
+
+  fn elm_unicode_space<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::map(Self::elm_unicode_space_ref(), Clone::clone)
+  }
+
+  fn elm_unicode_category_ref<'a, I>(categories: &[GeneralCategory]) -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a;
+
+  fn elm_unicode_category<'a, I>(categories: &[GeneralCategory]) -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::map(Self::elm_unicode_category_ref(categories), Clone::clone)
+  }
+
+  fn elm_xid_start_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a;
+
+  fn elm_xid_start<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::map(Self::elm_xid_start_ref(), Clone::clone)
+  }
+
+  fn elm_xid_continue_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a;
+
+  fn elm_xid_continue<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::map(Self::elm_xid_continue_ref(), Clone::clone)
+  }
+
+  fn ident_xid<'a, I>() -> Self::P<'a, I, &'a [I]>
+  where
+    I: TextInput + 'a;
+
   fn elm_ref_of<'a, I, S>(set: &'a S) -> Self::P<'a, I, &'a I>
   where
-    I: PartialEq + Display + Debug + 'a,
-    S: Set<I> + ?Sized;
+    I: PartialEq + Display + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized;
 
   fn elm_of<'a, I, S>(set: &'a S) -> Self::P<'a, I, I>
   where
-    I: PartialEq + Clone + Display + Debug + 'a,
-    S: Set<I> + ?Sized, {
+    I: PartialEq + Clone + Display + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
     Self::map(Self::elm_ref_of(set), Clone::clone)
   }
 
   fn elm_ref_in<'a, I>(start: I, end: I) -> Self::P<'a, I, &'a I>
   where
-    I: PartialEq + PartialOrd + Display + Debug + Copy + 'a;
+    I: PartialEq + PartialOrd + Display + Debug + Copy + ThreadSafe + 'a;
 
   fn elm_in<'a, I>(start: I, end: I) -> Self::P<'a, I, I>
   where
-    I: PartialEq + PartialOrd + Display + Debug + Copy + Clone + 'a, {
+    I: PartialEq + PartialOrd + Display + Debug + Copy + Clone + ThreadSafe + 'a, {
     Self::map(Self::elm_ref_in(start, end), Clone::clone)
   }
 
   fn elm_ref_from_until<'a, I>(start: I, end: I) -> Self::P<'a, I, &'a I>
   where
-    I: PartialEq + PartialOrd + Display + Debug + Copy + 'a;
+    I: PartialEq + PartialOrd + Display + Debug + Copy + ThreadSafe + 'a;
 
   fn elm_from_until<'a, I>(start: I, end: I) -> Self::P<'a, I, I>
   where
-    I: PartialEq + PartialOrd + Display + Debug + Copy + Clone + 'a, {
+    I: PartialEq + PartialOrd + Display + Debug + Copy + Clone + ThreadSafe + 'a, {
     Self::map(Self::elm_ref_from_until(start, end), Clone::clone)
   }
 
   fn none_ref_of<'a, I, S>(set: &'a S) -> Self::P<'a, I, &'a I>
   where
-    I: PartialEq + Display + Debug + 'a,
-    S: Set<I> + ?Sized;
+    I: PartialEq + Display + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized;
 
   fn none_of<'a, I, S>(set: &'a S) -> Self::P<'a, I, I>
   where
-    I: PartialEq + Display + Clone + Debug + 'a,
-    S: Set<I> + ?Sized, {
+    I: PartialEq + Display + Clone + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
     Self::map(Self::none_ref_of(set), Clone::clone)
   }
 }
@@ -1,19 +1,24 @@
-use crate::core::Parsers;
+use crate::core::{GrammarError, Parsers, TextInput, ThreadSafe};
 use std::fmt::Debug;
 
 pub trait ElementsParsers: Parsers {
-  fn seq<'a, 'b, I>(tag: &'b [I]) -> Self::P<'a, I, &'a [I]>
+  fn seq<'a, I>(seq: &'a [I]) -> Self::P<'a, I, &'a [I]>
   where
-    I: PartialEq + Debug + 'a,
-    'b: 'a;
+    I: Clone + PartialEq + Debug + ThreadSafe + 'a;
 
-  fn tag<'a, 'b>(tag: &'b str) -> Self::P<'a, char, &'a str>
+  fn tag<'a, I>(tag: &'a str) -> Self::P<'a, I, &'a str>
   where
-    'b: 'a;
+    I: TextInput + 'a;
 
-  fn tag_no_case<'a, 'b>(tag: &'b str) -> Self::P<'a, char, &'a str>
+  fn tag_no_case<'a, I>(tag: &'a str) -> Self::P<'a, I, &'a str>
   where
-    'b: 'a;
+    I: TextInput + 'a;
 
-  fn regex<'a>(pattern: &str) -> Self::P<'a, char, String>;
+  fn regex<'a, I>(pattern: &str) -> Self::P<'a, I, I::Text<'a>>
+  where
+    I: TextInput + 'a;
+
+  fn try_regex<'a, I>(pattern: &str) -> Result<Self::P<'a, I, I::Text<'a>>, GrammarError>
+  where
+    I: TextInput + 'a;
 }
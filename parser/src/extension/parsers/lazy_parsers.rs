@@ -1,9 +1,14 @@
-use crate::core::Parsers;
+use crate::core::{Parsers, ThreadSafe};
 use std::fmt::Debug;
 
 pub trait LazyParsers: Parsers {
   fn lazy<'a, I, A, F>(f: F) -> Self::P<'a, I, A>
   where
-    F: Fn() -> Self::P<'a, I, A> + 'a,
+    F: Fn() -> Self::P<'a, I, A> + ThreadSafe + 'a,
+    A: Debug + 'a;
+
+  fn recursive<'a, I, A, F>(f: F) -> Self::P<'a, I, A>
+  where
+    F: Fn(Self::P<'a, I, A>) -> Self::P<'a, I, A>,
     A: Debug + 'a;
 }
@@ -1,4 +1,4 @@
-use crate::core::Parsers;
+use crate::core::{Parsers, Spanned};
 use std::fmt::Debug;
 
 pub trait OffsetParsers: Parsers {
@@ -9,4 +9,8 @@ pub trait OffsetParsers: Parsers {
   fn next_offset<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, usize>
   where
     A: Debug + 'a;
+
+  fn spanned<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, Spanned<A>>
+  where
+    A: Debug + 'a;
 }
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use crate::core::Parsers;
+use crate::core::{Parsers, ThreadSafe};
 
 pub trait OperatorParsers: Parsers {
   fn exists<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, bool>
@@ -14,7 +14,7 @@ pub trait OperatorParsers: Parsers {
   fn opt<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, Option<A>>
   where
     A: Clone + Debug + 'a, {
-    Self::or(Self::map(Self::attempt(parser), Some), Self::successful(None))
+    Self::or(Self::map(Self::attempt(parser), Some), Self::successful_lazy(|| None))
   }
 
   fn or<'a, I, A>(parser1: Self::P<'a, I, A>, parser2: Self::P<'a, I, A>) -> Self::P<'a, I, A>
@@ -30,29 +30,37 @@ pub trait OperatorParsers: Parsers {
   where
     A: Debug + 'a;
 
+  fn recover_with<'a, I, A>(parser: Self::P<'a, I, A>, recovery: Self::P<'a, I, A>) -> Self::P<'a, I, A>
+  where
+    A: Debug + 'a;
+
+  fn skip_until<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, A>
+  where
+    A: Debug + 'a;
+
   fn scan_right1<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>) -> Self::P<'a, I, A>
   where
-    BOP: Fn(A, A) -> A + 'a,
-    A: Clone + Debug + 'a;
+    BOP: Fn(A, A) -> A + ThreadSafe + 'a,
+    A: Clone + Debug + ThreadSafe + 'a;
 
   fn chain_right0<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>, x: A) -> Self::P<'a, I, A>
   where
-    BOP: Fn(A, A) -> A + 'a,
-    A: Clone + Debug + 'a, {
+    BOP: Fn(A, A) -> A + ThreadSafe + 'a,
+    A: Clone + Debug + ThreadSafe + 'a, {
     Self::or(Self::chain_right1(p, op), Self::successful(x.clone()))
   }
 
   fn chain_left0<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>, x: A) -> Self::P<'a, I, A>
   where
-    BOP: Fn(A, A) -> A + 'a,
-    A: Clone + Debug + 'a, {
+    BOP: Fn(A, A) -> A + ThreadSafe + 'a,
+    A: Clone + Debug + ThreadSafe + 'a, {
     Self::or(Self::chain_left1(p, op), Self::successful(x.clone()))
   }
 
   fn chain_right1<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>) -> Self::P<'a, I, A>
   where
-    BOP: Fn(A, A) -> A + 'a,
-    A: Clone + Debug + 'a, {
+    BOP: Fn(A, A) -> A + ThreadSafe + 'a,
+    A: Clone + Debug + ThreadSafe + 'a, {
     Self::scan_right1(p, op)
   }
 
@@ -63,11 +71,11 @@ pub trait OperatorParsers: Parsers {
 
   fn rest_right1<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>, x: A) -> Self::P<'a, I, A>
   where
-    BOP: Fn(A, A) -> A + 'a,
-    A: Clone + Debug + 'a;
+    BOP: Fn(A, A) -> A + ThreadSafe + 'a,
+    A: Clone + Debug + ThreadSafe + 'a;
 
   fn rest_left1<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>, x: A) -> Self::P<'a, I, A>
   where
-    BOP: Fn(A, A) -> A + 'a,
-    A: Clone + Debug + 'a;
+    BOP: Fn(A, A) -> A + ThreadSafe + 'a,
+    A: Clone + Debug + ThreadSafe + 'a;
 }
@@ -0,0 +1,7 @@
+use crate::core::{Parsers, Regex, RegexInput, RegexMatch};
+
+pub trait RegexParsers: Parsers {
+  fn regex_match<'a, I>(regex: Regex) -> Self::P<'a, I, RegexMatch<'a, I>>
+  where
+    I: RegexInput + Copy + 'a;
+}
@@ -1,7 +1,7 @@
-use crate::core::Parsers;
+use crate::core::{GrammarError, Parsers, ThreadSafe};
 use std::fmt::Debug;
 
-use crate::utils::RangeArgument;
+use crate::utils::{Bound, RangeArgument};
 
 pub trait RepeatParsers: Parsers {
   /// `rep(5)` repeat p exactly 5 times
@@ -10,11 +10,34 @@ pub trait RepeatParsers: Parsers {
   /// `rep(1..4)` match p at least 1 and at most 3 times
   fn repeat<'a, I, A, R>(parser: Self::P<'a, I, A>, range: R) -> Self::P<'a, I, Vec<A>>
   where
-    R: RangeArgument<usize> + Debug + 'a,
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
     A: Debug + 'a, {
     Self::repeat_sep::<'a, I, A, (), R>(parser, range, None)
   }
 
+  /// `repeat`, that returns an error for a range that no count satisfies, such as `3..1`
+  fn try_repeat<'a, I, A, R>(parser: Self::P<'a, I, A>, range: R) -> Result<Self::P<'a, I, Vec<A>>, GrammarError>
+  where
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
+    A: Debug + 'a, {
+    let min = match range.start() {
+      Bound::Included(&n) => Some(n),
+      Bound::Excluded(&n) => n.checked_add(1),
+      Bound::Unbounded => Some(0),
+    };
+    let satisfiable = match (min, range.end()) {
+      (None, _) => false,
+      (Some(min), Bound::Included(&max)) => min <= max,
+      (Some(min), Bound::Excluded(&max)) => min < max,
+      (Some(_), Bound::Unbounded) => true,
+    };
+    if satisfiable {
+      Ok(Self::repeat(parser, range))
+    } else {
+      Err(GrammarError::Range(format!("{:?}", range)))
+    }
+  }
+
   fn many0<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, Vec<A>>
   where
     A: Debug + 'a, {
@@ -45,7 +68,7 @@ pub trait RepeatParsers: Parsers {
     separator: Option<Self::P<'a, I, B>>,
   ) -> Self::P<'a, I, Vec<A>>
   where
-    R: RangeArgument<usize> + Debug + 'a,
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
     A: Debug + 'a,
     B: Debug + 'a;
 
@@ -1,4 +1,4 @@
-use crate::core::Element;
+use crate::core::{Element, TextInput, ThreadSafe};
 use crate::extension::parsers::element_parsers::ElementParsers;
 use std::fmt::Debug;
 
@@ -7,26 +7,36 @@ pub trait TakenParsers: ElementParsers {
 
   fn take_while0<'a, I, F>(f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a;
 
   fn take_while1<'a, I, F>(f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a;
 
   fn take_while_n_m<'a, I, F>(n: usize, m: usize, f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a;
 
   fn take_till0<'a, I, F>(f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a;
 
   fn take_till1<'a, I, F>(f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a;
+
+  fn take_chars_while0<'a, I, F>(f: F) -> Self::P<'a, I, I::Text<'a>>
+  where
+    F: Fn(char) -> bool + ThreadSafe + 'a,
+    I: TextInput + 'a;
+
+  fn take_chars_while1<'a, I, F>(f: F) -> Self::P<'a, I, I::Text<'a>>
+  where
+    F: Fn(char) -> bool + ThreadSafe + 'a,
+    I: TextInput + 'a;
 }
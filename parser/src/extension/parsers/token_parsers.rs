@@ -0,0 +1,8 @@
+use crate::core::{Parsers, ThreadSafe, Token};
+use std::fmt::Debug;
+
+pub trait TokenParsers: Parsers {
+  fn token<'a, K>(kind: K) -> Self::P<'a, Token<K>, &'a Token<K>>
+  where
+    K: PartialEq + Debug + ThreadSafe + 'a;
+}
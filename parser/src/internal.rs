@@ -1,4 +1,21 @@
+use std::marker::PhantomData;
+
+/// Returns the parser of the closure that runs the parsers, which is free if all of them are free.
+///
+/// The closure is written once and compiled twice: with the parsers shadowed by their free closures, for inputs of
+/// any lifetime, and with the parsers themselves, for inputs of their lifetime.
+macro_rules! combine {
+  (($($parser:ident),+) => move |$parse_state:ident| $body:expr) => {
+    match ($($parser.as_free(),)+) {
+      ($(Some($parser),)+) => $crate::core::Parser::free(move |$parse_state| $body),
+      _ => $crate::core::Parser::new(move |$parse_state| $body),
+    }
+  };
+}
+
+pub(crate) use combine;
+
 mod parser_impl;
 mod parsers_impl;
 
-pub(crate) struct ParsersImpl;
+pub(crate) struct ParsersImpl<E = ()>(PhantomData<E>);
@@ -1,16 +1,16 @@
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parser::OperatorParser;
 use std::fmt::Debug;
 use std::ops::Add;
 
-impl<'a, I, A, B> Add<Parser<'a, I, B>> for Parser<'a, I, A>
+impl<'a, I, A, B, E: UserError> Add<Parser<'a, I, B, E>> for Parser<'a, I, A, E>
 where
   A: Clone + Debug + 'a,
   B: Clone + Debug + 'a,
 {
-  type Output = Parser<'a, I, (A, B)>;
+  type Output = Parser<'a, I, (A, B), E>;
 
-  fn add(self, rhs: Parser<'a, I, B>) -> Self::Output {
+  fn add(self, rhs: Parser<'a, I, B, E>) -> Self::Output {
     self.and_then(rhs)
   }
 }
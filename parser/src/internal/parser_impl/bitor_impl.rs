@@ -1,15 +1,15 @@
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parser::OperatorParser;
 use std::fmt::Debug;
 use std::ops::BitOr;
 
-impl<'a, I, A> BitOr for Parser<'a, I, A>
+impl<'a, I, A, E: UserError> BitOr for Parser<'a, I, A, E>
 where
   A: Debug + 'a,
 {
   type Output = Self;
 
-  fn bitor(self, rhs: Parser<'a, I, A>) -> Self::Output {
+  fn bitor(self, rhs: Parser<'a, I, A, E>) -> Self::Output {
     self.or(rhs)
   }
 }
@@ -1,11 +1,11 @@
 use std::fmt::Debug;
 
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parser::CacheParser;
 use crate::extension::parsers::CacheParsers;
 use crate::internal::ParsersImpl;
 
-impl<'a, I, A> CacheParser<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> CacheParser<'a> for Parser<'a, I, A, E> {
   fn cache(self) -> Self::P<'a, Self::Input, Self::Output>
   where
     Self::Input: Clone + 'a,
@@ -1,14 +1,21 @@
 use std::fmt::Debug;
 
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parser::CollectParser;
 use crate::extension::parsers::CollectParsers;
 use crate::internal::ParsersImpl;
 
-impl<'a, I, A> CollectParser<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> CollectParser<'a> for Parser<'a, I, A, E> {
   fn collect(self) -> Self::P<'a, Self::Input, &'a [Self::Input]>
   where
     Self::Output: Debug + 'a, {
     ParsersImpl::collect(self)
   }
+
+  fn collect_vec(self) -> Self::P<'a, Self::Input, Vec<Self::Input>>
+  where
+    Self::Input: Clone,
+    Self::Output: Debug + 'a, {
+    ParsersImpl::collect_vec(self)
+  }
 }
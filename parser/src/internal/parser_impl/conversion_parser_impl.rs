@@ -1,14 +1,15 @@
-use crate::core::Parser;
+use crate::core::{FromConversionError, Parser, ThreadSafe, UserError};
 use crate::extension::parser::ConversionParser;
 use crate::extension::parsers::ConversionParsers;
 use crate::internal::ParsersImpl;
 use std::fmt::Debug;
 
-impl<'a, I, A> ConversionParser<'a> for Parser<'a, I, A> {
-  fn map_res<B, E, F>(self, f: F) -> Self::P<'a, Self::Input, B>
+impl<'a, I, A, E: UserError> ConversionParser<'a> for Parser<'a, I, A, E> {
+  fn map_res<B, R, F>(self, f: F) -> Self::P<'a, Self::Input, B>
   where
-    F: Fn(Self::Output) -> Result<B, E> + 'a,
-    E: Debug,
+    F: Fn(Self::Output) -> Result<B, R> + ThreadSafe + 'a,
+    R: Debug,
+    E: FromConversionError<R>,
     Self::Output: Debug + 'a,
     B: Debug + 'a, {
     ParsersImpl::map_res(self, f)
@@ -1,10 +1,10 @@
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parser::DiscardParser;
 use crate::extension::parsers::DiscardParsers;
 use crate::internal::ParsersImpl;
 use std::fmt::Debug;
 
-impl<'a, I, A> DiscardParser<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> DiscardParser<'a> for Parser<'a, I, A, E> {
   fn discard(self) -> Self::P<'a, Self::Input, ()>
   where
     Self::Output: Debug + 'a, {
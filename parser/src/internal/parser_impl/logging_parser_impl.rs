@@ -1,10 +1,10 @@
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parser::LoggingParser;
 use crate::extension::parsers::{LogLevel, LoggingParsers};
 use crate::internal::ParsersImpl;
 use std::fmt::Debug;
 
-impl<'a, I, A> LoggingParser<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> LoggingParser<'a> for Parser<'a, I, A, E> {
   fn log(self, name: &'a str, log_level: LogLevel) -> Self::P<'a, Self::Input, Self::Output>
   where
     Self::Input: Debug,
@@ -46,4 +46,11 @@ impl<'a, I, A> LoggingParser<'a> for Parser<'a, I, A> {
     Self::Output: Debug + 'a, {
     ParsersImpl::name(self, name)
   }
+
+  fn expect(self, name: &'a str) -> Self::P<'a, Self::Input, Self::Output>
+  where
+    Self::Input: Debug,
+    Self::Output: Debug + 'a, {
+    ParsersImpl::expect(self, name)
+  }
 }
@@ -1,16 +1,16 @@
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parser::SkipParser;
 use std::fmt::Debug;
 use std::ops::Mul;
 
-impl<'a, I, A, B> Mul<Parser<'a, I, B>> for Parser<'a, I, A>
+impl<'a, I, A, B, E: UserError> Mul<Parser<'a, I, B, E>> for Parser<'a, I, A, E>
 where
   A: Clone + Debug + 'a,
   B: Clone + Debug + 'a,
 {
-  type Output = Parser<'a, I, B>;
+  type Output = Parser<'a, I, B, E>;
 
-  fn mul(self, rhs: Parser<'a, I, B>) -> Self::Output {
+  fn mul(self, rhs: Parser<'a, I, B, E>) -> Self::Output {
     self.skip_left(rhs)
   }
 }
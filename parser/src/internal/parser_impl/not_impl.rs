@@ -1,14 +1,14 @@
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parsers::OperatorParsers;
 use crate::internal::ParsersImpl;
 use std::fmt::Debug;
 use std::ops::Not;
 
-impl<'a, I, A> Not for Parser<'a, I, A>
+impl<'a, I, A, E: UserError> Not for Parser<'a, I, A, E>
 where
   A: Debug + 'a,
 {
-  type Output = Parser<'a, I, ()>;
+  type Output = Parser<'a, I, (), E>;
 
   fn not(self) -> Self::Output {
     ParsersImpl::not(self)
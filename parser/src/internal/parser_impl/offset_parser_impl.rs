@@ -1,10 +1,10 @@
-use crate::core::Parser;
+use crate::core::{Parser, Spanned, UserError};
 use crate::extension::parser::OffsetParser;
 use crate::extension::parsers::OffsetParsers;
 use crate::internal::ParsersImpl;
 use std::fmt::Debug;
 
-impl<'a, I, A> OffsetParser<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> OffsetParser<'a> for Parser<'a, I, A, E> {
   fn last_offset(self) -> Self::P<'a, Self::Input, usize>
   where
     Self::Output: Debug + 'a, {
@@ -16,4 +16,10 @@ impl<'a, I, A> OffsetParser<'a> for Parser<'a, I, A> {
     Self::Output: Debug + 'a, {
     ParsersImpl::next_offset(self)
   }
+
+  fn spanned(self) -> Self::P<'a, Self::Input, Spanned<Self::Output>>
+  where
+    Self::Output: Debug + 'a, {
+    ParsersImpl::spanned(self)
+  }
 }
@@ -1,11 +1,11 @@
 use std::fmt::Debug;
 
-use crate::core::Parser;
+use crate::core::{Parser, ThreadSafe, UserError};
 use crate::extension::parser::OperatorParser;
 use crate::extension::parsers::OperatorParsers;
 use crate::internal::ParsersImpl;
 
-impl<'a, I, A> OperatorParser<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> OperatorParser<'a> for Parser<'a, I, A, E> {
   fn and_then<B>(self, pb: Self::P<'a, Self::Input, B>) -> Self::P<'a, Self::Input, (Self::Output, B)>
   where
     Self::Output: Clone + Debug + 'a,
@@ -43,10 +43,16 @@ impl<'a, I, A> OperatorParser<'a> for Parser<'a, I, A> {
     ParsersImpl::attempt(self)
   }
 
+  fn recover_with(self, recovery: Self::P<'a, Self::Input, Self::Output>) -> Self::P<'a, Self::Input, Self::Output>
+  where
+    Self::Output: Debug + 'a, {
+    ParsersImpl::recover_with(self, recovery)
+  }
+
   fn scan_right1<BOP>(self, op: Self::P<'a, Self::Input, BOP>) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a, {
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a, {
     ParsersImpl::scan_right1(self, op)
   }
 
@@ -56,8 +62,8 @@ impl<'a, I, A> OperatorParser<'a> for Parser<'a, I, A> {
     x: Self::Output,
   ) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a, {
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a, {
     ParsersImpl::chain_right0(self, op, x)
   }
 
@@ -67,15 +73,15 @@ impl<'a, I, A> OperatorParser<'a> for Parser<'a, I, A> {
     x: Self::Output,
   ) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a, {
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a, {
     ParsersImpl::chain_left0(self, op, x)
   }
 
   fn chain_right1<BOP>(self, op: Self::P<'a, Self::Input, BOP>) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a, {
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a, {
     ParsersImpl::chain_right1(self, op)
   }
 
@@ -92,8 +98,8 @@ impl<'a, I, A> OperatorParser<'a> for Parser<'a, I, A> {
     x: Self::Output,
   ) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a, {
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a, {
     ParsersImpl::rest_right1(self, op, x)
   }
 
@@ -103,8 +109,8 @@ impl<'a, I, A> OperatorParser<'a> for Parser<'a, I, A> {
     x: Self::Output,
   ) -> Self::P<'a, Self::Input, Self::Output>
   where
-    BOP: Fn(Self::Output, Self::Output) -> Self::Output + 'a,
-    Self::Output: Clone + Debug + 'a, {
+    BOP: Fn(Self::Output, Self::Output) -> Self::Output + ThreadSafe + 'a,
+    Self::Output: Clone + Debug + ThreadSafe + 'a, {
     ParsersImpl::rest_left1(self, op, x)
   }
 }
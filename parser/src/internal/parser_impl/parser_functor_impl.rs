@@ -1,10 +1,10 @@
-use crate::core::{Parser, ParserFunctor, Parsers};
+use crate::core::{Parser, ParserFunctor, Parsers, ThreadSafe, UserError};
 use crate::internal::ParsersImpl;
 
-impl<'a, I, A> ParserFunctor<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> ParserFunctor<'a> for Parser<'a, I, A, E> {
   fn map<B, F>(self, f: F) -> Self::P<'a, Self::Input, B>
   where
-    F: Fn(Self::Output) -> B + 'a,
+    F: Fn(Self::Output) -> B + ThreadSafe + 'a,
     Self::Input: 'a,
     Self::Output: Clone + 'a,
     B: Clone + 'a, {
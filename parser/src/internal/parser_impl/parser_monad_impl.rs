@@ -1,23 +1,23 @@
-use crate::core::{Parser, ParserFilter, ParserMonad, Parsers};
+use crate::core::{Parser, ParserFilter, ParserMonad, Parsers, ThreadSafe, UserError};
 use crate::internal::ParsersImpl;
 
-impl<'a, I, A> ParserFilter<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> ParserFilter<'a> for Parser<'a, I, A, E> {
   fn with_filter<F>(self, f: F) -> Self::P<'a, Self::Input, Self::Output>
   where
-    F: Fn(&Self::Output) -> bool + 'a,
+    F: Fn(&Self::Output) -> bool + ThreadSafe + 'a,
     Self::Input: 'a,
     Self::Output: 'a, {
     ParsersImpl::filter(self, move |e| f(e))
   }
 }
 
-impl<'a, I, A> ParserMonad<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> ParserMonad<'a> for Parser<'a, I, A, E> {
   fn flat_map<B, F>(self, f: F) -> Self::P<'a, Self::Input, B>
   where
-    F: Fn(Self::Output) -> Self::P<'a, Self::Input, B> + 'a,
+    F: Fn(Self::Output) -> Self::P<'a, Self::Input, B> + ThreadSafe + 'a,
     Self::Input: 'a,
     Self::Output: 'a,
     B: 'a, {
-    ParsersImpl::flat_map(self, move |e| f(e))
+    ParsersImpl::flat_map(self, f)
   }
 }
@@ -1,10 +1,10 @@
-use crate::core::{Parser, ParserPure, Parsers};
+use crate::core::{Parser, ParserPure, Parsers, ThreadSafe, UserError};
 use crate::internal::ParsersImpl;
 
-impl<'a, I, A> ParserPure<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> ParserPure<'a> for Parser<'a, I, A, E> {
   fn pure<F>(value: F) -> Self::P<'a, Self::Input, Self::Output>
   where
-    F: Fn() -> Self::Output + 'a,
+    F: Fn() -> Self::Output + ThreadSafe + 'a,
     Self::Input: 'a,
     Self::Output: 'a, {
     ParsersImpl::successful_lazy(value)
@@ -1,19 +1,86 @@
-use crate::core::{ParseResult, ParseState, Parser, ParserRunner};
+use crate::core::{
+  mark_end_reached, Input, Method, ParseError, ParseResult, ParseState, Parser, ParserRunner, TraceEvent, Tracer,
+  UserError,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
 
-impl<'a, I, A> ParserRunner<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> ParserRunner<'a> for Parser<'a, I, A, E> {
+  type Error = E;
   type Input = I;
   type Output = A;
   type P<'m, X, Y>
-    = Parser<'m, X, Y>
+    = Parser<'m, X, Y, E>
   where
     X: 'm;
 
-  fn parse(&self, input: &'a [Self::Input]) -> ParseResult<'a, Self::Input, Self::Output> {
+  fn parse(&self, input: impl Input<'a, Self::Input>) -> ParseResult<'a, Self::Input, Self::Output, Self::Error> {
+    self.parse_with_errors(input).0
+  }
+
+  fn parse_with_errors(
+    &self,
+    input: impl Input<'a, Self::Input>,
+  ) -> (
+    ParseResult<'a, Self::Input, Self::Output, Self::Error>,
+    Vec<ParseError<'a, Self::Input, Self::Error>>,
+  ) {
     let parse_state = ParseState::new(input, 0);
-    self.run(&parse_state)
+    let result = run_to_end(self, &parse_state);
+    (result, parse_state.take_recovered_errors())
   }
 
-  fn run(&self, param: &ParseState<'a, Self::Input>) -> ParseResult<'a, Self::Input, Self::Output> {
-    (self.method)(param)
+  fn parse_with_tracer<T>(
+    &self,
+    input: impl Input<'a, Self::Input>,
+    tracer: T,
+  ) -> (ParseResult<'a, Self::Input, Self::Output, Self::Error>, T)
+  where
+    T: Tracer + 'a, {
+    let slot = Rc::new(RefCell::new(TracerSlot(Some(tracer))));
+    let parse_state = ParseState::new(input, 0);
+    parse_state.set_tracer(Some(slot.clone()));
+    let result = run_to_end(self, &parse_state);
+    parse_state.set_tracer(None);
+    // The slot is still shared only if a parser kept it, and then the tracer is taken out through the cell.
+    let tracer = Rc::try_unwrap(slot)
+      .map(RefCell::into_inner)
+      .unwrap_or_else(|slot| slot.replace(TracerSlot(None)))
+      .0
+      .expect("the tracer is taken out of the slot only once");
+    (result, tracer)
+  }
+
+  fn run(&self, param: &ParseState<'a, Self::Input>) -> ParseResult<'a, Self::Input, Self::Output, Self::Error> {
+    let result = match &self.method {
+      Method::Bound(parse) => parse(param),
+      Method::Free(parse) => parse(param),
+    };
+    mark_end_reached(param, result)
+  }
+}
+
+/// The slot of the tracer given to `parse_with_tracer`, which forwards the events until the tracer is taken out.
+struct TracerSlot<T>(Option<T>);
+
+impl<T: Tracer> Tracer for TracerSlot<T> {
+  fn trace(&mut self, event: TraceEvent) {
+    if let Some(tracer) = &mut self.0 {
+      tracer.trace(event);
+    }
+  }
+}
+
+/// Runs the parser, and returns the farthest failure recorded in the run if it fails.
+fn run_to_end<'a, I, A, E: UserError>(
+  parser: &Parser<'a, I, A, E>,
+  parse_state: &ParseState<'a, I>,
+) -> ParseResult<'a, I, A, E> {
+  match parser.run(parse_state) {
+    ParseResult::Failure {
+      error,
+      committed_status,
+    } => ParseResult::failed(parse_state.farthest_failure(error), committed_status),
+    result => result,
   }
 }
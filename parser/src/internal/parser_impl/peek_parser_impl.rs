@@ -1,10 +1,10 @@
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parsers::PeekParsers;
 use crate::internal::ParsersImpl;
 use crate::prelude::PeekParser;
 use std::fmt::Debug;
 
-impl<'a, I, A> PeekParser<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> PeekParser<'a> for Parser<'a, I, A, E> {
   fn peek(self) -> Self::P<'a, Self::Input, Self::Output>
   where
     Self::Output: Debug + 'a, {
@@ -1,19 +1,27 @@
-use crate::core::Parser;
+use crate::core::{GrammarError, Parser, ThreadSafe, UserError};
 use crate::extension::parser::RepeatParser;
 use crate::extension::parsers::RepeatParsers;
 use crate::internal::ParsersImpl;
 use crate::utils::RangeArgument;
 use std::fmt::Debug;
 
-impl<'a, I, A> RepeatParser<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> RepeatParser<'a> for Parser<'a, I, A, E> {
   fn repeat<R>(self, range: R) -> Self::P<'a, Self::Input, Vec<Self::Output>>
   where
-    R: RangeArgument<usize> + Debug + 'a,
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
     Self::Output: Debug + 'a,
     Self: Sized, {
     ParsersImpl::repeat(self, range)
   }
 
+  fn try_repeat<R>(self, range: R) -> Result<Self::P<'a, Self::Input, Vec<Self::Output>>, GrammarError>
+  where
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
+    Self::Output: Debug + 'a,
+    Self: Sized, {
+    ParsersImpl::try_repeat(self, range)
+  }
+
   fn of_many0(self) -> Self::P<'a, Self::Input, Vec<Self::Output>>
   where
     Self::Output: Debug + 'a, {
@@ -44,7 +52,7 @@ impl<'a, I, A> RepeatParser<'a> for Parser<'a, I, A> {
     separator: Option<Self::P<'a, Self::Input, B>>,
   ) -> Self::P<'a, Self::Input, Vec<Self::Output>>
   where
-    R: RangeArgument<usize> + Debug + 'a,
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
     Self::Output: Debug + 'a,
     B: Debug + 'a, {
     ParsersImpl::repeat_sep(self, range, separator)
@@ -1,10 +1,10 @@
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parser::SkipParser;
 use crate::extension::parsers::SkipParsers;
 use crate::internal::ParsersImpl;
 use std::fmt::Debug;
 
-impl<'a, I, A> SkipParser<'a> for Parser<'a, I, A> {
+impl<'a, I, A, E: UserError> SkipParser<'a> for Parser<'a, I, A, E> {
   fn skip_left<B>(self, pb: Self::P<'a, Self::Input, B>) -> Self::P<'a, Self::Input, B>
   where
     Self::Output: Clone + Debug + 'a,
@@ -1,16 +1,16 @@
-use crate::core::Parser;
+use crate::core::{Parser, UserError};
 use crate::extension::parser::SkipParser;
 use std::fmt::Debug;
 use std::ops::Sub;
 
-impl<'a, I, A, B> Sub<Parser<'a, I, B>> for Parser<'a, I, A>
+impl<'a, I, A, B, E: UserError> Sub<Parser<'a, I, B, E>> for Parser<'a, I, A, E>
 where
   A: Clone + Debug + 'a,
   B: Clone + Debug + 'a,
 {
   type Output = Self;
 
-  fn sub(self, rhs: Parser<'a, I, B>) -> Self::Output {
+  fn sub(self, rhs: Parser<'a, I, B, E>) -> Self::Output {
     self.skip_right(rhs)
   }
 }
@@ -1,6 +1,9 @@
-use crate::core::{CommittedStatus, ParseError, ParseResult, ParseState, Parser, ParserRunner, Parsers};
+use crate::core::{
+  CommittedStatus, ParseError, ParseResult, Parser, ParserRunner, Parsers, Syntax, ThreadSafe, UserError,
+};
 use crate::internal::ParsersImpl;
 
+mod binary_parsers_impl;
 mod cache_parsers_impl;
 mod collect_parsers_impl;
 mod conversion_parsers_impl;
@@ -13,48 +16,50 @@ mod offset_parsers_impl;
 mod operator_parsers_impl;
 mod peek_parsers_impl;
 mod primitive_parsers_impl;
+mod regex_parsers_impl;
 mod repeat_parsers_impl;
 mod skip_parser_impl;
 mod taken_parsers_impl;
+mod token_parsers_impl;
 
-impl Parsers for ParsersImpl {
+impl<E: UserError> Parsers for ParsersImpl<E> {
+  type Error = E;
   type P<'p, I, A>
-    = Parser<'p, I, A>
+    = Parser<'p, I, A, E>
   where
     I: 'p,
     A: 'p;
 
-  fn parse<'a, 'b, I, A>(parser: &Self::P<'a, I, A>, input: &'b [I]) -> Result<A, ParseError<'a, I>>
+  fn parse<'a, 'b, I, A>(parser: &Self::P<'a, I, A>, input: &'b [I]) -> Result<A, ParseError<'a, I, E>>
   where
     A: 'a,
     'b: 'a, {
-    let parse_state = ParseState::new(input, 0);
-    parser.run(&parse_state).to_result()
+    parser.parse(input).to_result()
   }
 
   fn successful<'a, I, A>(value: A) -> Self::P<'a, I, A>
   where
-    A: Clone + 'a, {
-    Parser::new(move |_| ParseResult::successful(value.clone(), 0))
+    A: Clone + ThreadSafe + 'a, {
+    Parser::free(move |_| ParseResult::successful(value.clone(), 0)).with_syntax(Syntax::Empty)
   }
 
   fn successful_lazy<'a, I, A, F>(value: F) -> Self::P<'a, I, A>
   where
-    F: Fn() -> A + 'a,
+    F: Fn() -> A + ThreadSafe + 'a,
     A: 'a, {
-    Parser::new(move |_| ParseResult::successful(value(), 0))
+    Parser::free(move |_| ParseResult::successful(value(), 0)).with_syntax(Syntax::Empty)
   }
 
-  fn failed<'a, I, A>(value: ParseError<'a, I>, committed: CommittedStatus) -> Self::P<'a, I, A>
+  fn failed<'a, I, A>(value: ParseError<'a, I, E>, committed: CommittedStatus) -> Self::P<'a, I, A>
   where
-    I: Clone + 'a,
+    I: Clone + ThreadSafe + 'a,
     A: 'a, {
     Parser::new(move |_| ParseResult::failed(value.clone(), committed.clone()))
   }
 
   fn failed_lazy<'a, I, A, F>(f: F) -> Self::P<'a, I, A>
   where
-    F: Fn() -> (ParseError<'a, I>, CommittedStatus) + 'a,
+    F: Fn() -> (ParseError<'a, I, E>, CommittedStatus) + ThreadSafe + 'a,
     I: 'a,
     A: 'a, {
     Parser::new(move |_| {
@@ -63,21 +68,31 @@ impl Parsers for ParsersImpl {
     })
   }
 
+  fn custom<'a, I, A>(error: E) -> Self::P<'a, I, A>
+  where
+    I: 'a,
+    A: 'a, {
+    Parser::free(move |parse_state| {
+      let msg = format!("{:?}", error);
+      let pe = ParseError::of_custom(parse_state.next_offset(), None, msg).with_payload(error.clone());
+      ParseResult::failed_with_uncommitted(pe)
+    })
+  }
+
   fn filter<'a, I, A, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, A>
   where
-    F: Fn(&A) -> bool + 'a,
+    F: Fn(&A) -> bool + ThreadSafe + 'a,
     I: 'a,
     A: 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| match parser.run(parse_state) {
       ParseResult::Success { value, length } => {
         if f(&value) {
           ParseResult::successful(value, length)
         } else {
-          let input = parse_state.input();
           let offset = parse_state.last_offset().unwrap_or(0);
           let msg = format!("no matched to predicate: last offset: {}", offset);
-          let ps = parse_state.add_offset(length);
-          let pe = ParseError::of_mismatch(input, ps.next_offset(), length, msg);
+          let pe = ParseError::of_mismatch(parse_state.source(), parse_state.next_offset(), length, msg);
           ParseResult::failed_with_uncommitted(pe)
         }
       }
@@ -86,14 +101,15 @@ impl Parsers for ParsersImpl {
         committed_status: is_committed,
       } => ParseResult::failed(error, is_committed),
     })
+    .with_syntax_node(syntax)
   }
 
   fn flat_map<'a, I, A, B, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
   where
-    F: Fn(A) -> Self::P<'a, I, B> + 'a,
+    F: Fn(A) -> Self::P<'a, I, B> + ThreadSafe + 'a,
     A: 'a,
     B: 'a, {
-    Parser::new(move |parse_state| match parser.run(&parse_state) {
+    Parser::new(move |parse_state| match parser.run(parse_state) {
       ParseResult::Success { value: a, length: n } => {
         let ps = parse_state.add_offset(n);
         f(a).run(&ps).with_committed_fallback(n != 0).with_add_length(n)
@@ -107,9 +123,11 @@ impl Parsers for ParsersImpl {
 
   fn map<'a, I, A, B, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
   where
-    F: Fn(A) -> B + 'a,
+    F: Fn(A) -> B + ThreadSafe + 'a,
     A: 'a,
     B: Clone + 'a, {
-    Self::flat_map(parser, move |e| Self::successful(f(e)))
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| parser.run(parse_state).map(|a, length| (f(a), length)))
+      .with_syntax_node(syntax)
   }
 }
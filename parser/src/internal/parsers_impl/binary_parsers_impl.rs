@@ -0,0 +1,147 @@
+use crate::core::{ParseError, ParseResult, ParseState, Parser, ParserRunner, ThreadSafe, UserError};
+use crate::extension::parsers::{BinaryParsers, BitCursor, BitError, FromBytes};
+use crate::internal::ParsersImpl;
+use std::fmt::Debug;
+
+impl<E: UserError> ParsersImpl<E> {
+  fn number<'a, N, F>(decode: F) -> Parser<'a, u8, N, E>
+  where
+    N: FromBytes + 'a,
+    F: Fn(&[u8]) -> N + ThreadSafe + 'a, {
+    Parser::free(move |parse_state: &ParseState<u8>| {
+      if parse_state.remaining_len() >= N::SIZE {
+        ParseResult::successful(decode(parse_state.slice_with_len(N::SIZE)), N::SIZE)
+      } else {
+        ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
+      }
+    })
+  }
+
+  /// The body of a length-prefixed value needs more bytes than its length, which is a mismatch of the frame rather
+  /// than an incomplete input.
+  fn overrun<'a>(parse_state: &ParseState<'a, u8>, size: usize, error: ParseError<'a, u8, E>) -> ParseError<'a, u8, E> {
+    if error.is_in_complete() {
+      let msg = format!("expect the body within {} bytes", size);
+      ParseError::of_mismatch(parse_state.source(), parse_state.next_offset(), size, msg)
+    } else {
+      error
+    }
+  }
+
+  fn overflow<'a, T>(parse_state: &ParseState<'a, u8>, index: usize, name: &str) -> ParseResult<'a, u8, T, E> {
+    let expected = format!("{} within 64 bits", name);
+    let found = format!("{:#04x}", parse_state.slice_with_len(index + 1)[index]);
+    let ps = parse_state.add_offset(index);
+    let pe = ParseError::of_expected_mismatch(ps.source(), ps.next_offset(), 1, expected, found);
+    ParseResult::failed(pe, (index != 0).into())
+  }
+}
+
+impl<E: UserError> BinaryParsers for ParsersImpl<E> {
+  fn be<'a, N>() -> Self::P<'a, u8, N>
+  where
+    N: FromBytes + 'a, {
+    Self::number(N::from_be)
+  }
+
+  fn le<'a, N>() -> Self::P<'a, u8, N>
+  where
+    N: FromBytes + 'a, {
+    Self::number(N::from_le)
+  }
+
+  fn uleb128<'a>() -> Self::P<'a, u8, u64> {
+    Parser::free(move |parse_state: &ParseState<u8>| {
+      let mut value = 0u64;
+      let mut shift = 0;
+      for (index, &b) in parse_state.elements().enumerate() {
+        let low = (b & 0x7f) as u64;
+        if shift >= 64 || (shift == 63 && low > 1) {
+          return Self::overflow(parse_state, index, "uleb128");
+        }
+        value |= low << shift;
+        if b & 0x80 == 0 {
+          return ParseResult::successful(value, index + 1);
+        }
+        shift += 7;
+      }
+      ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
+    })
+  }
+
+  fn sleb128<'a>() -> Self::P<'a, u8, i64> {
+    Parser::free(move |parse_state: &ParseState<u8>| {
+      let mut value = 0i64;
+      let mut shift = 0;
+      for (index, &b) in parse_state.elements().enumerate() {
+        let low = (b & 0x7f) as i64;
+        if shift >= 64 || (shift == 63 && low != 0 && low != 0x7f) {
+          return Self::overflow(parse_state, index, "sleb128");
+        }
+        value |= low << shift;
+        shift += 7;
+        if b & 0x80 == 0 {
+          if shift < 64 && b & 0x40 != 0 {
+            value |= -1i64 << shift;
+          }
+          return ParseResult::successful(value, index + 1);
+        }
+      }
+      ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
+    })
+  }
+
+  fn length_prefixed<'a, N, A>(len: Self::P<'a, u8, N>, body: Self::P<'a, u8, A>) -> Self::P<'a, u8, A>
+  where
+    N: TryInto<usize> + Debug + 'a,
+    A: 'a, {
+    combine!((len,body) => move |parse_state| match len.run(parse_state) {
+      ParseResult::Success { value, length: n } => {
+        let ps = parse_state.add_offset(n);
+        let size = format!("{:?}", value);
+        let result = match value.try_into() {
+          Ok(size) if ps.remaining_len() >= size => body
+            .run(&ps.limit(size))
+            .map(|a, _| (a, size))
+            .map_err(|error| Self::overrun(&ps, size, error))
+            .with_committed_fallback(n != 0),
+          Ok(_) => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+          Err(_) => {
+            let msg = format!("expect length within usize, found: {}", size);
+            let pe = ParseError::of_conversion(parse_state.source(), parse_state.next_offset(), n, msg);
+            ParseResult::failed_with_commit(pe)
+          }
+        };
+        result.with_add_length(n)
+      }
+      ParseResult::Failure {
+        error,
+        committed_status,
+      } => ParseResult::failed(error, committed_status),
+    })
+  }
+
+  fn bits<'a, A, F>(f: F) -> Self::P<'a, u8, A>
+  where
+    F: Fn(&mut BitCursor) -> Result<A, BitError> + ThreadSafe + 'a,
+    A: 'a, {
+    Parser::free(move |parse_state: &ParseState<u8>| {
+      let mut cursor = BitCursor::new(parse_state.input());
+      let result = f(&mut cursor);
+      let consumed = cursor.consumed_bytes();
+      match result {
+        Ok(value) => ParseResult::successful(value, consumed),
+        Err(BitError::Incomplete) => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+        Err(BitError::Mismatch { expected, found, width }) => {
+          let (byte_offset, bit_offset) = cursor.position();
+          let length = (bit_offset + width).div_ceil(8);
+          let ps = parse_state.add_offset(byte_offset);
+          let expected = format!("{:#b} in {} bits at bit {}", expected, width, bit_offset);
+          let found = format!("{:#b}", found);
+          let pe = ParseError::of_expected_mismatch(ps.source(), ps.next_offset(), length, expected, found);
+          ParseResult::failed(pe, (consumed != 0).into())
+        }
+      }
+    })
+  }
+}
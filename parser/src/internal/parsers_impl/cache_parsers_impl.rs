@@ -1,30 +1,167 @@
-use crate::core::{Parser, ParserRunner};
+use crate::core::{
+  next_memo_id, MemoKey, MemoTable, ParseError, ParseResult, ParseState, Parser, ParserRunner, UserError,
+};
 use crate::extension::parsers::CacheParsers;
 use crate::internal::ParsersImpl;
 use std::cell::RefCell;
-
-use std::collections::HashMap;
 use std::fmt::Debug;
 
-impl CacheParsers for ParsersImpl {
+/// The body of a left-recursive parser.
+type Body<'s, 'a, I, A, E> = &'s dyn Fn(&ParseState<'a, I>) -> ParseResult<'a, I, A, E>;
+
+/// The body and the seeds of a left-recursive parser, lent to its recursive calls while its outermost call runs.
+struct LeftRec<'s, 'a, I, A, E> {
+  body: Body<'s, 'a, I, A, E>,
+  seeds: RefCell<MemoTable<Memo<'a, I, A, E>>>,
+}
+
+/// The result of a parser at an offset, with the errors recorded while computing it.
+#[derive(Clone)]
+struct Memo<'a, I, A, E> {
+  parse_result: ParseResult<'a, I, A, E>,
+  recovered_errors: Vec<ParseError<'a, I, E>>,
+  farthest_failure: Option<ParseError<'a, I, E>>,
+}
+
+impl<'a, I: Clone, A: Clone, E: UserError> Memo<'a, I, A, E> {
+  /// Runs the parser, and returns its result with the errors recorded while running it.
+  fn compute(
+    parse_state: &ParseState<'a, I>,
+    run: impl FnOnce(&ParseState<'a, I>) -> ParseResult<'a, I, A, E>,
+  ) -> Self {
+    let recovered_error_count = parse_state.recovered_error_count();
+    let failure_count = parse_state.recorded_failure_count();
+    let parse_result = run(parse_state);
+    let recovered_errors = parse_state.recovered_errors_since(recovered_error_count);
+    let farthest_failure = if parse_state.recorded_failure_count() != failure_count {
+      parse_state.recorded_failure()
+    } else {
+      None
+    };
+    Self {
+      parse_result,
+      recovered_errors,
+      farthest_failure,
+    }
+  }
+
+  /// Records the errors of the memo again, and returns its result.
+  fn replay(self, parse_state: &ParseState<'a, I>) -> ParseResult<'a, I, A, E> {
+    for error in self.recovered_errors {
+      parse_state.record_recovered_error(error);
+    }
+    if let Some(error) = self.farthest_failure {
+      parse_state.replay_failure(error);
+    }
+    self.parse_result
+  }
+}
+
+impl<E: UserError> CacheParsers for ParsersImpl<E> {
   fn cache<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, A>
   where
     I: Clone + 'a,
     A: Clone + Debug + 'a, {
-    let caches = RefCell::new(HashMap::new());
-    Parser::new(move |parser_state| {
-      let key = format!(
-        "{:p}:{}:{:p}",
-        parser_state,
-        parser_state.last_offset().unwrap_or(0),
-        &parser.method
-      );
-      let parse_result = caches
-        .borrow_mut()
-        .entry(key)
-        .or_insert_with(|| parser.run(parser_state))
-        .clone();
-      parse_result
+    let key = MemoKey::<Memo<'a, I, A, E>>::new();
+    let syntax = parser.syntax.clone();
+    Parser::new(move |parse_state| {
+      if let Some(entry) = parse_state.memo(&key) {
+        return entry.replay(parse_state);
+      }
+      let entry = Memo::compute(parse_state, |parse_state| parser.run(parse_state));
+      parse_state.memoize(&key, entry.clone());
+      entry.parse_result
     })
+    .with_syntax_node(syntax)
+  }
+
+  fn left_rec<'a, I, A, F>(f: F) -> Self::P<'a, I, A>
+  where
+    F: FnOnce(Self::P<'a, I, A>) -> Self::P<'a, I, A>,
+    I: Clone + 'a,
+    A: Clone + Debug + 'a, {
+    let id = next_memo_id();
+    let recursion = Parser::free(move |parse_state| Self::left_rec_at(id, None, parse_state));
+    let parser = f(recursion);
+    combine!((parser) => move |parse_state| {
+      Self::left_rec_at(id, Some(&|parse_state| parser.run(parse_state)), parse_state)
+    })
+  }
+}
+
+impl<E: UserError> ParsersImpl<E> {
+  /// Runs a left-recursive parser at the offset.
+  ///
+  /// The outermost call is given the body, and lends it with the seeds to the inner calls until it returns.
+  fn left_rec_at<'a, I, A>(
+    id: usize,
+    body: Option<Body<'_, 'a, I, A, E>>,
+    parse_state: &ParseState<'a, I>,
+  ) -> ParseResult<'a, I, A, E>
+  where
+    I: Clone,
+    A: Clone, {
+    // SAFETY: The id is given only to one left_rec, whose lent values are of this type.
+    let key = unsafe { MemoKey::<LeftRec<'_, 'a, I, A, E>>::of_id(id) };
+    parse_state.with_lent(&key, |left_rec| match (left_rec, body) {
+      (Some(left_rec), _) => Self::grow_seed(left_rec, parse_state),
+      (None, Some(body)) => {
+        let left_rec = LeftRec {
+          body,
+          seeds: RefCell::new(MemoTable::new()),
+        };
+        parse_state.lend(&key, &left_rec, || Self::grow_seed(&left_rec, parse_state))
+      }
+      (None, None) => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+    })
+  }
+
+  /// Grows the seed of a left-recursive parser at the offset, as in the algorithm of Warth et al.
+  ///
+  /// The recursive call at the same offset returns the current seed, which starts as a failure. The body is
+  /// re-evaluated while it consumes more input than the previous seed.
+  fn grow_seed<'a, I, A>(
+    left_rec: &LeftRec<'_, 'a, I, A, E>,
+    parse_state: &ParseState<'a, I>,
+  ) -> ParseResult<'a, I, A, E>
+  where
+    I: Clone,
+    A: Clone, {
+    let offsets = parse_state.memo_offsets();
+    if let Some(seed) = left_rec.seeds.borrow().get(&offsets).cloned() {
+      return seed.replay(parse_state);
+    }
+    let pe = ParseError::of_mismatch(
+      parse_state.source(),
+      parse_state.next_offset(),
+      0,
+      "left recursion without seed".to_string(),
+    );
+    let mut seed = Memo {
+      parse_result: ParseResult::failed_with_uncommitted(pe),
+      recovered_errors: vec![],
+      farthest_failure: None,
+    };
+    left_rec.seeds.borrow_mut().insert(offsets, seed.clone());
+    let recovered_error_count = parse_state.recovered_error_count();
+    let mut seed_length = None;
+    loop {
+      let memo = Memo::compute(parse_state, left_rec.body);
+      parse_state.truncate_recovered_errors(recovered_error_count);
+      match memo.parse_result {
+        ParseResult::Success { length, .. } if seed_length.is_none_or(|l| length > l) => {
+          seed_length = Some(length);
+          seed = memo;
+          left_rec.seeds.borrow_mut().insert(offsets, seed.clone());
+        }
+        _ if seed_length.is_none() => {
+          seed = memo;
+          left_rec.seeds.borrow_mut().insert(offsets, seed.clone());
+          break;
+        }
+        _ => break,
+      }
+    }
+    seed.replay(parse_state)
   }
 }
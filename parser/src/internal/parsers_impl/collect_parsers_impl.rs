@@ -1,12 +1,13 @@
-use crate::core::{ParseResult, Parser, ParserRunner};
+use crate::core::{ParseResult, Parser, ParserRunner, UserError};
 use crate::extension::parsers::CollectParsers;
 use crate::internal::ParsersImpl;
 
-impl CollectParsers for ParsersImpl {
+impl<E: UserError> CollectParsers for ParsersImpl<E> {
   #[inline]
   fn collect<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, &'a [I]>
   where
     A: 'a, {
+    let syntax = parser.syntax.clone();
     Parser::new(move |parse_state| match parser.run(parse_state) {
       ParseResult::Success { length, .. } => {
         let slice = parse_state.slice_with_len(length);
@@ -17,5 +18,23 @@ impl CollectParsers for ParsersImpl {
         committed_status: is_committed,
       } => ParseResult::failed(error, is_committed),
     })
+    .with_syntax_node(syntax)
+  }
+
+  fn collect_vec<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, Vec<I>>
+  where
+    I: Clone,
+    A: 'a, {
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| match parser.run(parse_state) {
+      ParseResult::Success { length, .. } => {
+        ParseResult::successful(parse_state.slice_with_len(length).to_vec(), length)
+      }
+      ParseResult::Failure {
+        error,
+        committed_status,
+      } => ParseResult::failed(error, committed_status),
+    })
+    .with_syntax_node(syntax)
   }
 }
@@ -1,22 +1,27 @@
-use crate::core::{ParseError, ParseResult, Parser, ParserRunner};
+use crate::core::{FromConversionError, ParseError, ParseResult, ParserRunner, ThreadSafe, UserError};
 use crate::extension::parsers::ConversionParsers;
 use crate::internal::ParsersImpl;
 use std::fmt::Debug;
 
-impl ConversionParsers for ParsersImpl {
-  fn map_res<'a, I, A, B, E, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
+impl<E: UserError> ConversionParsers for ParsersImpl<E> {
+  fn map_res<'a, I, A, B, R, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
   where
-    F: Fn(A) -> Result<B, E> + 'a,
-    E: Debug,
+    F: Fn(A) -> Result<B, R> + ThreadSafe + 'a,
+    R: Debug,
+    E: FromConversionError<R>,
     A: 'a,
     B: 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| match parser.run(parse_state) {
       ParseResult::Success { value: a, length } => match f(a) {
         Ok(value) => ParseResult::successful(value, length),
         Err(err) => {
-          let ps = parse_state.add_offset(0);
           let msg = format!("Conversion error: {:?}", err);
-          let parser_error = ParseError::of_conversion(ps.input(), ps.last_offset().unwrap_or(0), 0, msg);
+          let mut parser_error =
+            ParseError::of_conversion(parse_state.source(), parse_state.next_offset(), length, msg);
+          if let Some(payload) = E::from_conversion_error(err) {
+            parser_error = parser_error.with_payload(payload);
+          }
           ParseResult::failed_with_uncommitted(parser_error)
         }
       },
@@ -25,20 +30,22 @@ impl ConversionParsers for ParsersImpl {
         committed_status: is_committed,
       } => ParseResult::failed(error, is_committed),
     })
+    .with_syntax_node(syntax)
   }
 
-  fn map_opt<'a, I, A, B, E, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
+  fn map_opt<'a, I, A, B, R, F>(parser: Self::P<'a, I, A>, f: F) -> Self::P<'a, I, B>
   where
-    F: Fn(A) -> Option<B> + 'a,
+    F: Fn(A) -> Option<B> + ThreadSafe + 'a,
     A: Debug + 'a,
     B: Debug + 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| match parser.run(parse_state) {
       ParseResult::Success { value: a, length } => match f(a) {
         Some(value) => ParseResult::successful(value, length),
         None => {
-          let ps = parse_state.add_offset(0);
-          let msg = format!("Conversion error");
-          let parser_error = ParseError::of_conversion(ps.input(), ps.last_offset().unwrap_or(0), 0, msg);
+          let msg = "Conversion error".to_string();
+          let parser_error =
+            ParseError::of_conversion(parse_state.source(), parse_state.next_offset(), length, msg);
           ParseResult::failed_with_uncommitted(parser_error)
         }
       },
@@ -47,5 +54,6 @@ impl ConversionParsers for ParsersImpl {
         committed_status: is_committed,
       } => ParseResult::failed(error, is_committed),
     })
+    .with_syntax_node(syntax)
   }
 }
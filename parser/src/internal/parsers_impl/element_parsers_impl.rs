@@ -1,152 +1,497 @@
-use crate::core::{Element, ParseError, ParseResult, Parser};
+use crate::core::{
+  ClassKind, Element, ElementClass, GeneralCategory, ParseError, ParseResult, ParseState, Parser, Shared, Syntax,
+  TextInput, ThreadSafe, UnicodeElement, UserError,
+};
 use crate::extension::parsers::ElementParsers;
 use crate::internal::ParsersImpl;
 use crate::utils::Set;
 use std::fmt::{Debug, Display};
 
-impl ElementParsers for ParsersImpl {
+impl<E: UserError> ElementParsers for ParsersImpl<E> {
+  fn elm_any_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: Element + PartialEq + 'a, {
+    Self::elm_class_ref(ElementClass::new(ClassKind::Any, |_| true))
+  }
+
+  fn elm_any<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(ClassKind::Any, |_| true))
+  }
+
+  fn elm_ref<'a, I>(element: I) -> Self::P<'a, I, &'a I>
+  where
+    I: Element + PartialEq + 'a, {
+    let elements = Shared::new([element]);
+    let syntax = Syntax::Terminal(elements.clone());
+    Self::elm_pred_ref(move |actual| *actual == elements[0]).with_syntax(syntax)
+  }
+
+  fn elm<'a, I>(element: I) -> Self::P<'a, I, I>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    let elements = Shared::new([element]);
+    let syntax = Syntax::Terminal(elements.clone());
+    Self::elm_pred(move |actual| *actual == elements[0]).with_syntax(syntax)
+  }
+
   fn elm_pred_ref<'a, I, F>(f: F) -> Self::P<'a, I, &'a I>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + PartialEq + 'a, {
-    Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      if let Some(actual) = input.get(0) {
-        if f(actual) {
-          return ParseResult::successful(actual, 1);
-        }
-      }
-      let offset = parse_state.next_offset();
-      let msg = format!("offset: {}", offset);
-      let ps = parse_state.add_offset(1);
-      let pe = ParseError::of_mismatch(input, ps.next_offset(), 1, msg);
-      ParseResult::failed_with_uncommitted(pe)
-    })
+    Self::elm_class_ref(ElementClass::new(ClassKind::Predicate, f))
+  }
+
+  fn elm_pred<'a, I, F>(f: F) -> Self::P<'a, I, I>
+  where
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
+    I: Element + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(ClassKind::Predicate, f))
   }
 
   fn elm_space_ref<'a, I>() -> Self::P<'a, I, &'a I>
   where
     I: Element + PartialEq + 'a, {
-    Self::elm_pred_ref(Element::is_ascii_space)
+    Self::elm_class_ref(ElementClass::new(ClassKind::Named("[ #x9]"), Element::is_ascii_space))
+  }
+
+  fn elm_space<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(ClassKind::Named("[ #x9]"), Element::is_ascii_space))
   }
 
   fn elm_multi_space_ref<'a, I>() -> Self::P<'a, I, &'a I>
   where
     I: Element + PartialEq + 'a, {
-    Self::elm_pred_ref(Element::is_ascii_multi_space)
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("[ #x9#xA#xD]"),
+      Element::is_ascii_multi_space,
+    ))
+  }
+
+  fn elm_multi_space<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("[ #x9#xA#xD]"),
+      Element::is_ascii_multi_space,
+    ))
   }
 
   fn elm_alpha_ref<'a, I>() -> Self::P<'a, I, &'a I>
   where
     I: Element + PartialEq + 'a, {
-    Self::elm_pred_ref(Element::is_ascii_alpha)
+    Self::elm_class_ref(ElementClass::new(ClassKind::Named("[a-zA-Z]"), Element::is_ascii_alpha))
+  }
+
+  fn elm_alpha<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(ClassKind::Named("[a-zA-Z]"), Element::is_ascii_alpha))
   }
 
   fn elm_alpha_digit_ref<'a, I>() -> Self::P<'a, I, &'a I>
   where
     I: Element + PartialEq + 'a, {
-    Self::elm_pred_ref(Element::is_ascii_alpha_digit)
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("[a-zA-Z0-9]"),
+      Element::is_ascii_alpha_digit,
+    ))
+  }
+
+  fn elm_alpha_digit<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("[a-zA-Z0-9]"),
+      Element::is_ascii_alpha_digit,
+    ))
   }
 
   fn elm_digit_ref<'a, I>() -> Self::P<'a, I, &'a I>
   where
     I: Element + PartialEq + 'a, {
-    Self::elm_pred_ref(Element::is_ascii_digit)
+    Self::elm_class_ref(ElementClass::new(ClassKind::Named("[0-9]"), Element::is_ascii_digit))
+  }
+
+  fn elm_digit<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(ClassKind::Named("[0-9]"), Element::is_ascii_digit))
   }
 
   fn elm_hex_digit_ref<'a, I>() -> Self::P<'a, I, &'a I>
   where
     I: Element + PartialEq + 'a, {
-    Self::elm_pred_ref(Element::is_ascii_hex_digit)
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("[0-9a-fA-F]"),
+      Element::is_ascii_hex_digit,
+    ))
+  }
+
+  fn elm_hex_digit<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("[0-9a-fA-F]"),
+      Element::is_ascii_hex_digit,
+    ))
   }
 
   fn elm_oct_digit_ref<'a, I>() -> Self::P<'a, I, &'a I>
   where
     I: Element + PartialEq + 'a, {
-    Self::elm_pred_ref(Element::is_ascii_oct_digit)
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("[0-7]"),
+      Element::is_ascii_oct_digit,
+    ))
   }
 
-  fn elm_ref_of<'a, I, S>(set: &'a S) -> Self::P<'a, I, &'a I>
+  fn elm_oct_digit<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("[0-7]"),
+      Element::is_ascii_oct_digit,
+    ))
+  }
+
+  fn elm_unicode_alpha_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a, {
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("<alphabetic>"),
+      UnicodeElement::is_unicode_alpha,
+    ))
+  }
+
+  fn elm_unicode_alpha<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("<alphabetic>"),
+      UnicodeElement::is_unicode_alpha,
+    ))
+  }
+
+  fn elm_unicode_digit_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a, {
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("<numeric>"),
+      UnicodeElement::is_unicode_digit,
+    ))
+  }
+
+  fn elm_unicode_digit<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("<numeric>"),
+      UnicodeElement::is_unicode_digit,
+    ))
+  }
+
+  fn elm_unicode_alpha_digit_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a, {
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("<alphanumeric>"),
+      UnicodeElement::is_unicode_alpha_digit,
+    ))
+  }
+
+  fn elm_unicode_alpha_digit<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("<alphanumeric>"),
+      UnicodeElement::is_unicode_alpha_digit,
+    ))
+  }
+
+  fn elm_unicode_space_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a, {
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("<white space>"),
+      UnicodeElement::is_unicode_whitespace,
+    ))
+  }
+
+  fn elm_unicode_space<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("<white space>"),
+      UnicodeElement::is_unicode_whitespace,
+    ))
+  }
+
+  fn elm_unicode_category_ref<'a, I>(categories: &[GeneralCategory]) -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a, {
+    let kind = ClassKind::Category(categories.to_vec());
+    let categories = categories.to_vec();
+    Self::elm_class_ref(ElementClass::new(kind, move |e: &I| e.is_unicode_category(&categories)))
+  }
+
+  fn elm_unicode_category<'a, I>(categories: &[GeneralCategory]) -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    let kind = ClassKind::Category(categories.to_vec());
+    let categories = categories.to_vec();
+    Self::elm_class(ElementClass::new(kind, move |e: &I| e.is_unicode_category(&categories)))
+  }
+
+  fn elm_xid_start_ref<'a, I>() -> Self::P<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a, {
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("<XID_Start>"),
+      UnicodeElement::is_xid_start,
+    ))
+  }
+
+  fn elm_xid_start<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("<XID_Start>"),
+      UnicodeElement::is_xid_start,
+    ))
+  }
+
+  fn elm_xid_continue_ref<'a, I>() -> Self::P<'a, I, &'a I>
   where
-    I: PartialEq + Display + Debug + 'a,
-    S: Set<I> + ?Sized, {
-    Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      if let Some(s) = input.get(0) {
-        if set.contains(s) {
-          ParseResult::successful(s, 1)
-        } else {
-          let msg = format!("expect one of: {}, found: {}", set.to_str(), s);
-          let ps = parse_state.add_offset(1);
-          let pe = ParseError::of_mismatch(input, ps.next_offset(), 1, msg);
+    I: UnicodeElement + PartialEq + 'a, {
+    Self::elm_class_ref(ElementClass::new(
+      ClassKind::Named("<XID_Continue>"),
+      UnicodeElement::is_xid_continue,
+    ))
+  }
+
+  fn elm_xid_continue<'a, I>() -> Self::P<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    Self::elm_class(ElementClass::new(
+      ClassKind::Named("<XID_Continue>"),
+      UnicodeElement::is_xid_continue,
+    ))
+  }
+
+  fn ident_xid<'a, I>() -> Self::P<'a, I, &'a [I]>
+  where
+    I: TextInput + 'a, {
+    let head = ElementClass::new(ClassKind::Named("(<XID_Start> | \"_\")"), |e: &I| {
+      I::decode(std::slice::from_ref(e)).is_some_and(|(c, _)| is_ident_start(c))
+    });
+    let tail = ElementClass::new(ClassKind::Named("<XID_Continue>"), |e: &I| {
+      I::decode(std::slice::from_ref(e)).is_some_and(|(c, _)| unicode_ident::is_xid_continue(c))
+    });
+    let syntax = Syntax::Sequence(vec![
+      Shared::new(Syntax::Class(Shared::new(head))),
+      Shared::new(Syntax::Repeat {
+        syntax: Shared::new(Syntax::Class(Shared::new(tail))),
+        min: 0,
+        max: None,
+        separator: None,
+      }),
+    ]);
+    Parser::new(
+      move |parse_state| match I::decode(Self::char_elements(parse_state, 0)) {
+        Some((head, mut len)) if is_ident_start(head) => {
+          while let Some((c, length)) = I::decode(Self::char_elements(parse_state, len)) {
+            if !unicode_ident::is_xid_continue(c) {
+              break;
+            }
+            len += length;
+          }
+          if len == parse_state.remaining_len() {
+            parse_state.mark_end_reached();
+          }
+          ParseResult::successful(parse_state.slice_with_len(len), len)
+        }
+        _ => {
+          let offset = parse_state.next_offset();
+          let msg = format!("offset: {}", offset);
+          let pe = ParseError::of_mismatch(parse_state.source(), offset, 1, msg);
           ParseResult::failed_with_uncommitted(pe)
         }
-      } else {
-        ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
-      }
-    })
+      },
+    )
+    .with_syntax(syntax)
+  }
+
+  fn elm_ref_of<'a, I, S>(set: &'a S) -> Self::P<'a, I, &'a I>
+  where
+    I: PartialEq + Display + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
+    let class = ElementClass::new(set_kind(set.to_str(), false), move |e| set.contains(e));
+    Self::elm_expected_ref(class, move || format!("one of: {:?}", set.to_str()))
+  }
+
+  fn elm_of<'a, I, S>(set: &'a S) -> Self::P<'a, I, I>
+  where
+    I: PartialEq + Clone + Display + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
+    let class = ElementClass::new(set_kind(set.to_str(), false), move |e| set.contains(e));
+    Self::elm_expected(class, move || format!("one of: {:?}", set.to_str()))
   }
 
   fn elm_ref_in<'a, I>(start: I, end: I) -> Self::P<'a, I, &'a I>
   where
-    I: PartialEq + PartialOrd + Display + Copy + Debug + 'a, {
-    Parser::new(move |parse_state| {
-      let set = start..=end;
-      let input = parse_state.input();
-      if let Some(s) = input.get(0) {
-        if set.contains(s) {
-          ParseResult::successful(s, 1)
-        } else {
-          let msg = format!("expect elm of: {}, found: {}", set.to_str(), s);
-          let ps = parse_state.add_offset(1);
-          let pe = ParseError::of_mismatch(input, ps.next_offset(), 1, msg);
-          ParseResult::failed_with_uncommitted(pe)
-        }
-      } else {
-        ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
-      }
+    I: PartialEq + PartialOrd + Display + Copy + Debug + ThreadSafe + 'a, {
+    Self::elm_expected_ref(range_class(start, end, true), move || {
+      format!("elm of: {}", (start..=end).to_str())
+    })
+  }
+
+  fn elm_in<'a, I>(start: I, end: I) -> Self::P<'a, I, I>
+  where
+    I: PartialEq + PartialOrd + Display + Debug + Copy + Clone + ThreadSafe + 'a, {
+    Self::elm_expected(range_class(start, end, true), move || {
+      format!("elm of: {}", (start..=end).to_str())
     })
   }
 
   fn elm_ref_from_until<'a, I>(start: I, end: I) -> Self::P<'a, I, &'a I>
   where
-    I: PartialEq + PartialOrd + Display + Copy + Debug + 'a, {
-    Parser::new(move |parse_state| {
-      let set = start..end;
-      let input = parse_state.input();
-      if let Some(s) = input.get(0) {
-        if set.contains(s) {
-          ParseResult::successful(s, 1)
-        } else {
-          let msg = format!("expect elm of: {}, found: {}", set.to_str(), s);
-          let ps = parse_state.add_offset(1);
-          let pe = ParseError::of_mismatch(input, ps.next_offset(), 1, msg);
-          ParseResult::failed_with_uncommitted(pe)
-        }
-      } else {
-        ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
-      }
+    I: PartialEq + PartialOrd + Display + Copy + Debug + ThreadSafe + 'a, {
+    Self::elm_expected_ref(range_class(start, end, false), move || {
+      format!("elm of: {}", (start..end).to_str())
+    })
+  }
+
+  fn elm_from_until<'a, I>(start: I, end: I) -> Self::P<'a, I, I>
+  where
+    I: PartialEq + PartialOrd + Display + Debug + Copy + Clone + ThreadSafe + 'a, {
+    Self::elm_expected(range_class(start, end, false), move || {
+      format!("elm of: {}", (start..end).to_str())
     })
   }
 
   fn none_ref_of<'a, I, S>(set: &'a S) -> Self::P<'a, I, &'a I>
   where
-    I: PartialEq + Display + Debug + 'a,
-    S: Set<I> + ?Sized, {
-    Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      if let Some(s) = input.get(0) {
-        if !set.contains(s) {
-          ParseResult::successful(s, 1)
-        } else {
-          let msg = format!("expect none of: {}, found: {}", set.to_str(), s);
-          let ps = parse_state.add_offset(1);
-          let pe = ParseError::of_mismatch(input, ps.next_offset(), 1, msg);
-          ParseResult::failed_with_uncommitted(pe)
-        }
-      } else {
-        ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
+    I: PartialEq + Display + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
+    let class = ElementClass::new(set_kind(set.to_str(), true), move |e| !set.contains(e));
+    Self::elm_expected_ref(class, move || format!("none of: {:?}", set.to_str()))
+  }
+
+  fn none_of<'a, I, S>(set: &'a S) -> Self::P<'a, I, I>
+  where
+    I: PartialEq + Display + Clone + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
+    let class = ElementClass::new(set_kind(set.to_str(), true), move |e| !set.contains(e));
+    Self::elm_expected(class, move || format!("none of: {:?}", set.to_str()))
+  }
+}
+
+impl<E: UserError> ParsersImpl<E> {
+  fn elm_class_ref<'a, I>(class: ElementClass<'a, I>) -> Parser<'a, I, &'a I, E>
+  where
+    I: Element + PartialEq + 'a, {
+    let class = Shared::new(class);
+    let syntax = Syntax::Class(class.clone());
+    Parser::new(move |parse_state| Self::match_class(&class, parse_state)).with_syntax(syntax)
+  }
+
+  /// Returns the free parser of an element of the class, which clones the element.
+  fn elm_class<'a, I>(class: ElementClass<'a, I>) -> Parser<'a, I, I, E>
+  where
+    I: Element + Clone + PartialEq + 'a, {
+    let class = Shared::new(class);
+    let syntax = Syntax::Class(class.clone());
+    Parser::free(move |parse_state: &ParseState<I>| {
+      Self::match_class(&class, parse_state).map(|actual, length| (actual.clone(), length))
+    })
+    .with_syntax(syntax)
+  }
+
+  fn match_class<'i, I: Element>(
+    class: &ElementClass<'_, I>,
+    parse_state: &ParseState<'i, I>,
+  ) -> ParseResult<'i, I, &'i I, E> {
+    if let Some(actual) = parse_state.chunk().first() {
+      if class.contains(actual) {
+        return ParseResult::successful(actual, 1);
       }
+    }
+    let offset = parse_state.next_offset();
+    let msg = format!("offset: {}", offset);
+    let pe = ParseError::of_mismatch(parse_state.source(), offset, 1, msg);
+    ParseResult::failed_with_uncommitted(pe)
+  }
+
+  /// Returns the parser of an element of the class, which describes the expected elements on a mismatch.
+  fn elm_expected_ref<'a, I, X>(class: ElementClass<'a, I>, expected: X) -> Parser<'a, I, &'a I, E>
+  where
+    I: Display + 'a,
+    X: Fn() -> String + ThreadSafe + 'a, {
+    let class = Shared::new(class);
+    let syntax = Syntax::Class(class.clone());
+    Parser::new(move |parse_state| Self::match_expected(&class, &expected, parse_state)).with_syntax(syntax)
+  }
+
+  /// Returns the free parser of an element of the class, which clones the element.
+  fn elm_expected<'a, I, X>(class: ElementClass<'a, I>, expected: X) -> Parser<'a, I, I, E>
+  where
+    I: Display + Clone + 'a,
+    X: Fn() -> String + ThreadSafe + 'a, {
+    let class = Shared::new(class);
+    let syntax = Syntax::Class(class.clone());
+    Parser::free(move |parse_state: &ParseState<I>| {
+      Self::match_expected(&class, &expected, parse_state).map(|actual, length| (actual.clone(), length))
     })
+    .with_syntax(syntax)
+  }
+
+  fn match_expected<'i, I: Display>(
+    class: &ElementClass<'_, I>,
+    expected: impl Fn() -> String,
+    parse_state: &ParseState<'i, I>,
+  ) -> ParseResult<'i, I, &'i I, E> {
+    if let Some(s) = parse_state.chunk().first() {
+      if class.contains(s) {
+        ParseResult::successful(s, 1)
+      } else {
+        let found = s.to_string();
+        let pe =
+          ParseError::of_expected_mismatch(parse_state.source(), parse_state.next_offset(), 1, expected(), found);
+        ParseResult::failed_with_uncommitted(pe)
+      }
+    } else {
+      ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
+    }
   }
 }
+
+/// Returns the class of the elements from the start to the end.
+fn range_class<'a, I>(start: I, end: I, inclusive: bool) -> ElementClass<'a, I>
+where
+  I: PartialOrd + Copy + ThreadSafe + 'a, {
+  let kind = ClassKind::Range { start, end, inclusive };
+  if inclusive {
+    ElementClass::new(kind, move |e| start <= *e && *e <= end)
+  } else {
+    ElementClass::new(kind, move |e| start <= *e && *e < end)
+  }
+}
+
+/// Returns the kind of the class of a set, from the text of the set.
+fn set_kind<I>(text: &str, negated: bool) -> ClassKind<I> {
+  match (text, negated) {
+    ("..", false) => ClassKind::Any,
+    ("..", true) | ("<set>" | "<byte array>", _) => ClassKind::Predicate,
+    (text, false) => ClassKind::OneOf(text.to_string()),
+    (text, true) => ClassKind::NoneOf(text.to_string()),
+  }
+}
+
+/// Returns whether the character starts an identifier, which is `XID_Start` or `_`.
+fn is_ident_start(c: char) -> bool {
+  unicode_ident::is_xid_start(c) || c == '_'
+}
@@ -1,107 +1,123 @@
-use crate::core::{ParseError, ParseResult, ParseState, Parser};
-use crate::extension::parsers::ElementsParsers;
+use crate::core::{
+  GrammarError, ParseError, ParseResult, ParseState, Parser, ParserRunner, Regex, Shared, Syntax, TextInput,
+  ThreadSafe, UserError,
+};
+use crate::extension::parsers::{ElementsParsers, RegexParsers};
 use crate::internal::ParsersImpl;
-use regex::Regex;
 use std::fmt::Debug;
-use std::iter::FromIterator;
 
-impl ElementsParsers for ParsersImpl {
-  fn seq<'a, 'b, I>(seq: &'b [I]) -> Self::P<'a, I, &'a [I]>
+impl<E: UserError> ElementsParsers for ParsersImpl<E> {
+  fn seq<'a, I>(seq: &'a [I]) -> Self::P<'a, I, &'a [I]>
   where
-    I: PartialEq + Debug + 'a,
-    'b: 'a, {
-    Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      let mut index = 0;
-      loop {
-        if index == seq.len() {
-          return ParseResult::successful(seq, index);
-        }
-        if let Some(str) = input.get(index) {
-          if seq[index] != *str {
-            let msg = format!("seq {:?} expect: {:?}, found: {:?}", seq, seq[index], str);
+    I: Clone + PartialEq + Debug + ThreadSafe + 'a, {
+    let syntax = Syntax::Terminal(Shared::new(seq.to_vec()));
+    Parser::free(move |parse_state: &ParseState<I>| {
+      let mut elements = parse_state.elements();
+      for (index, expected) in seq.iter().enumerate() {
+        match elements.next() {
+          Some(actual) if actual == expected => (),
+          Some(actual) => {
+            let expected = format!("seq {:?}", seq);
+            let found = format!("{:?}", actual);
             let ps = parse_state.add_offset(index);
-            let pe = ParseError::of_mismatch(input, ps.next_offset(), index, msg);
+            let pe = ParseError::of_expected_mismatch(ps.source(), ps.next_offset(), 1, expected, found);
             return ParseResult::failed(pe, (index != 0).into());
           }
-        } else {
-          return ParseResult::failed_with_uncommitted(ParseError::of_in_complete());
+          None => return ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
         }
-        index += 1;
       }
+      ParseResult::successful(seq, seq.len())
     })
+    .with_syntax(syntax)
   }
 
   #[inline(always)]
-  fn tag<'a, 'b>(tag: &'b str) -> Self::P<'a, char, &'a str>
+  fn tag<'a, I>(tag: &'a str) -> Self::P<'a, I, &'a str>
   where
-    'b: 'a, {
-    Parser::new(move |parse_state| {
-      let input: &[char] = parse_state.input();
-      let mut index = 0;
-      for c in tag.chars() {
-        if let Some(&actual) = input.get(index) {
-          if c != actual {
-            let msg = format!("tag {:?} expect: {:?}, found: {}", tag, c, actual);
-            let ps = parse_state.add_offset(index);
-            let pe = ParseError::of_mismatch(input, ps.next_offset(), index, msg);
-            return ParseResult::failed(pe, (index != 0).into());
-          }
-        } else {
-          return ParseResult::failed_with_uncommitted(ParseError::of_in_complete());
-        }
-        index += 1;
-      }
-      ParseResult::successful(tag, index)
+    I: TextInput + 'a, {
+    Self::tag_with(tag, "tag", |expected, actual| expected == actual)
+  }
+
+  fn tag_no_case<'a, I>(tag: &'a str) -> Self::P<'a, I, &'a str>
+  where
+    I: TextInput + 'a, {
+    Self::tag_with(tag, "tag_no_case", |expected, actual| {
+      expected.eq_ignore_ascii_case(&actual)
     })
   }
 
-  fn tag_no_case<'a, 'b>(tag: &'b str) -> Self::P<'a, char, &'a str>
+  fn regex<'a, I>(pattern: &str) -> Self::P<'a, I, I::Text<'a>>
   where
-    'b: 'a, {
-    Parser::new(move |parse_state: &ParseState<char>| {
-      let input = parse_state.input();
+    I: TextInput + 'a, {
+    Self::try_regex(pattern).unwrap_or_else(|error| panic!("{}", error))
+  }
+
+  fn try_regex<'a, I>(pattern: &str) -> Result<Self::P<'a, I, I::Text<'a>>, GrammarError>
+  where
+    I: TextInput + 'a, {
+    let regex = Regex::new(pattern)?;
+    let pattern = pattern.to_string();
+    let parser = Self::regex_match(regex);
+    let syntax = parser.syntax.clone();
+    Ok(
+      Parser::new(move |parse_state| match parser.run(parse_state) {
+        ParseResult::Success { value, length } => match I::to_text(value.as_slice()) {
+          Some(text) => ParseResult::successful(text, length),
+          None => {
+            // The bytes are checked rather than assumed to be valid UTF-8.
+            let msg = format!("regex {:?} matched invalid UTF-8", pattern);
+            let pe = ParseError::of_conversion(parse_state.source(), parse_state.next_offset(), length, msg);
+            ParseResult::failed_with_uncommitted(pe)
+          }
+        },
+        ParseResult::Failure {
+          error,
+          committed_status,
+        } => ParseResult::failed(error, committed_status),
+      })
+      .with_syntax_node(syntax),
+    )
+  }
+}
+
+impl<E: UserError> ParsersImpl<E> {
+  fn tag_with<'a, I, F>(tag: &'a str, name: &'static str, eq: F) -> Parser<'a, I, &'a str, E>
+  where
+    I: TextInput + 'a,
+    F: Fn(char, char) -> bool + ThreadSafe + 'a, {
+    Parser::free(move |parse_state: &ParseState<I>| {
       let mut index = 0;
-      for c in tag.chars() {
-        if let Some(actual) = input.get(index) {
-          if !c.eq_ignore_ascii_case(actual) {
-            let msg = format!("tag_no_case {:?} expect: {:?}, found: {}", tag, c, actual);
+      for expected in tag.chars() {
+        let rest = Self::char_elements(parse_state, index);
+        match I::decode(rest) {
+          Some((actual, length)) if eq(expected, actual) => index += length,
+          _ if rest.is_empty() => return ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+          _ => {
+            let (found, length) = Self::found_char(rest);
+            let expected = format!("{} {:?}", name, tag);
             let ps = parse_state.add_offset(index);
-            let pe = ParseError::of_mismatch(input, ps.next_offset(), index, msg);
+            let pe = ParseError::of_expected_mismatch(ps.source(), ps.next_offset(), length, expected, found);
             return ParseResult::failed(pe, (index != 0).into());
           }
-        } else {
-          return ParseResult::failed_with_uncommitted(ParseError::of_in_complete());
         }
-        index += 1;
       }
       ParseResult::successful(tag, index)
     })
+    .with_syntax(Syntax::Terminal(Shared::new(I::encode(tag))))
   }
 
-  fn regex<'a>(pattern: &str) -> Self::P<'a, char, String> {
-    let pattern = if !pattern.starts_with("^") {
-      format!("^{}", pattern)
-    } else {
-      pattern.to_string()
-    };
-    let regex = Regex::new(&pattern).unwrap();
-    Parser::new(move |parse_state| {
-      let input: &[char] = parse_state.input();
-      log::debug!("regex: input = {:?}", input);
-      let str = String::from_iter(input);
-      if let Some(captures) = regex.captures(&str).as_ref() {
-        if let Some(m) = captures.get(0) {
-          let str = m.as_str();
-          ParseResult::successful(str.to_string(), str.len())
-        } else {
-          let msg = format!("regex {:?} found: {:?}", regex, str);
-          let pe = ParseError::of_mismatch(input, parse_state.next_offset(), str.len(), msg);
-          return ParseResult::failed(pe, (captures.len() != 0).into());
-        }
-      } else {
-        return ParseResult::failed_with_uncommitted(ParseError::of_in_complete());
-      }
-    })
+  /// Returns the elements of the character at the index from the next offset, which spans up to the 4 bytes of a
+  /// UTF-8 sequence, without copying the chunks of the input unless the character lies across them.
+  pub(crate) fn char_elements<'a, I>(parse_state: &ParseState<'a, I>, index: usize) -> &'a [I] {
+    let start = parse_state.next_offset() + index;
+    parse_state.source().slice(start, start + 4)
+  }
+
+  /// Describes the first character of the non-empty elements, and returns it with its length.
+  pub(crate) fn found_char<I: TextInput>(elements: &[I]) -> (String, usize) {
+    match I::decode(elements) {
+      Some((c, length)) => (c.to_string(), length),
+      None => (format!("invalid UTF-8 byte {:?}", elements[0]), 1),
+    }
   }
 }
@@ -1,13 +1,78 @@
-use crate::core::{ParserMonad, Parsers};
+use crate::core::{
+  LazyKey, ParseError, ParseResult, ParseState, Parser, ParserMonad, ParserRunner, Parsers, Shared, Syntax, ThreadSafe,
+  UserError,
+};
 use crate::extension::parsers::LazyParsers;
 use crate::internal::ParsersImpl;
 use std::fmt::Debug;
 
-impl LazyParsers for ParsersImpl {
+#[cfg(not(feature = "sync"))]
+type Once<T> = std::cell::OnceCell<T>;
+#[cfg(feature = "sync")]
+type Once<T> = std::sync::OnceLock<T>;
+
+impl<E: UserError> LazyParsers for ParsersImpl<E> {
+  #[track_caller]
   fn lazy<'a, I, A, F>(f: F) -> Self::P<'a, I, A>
   where
-    F: Fn() -> Self::P<'a, I, A> + 'a,
+    F: Fn() -> Self::P<'a, I, A> + ThreadSafe + 'a,
+    A: Debug + 'a, {
+    let f = Shared::new(f);
+    let expand = f.clone();
+    Self::unit().flat_map(move |_| f()).with_syntax(Syntax::Lazy {
+      key: LazyKey::of::<F>(),
+      expand: Shared::new(move || Syntax::of(&expand().syntax)),
+    })
+  }
+
+  #[track_caller]
+  fn recursive<'a, I, A, F>(f: F) -> Self::P<'a, I, A>
+  where
+    F: Fn(Self::P<'a, I, A>) -> Self::P<'a, I, A>,
     A: Debug + 'a, {
-    Self::unit().flat_map(move |_| f())
+    let body: Shared<Once<Parser<'a, I, A, E>>> = Shared::new(Once::new());
+    let syntax = {
+      let body = Shared::downgrade(&body);
+      Syntax::Lazy {
+        key: LazyKey::of::<F>(),
+        expand: Shared::new(move || match body.upgrade() {
+          Some(body) => Syntax::of(&body.get().and_then(|body| body.syntax.clone())),
+          None => Shared::new(Syntax::Opaque(String::new())),
+        }),
+      }
+    };
+    let recursion = {
+      let body = Shared::downgrade(&body);
+      Parser::free(move |parse_state: &ParseState<I>| {
+        match body.upgrade().and_then(|body| body.get().and_then(Parser::as_free)) {
+          Some(body) => body.run(parse_state),
+          None => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+        }
+      })
+    };
+    let mut parser = f(recursion.with_syntax(syntax.clone()));
+    if !parser.is_free() {
+      // The body is bound, so it is built again with the recursion bound to the same input.
+      let recursion = {
+        let body = Shared::downgrade(&body);
+        Parser::new(move |parse_state| match body.upgrade() {
+          Some(body) => match body.get() {
+            Some(body) => body.run(parse_state),
+            None => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+          },
+          None => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+        })
+      };
+      parser = f(recursion.with_syntax(syntax.clone()));
+    }
+    let inner = parser.clone();
+    let _ = body.set(parser);
+    let parser = inner;
+    crate::internal::combine!((parser) => move |parse_state| {
+      // The body is kept alive by the parser, and the recursion refers to it weakly.
+      let _ = &body;
+      parser.run(parse_state)
+    })
+    .with_syntax(syntax)
   }
 }
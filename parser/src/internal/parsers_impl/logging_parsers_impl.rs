@@ -1,15 +1,16 @@
-use crate::core::{ParseError, ParseResult, Parser, ParserRunner};
+use crate::core::{ParseError, ParseResult, ParserRunner, Syntax, ThreadSafe, TraceOutcome, UserError};
 use crate::extension::parsers::{LogLevel, LoggingParsers};
 use crate::internal::ParsersImpl;
 use std::fmt::{Debug, Display};
 
-impl LoggingParsers for ParsersImpl {
+impl<E: UserError> LoggingParsers for ParsersImpl<E> {
   fn log_map<'a, I, A, B, F>(parser: Self::P<'a, I, A>, name: &'a str, log_level: LogLevel, f: F) -> Self::P<'a, I, A>
   where
-    F: Fn(&ParseResult<'a, I, A>) -> B + 'a,
+    F: Fn(&ParseResult<'_, I, A, E>) -> B + ThreadSafe + 'a,
     A: Debug + 'a,
     B: Display + 'a, {
-    Parser::new(move |parse_state| {
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| {
       let ps = parser.run(parse_state);
       let s = format!("{} = {}", name, f(&ps));
       match log_level {
@@ -20,48 +21,61 @@ impl LoggingParsers for ParsersImpl {
       }
       ps
     })
+    .with_syntax_node(syntax)
   }
 
   fn name<'a, I, A>(parser: Self::P<'a, I, A>, name: &'a str) -> Self::P<'a, I, A>
   where
     I: Debug,
     A: Debug + 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
-      res @ ParseResult::Success { .. } => res,
-      ParseResult::Failure {
-        error,
-        committed_status: is_committed,
-      } => match error {
-        ParseError::Custom { .. } => ParseResult::failed(error, is_committed),
-        _ => ParseResult::failed(
-          ParseError::of_custom(
-            parse_state.last_offset().unwrap_or(0),
-            Some(Box::new(error)),
-            format!("failed to parse {}", name),
+    let syntax = Syntax::Rule {
+      name: name.to_string(),
+      syntax: Syntax::of(&parser.syntax),
+    };
+    combine!((parser) => move |parse_state| {
+      let entered = parse_state.trace_enter(name);
+      let result = parser.run(parse_state);
+      if let Some(entered) = entered {
+        let outcome = match &result {
+          ParseResult::Success { length, .. } => TraceOutcome::Success { length: *length },
+          ParseResult::Failure { committed_status, .. } => TraceOutcome::Failure {
+            committed: committed_status.is_committed(),
+          },
+        };
+        parse_state.trace_exit(name, entered, outcome);
+      }
+      match result {
+        res @ ParseResult::Success { .. } => res,
+        ParseResult::Failure {
+          error,
+          committed_status: is_committed,
+        } => match error {
+          ParseError::Custom { .. } | ParseError::Expected { .. } => ParseResult::failed(error, is_committed),
+          _ => ParseResult::failed(
+            ParseError::of_expected(parse_state.next_offset(), vec![name.to_string()], vec![error]),
+            is_committed,
           ),
-          is_committed,
-        ),
-      },
+        },
+      }
     })
+    .with_syntax(syntax)
   }
 
   fn expect<'a, I, A>(parser: Self::P<'a, I, A>, name: &'a str) -> Self::P<'a, I, A>
   where
     I: Debug,
     A: Debug + 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| match parser.run(parse_state) {
       res @ ParseResult::Success { .. } => res,
       ParseResult::Failure {
         error,
         committed_status: is_committed,
       } => ParseResult::failed(
-        ParseError::of_expect(
-          parse_state.last_offset().unwrap_or(0),
-          Box::new(error),
-          format!("Expect {}", name),
-        ),
+        ParseError::of_expect(parse_state.next_offset(), Box::new(error), name.to_string()),
         is_committed,
       ),
     })
+    .with_syntax_node(syntax)
   }
 }
@@ -1,12 +1,13 @@
-use crate::core::{ParseResult, Parser, ParserRunner};
+use crate::core::{ParseResult, ParserRunner, Spanned, UserError};
 use crate::extension::parsers::OffsetParsers;
 use crate::internal::ParsersImpl;
 
-impl OffsetParsers for ParsersImpl {
+impl<E: UserError> OffsetParsers for ParsersImpl<E> {
   fn last_offset<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, usize>
   where
     A: 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| match parser.run(parse_state) {
       ParseResult::Success { length, .. } => {
         let ps = parse_state.add_offset(length);
         ParseResult::successful(ps.last_offset().unwrap_or(0), length)
@@ -16,12 +17,14 @@ impl OffsetParsers for ParsersImpl {
         committed_status: is_committed,
       } => ParseResult::failed(error, is_committed),
     })
+    .with_syntax_node(syntax)
   }
 
   fn next_offset<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, usize>
   where
     A: 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| match parser.run(parse_state) {
       ParseResult::Success { length, .. } => {
         let ps = parse_state.add_offset(length);
         ParseResult::successful(ps.next_offset(), length)
@@ -31,5 +34,19 @@ impl OffsetParsers for ParsersImpl {
         committed_status: is_committed,
       } => ParseResult::failed(error, is_committed),
     })
+    .with_syntax_node(syntax)
+  }
+
+  fn spanned<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, Spanned<A>>
+  where
+    A: 'a, {
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| {
+      let start = parse_state.next_offset();
+      parser
+        .run(parse_state)
+        .map(|value, length| (Spanned::new(value, start..start + length), length))
+    })
+    .with_syntax_node(syntax)
   }
 }
@@ -1,83 +1,221 @@
-use crate::core::{ParseError, ParseResult, ParserRunner, Parsers};
+use crate::core::{
+  CommittedStatus, ParseError, ParseResult, ParserRunner, Parsers, Shared, Syntax, ThreadSafe, UserError,
+};
 use std::fmt::Debug;
 
 use crate::core::Parser;
 use crate::extension::parsers::OperatorParsers;
 use crate::internal::ParsersImpl;
 
-impl OperatorParsers for ParsersImpl {
+impl<E: UserError> OperatorParsers for ParsersImpl<E> {
   fn exists<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, bool>
   where
     A: Debug + 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
-      ParseResult::Success { .. } => ParseResult::successful(true, 0),
-      ParseResult::Failure { .. } => ParseResult::successful(false, 0),
+    combine!((parser) => move |parse_state| {
+      let recovered_error_count = parse_state.recovered_error_count();
+      let result = parser.run(parse_state).is_success();
+      parse_state.truncate_recovered_errors(recovered_error_count);
+      ParseResult::successful(result, 0)
     })
   }
 
   fn not<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, ()>
   where
     A: 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
-      ParseResult::Success { .. } => {
-        let ps = parse_state.add_offset(0);
-        let parser_error = ParseError::of_mismatch(
-          ps.input(),
-          ps.last_offset().unwrap_or(0),
-          0,
-          "not predicate failed".to_string(),
-        );
-        ParseResult::failed_with_uncommitted(parser_error)
+    combine!((parser) => move |parse_state| {
+      let recovered_error_count = parse_state.recovered_error_count();
+      let result = parser.run(parse_state);
+      parse_state.truncate_recovered_errors(recovered_error_count);
+      match result {
+        ParseResult::Success { .. } => {
+          let parser_error = ParseError::of_mismatch(
+            parse_state.source(),
+            parse_state.next_offset(),
+            0,
+            "not predicate failed".to_string(),
+          );
+          ParseResult::failed_with_uncommitted(parser_error)
+        }
+        ParseResult::Failure { .. } => ParseResult::successful((), 0),
       }
-      ParseResult::Failure { .. } => ParseResult::successful((), 0),
     })
   }
 
   fn or<'a, I, A>(parser1: Self::P<'a, I, A>, parser2: Self::P<'a, I, A>) -> Self::P<'a, I, A>
   where
     A: 'a, {
-    Parser::new(move |parse_state| {
-      let result = parser1.run(parse_state);
-      if let Some(committed_status) = result.committed_status() {
-        if committed_status.is_uncommitted() {
-          return parser2.run(parse_state);
+    let syntax = Syntax::choice(&[&parser1.syntax, &parser2.syntax]);
+    combine!((parser1,parser2) => move |parse_state| {
+      let recovered_error_count = parse_state.recovered_error_count();
+      match parser1.run(parse_state) {
+        ParseResult::Failure {
+          error: error1,
+          committed_status: CommittedStatus::Uncommitted,
+        } => {
+          parse_state.truncate_recovered_errors(recovered_error_count);
+          match parser2.run(parse_state) {
+            ParseResult::Failure {
+              error: error2,
+              committed_status: CommittedStatus::Uncommitted,
+            } => {
+              parse_state.truncate_recovered_errors(recovered_error_count);
+              ParseResult::failed_with_uncommitted(error1.merge(error2))
+            }
+            result => {
+              parse_state.record_failure(error1);
+              result
+            }
+          }
         }
+        result => result,
       }
-      result
     })
+    .with_syntax_node(syntax)
   }
 
   fn and_then<'a, I, A, B>(parser1: Self::P<'a, I, A>, parser2: Self::P<'a, I, B>) -> Self::P<'a, I, (A, B)>
   where
     A: Clone + 'a,
     B: Clone + 'a, {
-    Self::flat_map(parser1, move |a| Self::map(parser2.clone(), move |b| (a.clone(), b)))
+    let syntax = Syntax::sequence(&[&parser1.syntax, &parser2.syntax]);
+    combine!((parser1,parser2) => move |parse_state| match parser1.run(parse_state) {
+      ParseResult::Success { value: a, length: n1 } => parser2
+        .run(&parse_state.add_offset(n1))
+        .map(|b, n2| ((a.clone(), b), n1 + n2))
+        .with_committed_fallback(n1 != 0),
+      ParseResult::Failure {
+        error,
+        committed_status,
+      } => ParseResult::failed(error, committed_status),
+    })
+    .with_syntax_node(syntax)
   }
 
   fn attempt<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, A>
   where
     A: Debug + 'a, {
-    Parser::new(move |parse_state| parser.run(parse_state).with_uncommitted())
+    let syntax = parser.syntax.clone();
+    combine!((parser) => move |parse_state| parser.run(parse_state).with_uncommitted()).with_syntax_node(syntax)
+  }
+
+  fn recover_with<'a, I, A>(parser: Self::P<'a, I, A>, recovery: Self::P<'a, I, A>) -> Self::P<'a, I, A>
+  where
+    A: Debug + 'a, {
+    let syntax = parser.syntax.clone();
+    combine!((parser,recovery) => move |parse_state| {
+      let recovered_error_count = parse_state.recovered_error_count();
+      match parser.run(parse_state) {
+        ParseResult::Failure {
+          error,
+          committed_status,
+        } => {
+          parse_state.truncate_recovered_errors(recovered_error_count);
+          match recovery.run(parse_state) {
+            ParseResult::Success { value, length } => {
+              parse_state.record_recovered_error(error);
+              ParseResult::successful(value, length)
+            }
+            ParseResult::Failure { .. } => {
+              parse_state.truncate_recovered_errors(recovered_error_count);
+              ParseResult::failed(error, committed_status)
+            }
+          }
+        }
+        result => result,
+      }
+    })
+    .with_syntax_node(syntax)
+  }
+
+  fn skip_until<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, A>
+  where
+    A: Debug + 'a, {
+    combine!((parser) => move |parse_state| {
+      let recovered_error_count = parse_state.recovered_error_count();
+      let mut index = 0;
+      loop {
+        let ps = parse_state.add_offset(index);
+        match parser.run(&ps) {
+          ParseResult::Success { value, length } => return ParseResult::successful(value, index + length),
+          ParseResult::Failure { error, .. } => {
+            ps.truncate_recovered_errors(recovered_error_count);
+            if ps.remaining_len() == 0 {
+              return ParseResult::failed_with_uncommitted(error);
+            }
+          }
+        }
+        index += 1;
+      }
+    })
   }
 
   fn scan_right1<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>) -> Self::P<'a, I, A>
   where
-    BOP: Fn(A, A) -> A + 'a,
-    A: Clone + Debug + 'a, {
-    Self::flat_map(p.clone(), move |x| Self::rest_left1(p.clone(), op.clone(), x.clone()))
+    BOP: Fn(A, A) -> A + ThreadSafe + 'a,
+    A: Clone + Debug + ThreadSafe + 'a, {
+    let syntax = chain_syntax(&p, &op);
+    Self::flat_map(p.clone(), move |x| Self::rest_left1(p.clone(), op.clone(), x.clone())).with_syntax_node(syntax)
   }
 
   fn chain_left1<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>) -> Self::P<'a, I, A>
   where
     BOP: Fn(A, A) -> A + 'a,
     A: Clone + Debug + 'a, {
-    Self::flat_map(p.clone(), move |x| Self::rest_left1(p.clone(), op.clone(), x))
+    let syntax = chain_syntax(&p, &op);
+    combine!((p,op) => move |parse_state| {
+      let (mut x, mut length) = match p.run(parse_state) {
+        ParseResult::Success { value, length } => (value, length),
+        ParseResult::Failure {
+          error,
+          committed_status,
+        } => return ParseResult::failed(error, committed_status),
+      };
+      loop {
+        let ps = parse_state.add_offset(length);
+        let recovered_error_count = ps.recovered_error_count();
+        let parse_result = match op.run(&ps) {
+          ParseResult::Success { value: f, length: n1 } => match p.run(&ps.add_offset(n1)) {
+            ParseResult::Success { value: y, length: n2 } => ParseResult::successful((f, y), n1 + n2),
+            ParseResult::Failure {
+              error,
+              committed_status,
+            } => ParseResult::failed(error, committed_status).with_committed_fallback(n1 != 0),
+          },
+          ParseResult::Failure {
+            error,
+            committed_status,
+          } => ParseResult::failed(error, committed_status),
+        };
+        match parse_result {
+          ParseResult::Success {
+            value: (f, y),
+            length: n,
+          } => {
+            x = f(x, y);
+            length += n;
+          }
+          ParseResult::Failure {
+            error,
+            committed_status: CommittedStatus::Uncommitted,
+          } => {
+            ps.truncate_recovered_errors(recovered_error_count);
+            ps.record_failure(error);
+            return ParseResult::successful(x, length);
+          }
+          ParseResult::Failure {
+            error,
+            committed_status,
+          } => return ParseResult::failed(error, committed_status).with_committed_fallback(length != 0),
+        }
+      }
+    })
+    .with_syntax_node(syntax)
   }
 
   fn rest_right1<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>, x: A) -> Self::P<'a, I, A>
   where
-    BOP: Fn(A, A) -> A + 'a,
-    A: Clone + Debug + 'a, {
+    BOP: Fn(A, A) -> A + ThreadSafe + 'a,
+    A: Clone + Debug + ThreadSafe + 'a, {
     let default_value = x.clone();
     Self::or(
       Self::flat_map(op.clone(), move |f| {
@@ -90,8 +228,8 @@ impl OperatorParsers for ParsersImpl {
 
   fn rest_left1<'a, I, A, BOP>(p: Self::P<'a, I, A>, op: Self::P<'a, I, BOP>, x: A) -> Self::P<'a, I, A>
   where
-    BOP: Fn(A, A) -> A + 'a,
-    A: Clone + Debug + 'a, {
+    BOP: Fn(A, A) -> A + ThreadSafe + 'a,
+    A: Clone + Debug + ThreadSafe + 'a, {
     let default_value = x.clone();
     Self::or(
       Parser::new(move |parse_state| {
@@ -124,3 +262,12 @@ impl OperatorParsers for ParsersImpl {
     )
   }
 }
+
+/// Returns the syntax `p (op p)*` of the chains.
+fn chain_syntax<'a, I, A, BOP, E>(
+  p: &Parser<'a, I, A, E>,
+  op: &Parser<'a, I, BOP, E>,
+) -> Option<Shared<Syntax<'a, I>>> {
+  let rest = Syntax::repeat(&Syntax::sequence(&[&op.syntax, &p.syntax]), 0, None, None);
+  Syntax::sequence(&[&p.syntax, &rest])
+}
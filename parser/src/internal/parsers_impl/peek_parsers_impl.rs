@@ -1,13 +1,13 @@
-use crate::core::{ParseResult, Parser, ParserRunner};
+use crate::core::{ParseResult, ParserRunner, UserError};
 use crate::internal::ParsersImpl;
 use crate::prelude::PeekParsers;
 use std::fmt::Debug;
 
-impl PeekParsers for ParsersImpl {
+impl<E: UserError> PeekParsers for ParsersImpl<E> {
   fn peek<'a, I, A>(parser: Self::P<'a, I, A>) -> Self::P<'a, I, A>
   where
     A: Debug + 'a, {
-    Parser::new(move |parse_state| match parser.run(parse_state) {
+    combine!((parser) => move |parse_state| match parser.run(parse_state) {
       ParseResult::Success { value, .. } => ParseResult::successful(value, 0),
       ParseResult::Failure {
         error,
@@ -1,23 +1,24 @@
-use crate::core::{ParseError, ParseResult, Parser, Parsers};
+use crate::core::{ParseError, ParseResult, ParseState, Parser, Parsers, Syntax, UserError};
 use crate::extension::parsers::PrimitiveParsers;
 use crate::internal::ParsersImpl;
 use std::fmt::{Debug, Display};
 
-impl PrimitiveParsers for ParsersImpl {
+impl<E: UserError> PrimitiveParsers for ParsersImpl<E> {
   fn end<'a, I>() -> Self::P<'a, I, ()>
   where
     I: Debug + Display + 'a, {
-    Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      if let Some(actual) = input.get(0) {
-        let msg = format!("expect end of input, found: {}", actual);
-        let ps = parse_state.add_offset(1);
-        let pe = ParseError::of_mismatch(input, ps.next_offset(), 1, msg);
+    Parser::free(move |parse_state: &ParseState<I>| {
+      if let Some(actual) = parse_state.chunk().first() {
+        let expected = "end of input".to_string();
+        let found = actual.to_string();
+        let pe = ParseError::of_expected_mismatch(parse_state.source(), parse_state.next_offset(), 1, expected, found);
         ParseResult::failed_with_uncommitted(pe)
       } else {
+        parse_state.mark_end_reached();
         ParseResult::successful((), 0)
       }
     })
+    .with_syntax(Syntax::Empty)
   }
 
   fn empty<'a, I>() -> Self::P<'a, I, ()> {
@@ -0,0 +1,40 @@
+use crate::core::{ParseError, ParseResult, Parser, Regex, RegexInput, RegexMatch, Syntax, UserError};
+use crate::extension::parsers::RegexParsers;
+use crate::internal::ParsersImpl;
+
+impl<E: UserError> RegexParsers for ParsersImpl<E> {
+  fn regex_match<'a, I>(regex: Regex) -> Self::P<'a, I, RegexMatch<'a, I>>
+  where
+    I: RegexInput + Copy + 'a, {
+    let syntax = Syntax::Opaque(format!("regex {}", regex.as_str()));
+    Parser::new(move |parse_state| {
+      let input = parse_state.original_input();
+      let offset = parse_state.next_offset();
+      match I::captures(&regex, &input[offset..]) {
+        Some(groups) => {
+          let groups = groups
+            .into_iter()
+            .map(|group| group.map(|range| offset + range.start..offset + range.end))
+            .collect();
+          let m = RegexMatch::new(&regex, input, groups);
+          let length = m.range().len();
+          if offset + length == input.len() {
+            parse_state.mark_end_reached();
+          }
+          ParseResult::successful(m, length)
+        }
+        None if offset == input.len() => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+        None => {
+          // Up to 4 elements, so that the character of a UTF-8 byte sequence is shown.
+          let rest = &input[offset..(offset + 4).min(input.len())];
+          let actual = I::to_string_lossy(rest).chars().next().unwrap_or_default();
+          let expected = format!("regex {:?}", regex.as_str());
+          let found = actual.to_string();
+          let pe = ParseError::of_expected_mismatch(input, offset, 1, expected, found);
+          ParseResult::failed_with_uncommitted(pe)
+        }
+      }
+    })
+    .with_syntax(syntax)
+  }
+}
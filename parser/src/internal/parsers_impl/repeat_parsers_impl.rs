@@ -1,66 +1,112 @@
-use crate::core::{ParseError, ParseResult, Parser, ParserRunner};
+use crate::core::{ParseError, ParseResult, ParserRunner, Parsers, Syntax, ThreadSafe, UserError};
 use crate::extension::parsers::RepeatParsers;
 use crate::internal::ParsersImpl;
 use crate::utils::{Bound, RangeArgument};
 use std::fmt::Debug;
 
-impl RepeatParsers for ParsersImpl {
+impl<E: UserError> RepeatParsers for ParsersImpl<E> {
   fn repeat_sep<'a, I, A, B, R>(
     parser: Self::P<'a, I, A>,
     range: R,
     separator: Option<Self::P<'a, I, B>>,
   ) -> Self::P<'a, I, Vec<A>>
   where
-    R: RangeArgument<usize> + Debug + 'a,
+    R: RangeArgument<usize> + Debug + ThreadSafe + 'a,
     A: 'a,
     B: 'a, {
-    Parser::new(move |parse_state| {
+    let min = match range.start() {
+      Bound::Included(&min) => min,
+      Bound::Excluded(&min) => min + 1,
+      Bound::Unbounded => 0,
+    };
+    let max = match range.end() {
+      Bound::Included(&max) => Some(max),
+      Bound::Excluded(&max) => Some(max.saturating_sub(1)),
+      Bound::Unbounded => None,
+    };
+    let syntax = Syntax::repeat(
+      &parser.syntax,
+      min,
+      max,
+      separator.as_ref().map(|separator| &separator.syntax),
+    );
+    // A missing separator is run as the empty parser, so that the separator is a parser in both cases.
+    let has_separator = separator.is_some();
+    let separator = match separator {
+      Some(separator) => {
+        combine!((separator) => move |parse_state| separator.run(parse_state).map(|_, length| ((), length)))
+      }
+      None => Self::unit(),
+    };
+    combine!((parser, separator) => move |parse_state| {
+      let recovered_error_count = parse_state.recovered_error_count();
       let mut all_length = 0;
       let mut items = vec![];
 
-      if let ParseResult::Success { value, length } = parser.run(parse_state) {
-        let mut current_parse_state = parse_state.add_offset(length);
-        items.push(value);
-        all_length += length;
-        loop {
-          match range.end() {
-            Bound::Included(&max_count) => {
-              if items.len() >= max_count {
-                break;
-              }
-            }
-            Bound::Excluded(&max_count) => {
-              if items.len() + 1 >= max_count {
-                break;
+      // A range without room for an item does not run the parser.
+      let no_room = matches!(
+        range.end(),
+        Bound::Included(&0) | Bound::Excluded(&0) | Bound::Excluded(&1)
+      );
+      if !no_room {
+        match parser.run(parse_state) {
+          ParseResult::Success { value, length } => {
+            let mut current_parse_state = parse_state.add_offset(length);
+            items.push(value);
+            all_length += length;
+            loop {
+              match range.end() {
+                Bound::Included(&max_count) => {
+                  if items.len() >= max_count {
+                    break;
+                  }
+                }
+                Bound::Excluded(&max_count) if items.len() + 1 >= max_count => {
+                  break;
+                }
+                _ => (),
               }
-            }
-            _ => (),
-          }
 
-          if let Some(sep) = &separator {
-            if let ParseResult::Success { length, .. } = sep.run(&current_parse_state) {
-              current_parse_state = current_parse_state.add_offset(length);
-              all_length += length;
-            } else {
-              break;
+              let recovered_error_count = current_parse_state.recovered_error_count();
+              if has_separator {
+                match separator.run(&current_parse_state) {
+                  ParseResult::Success { length, .. } => {
+                    current_parse_state = current_parse_state.add_offset(length);
+                    all_length += length;
+                  }
+                  ParseResult::Failure { error, .. } => {
+                    current_parse_state.truncate_recovered_errors(recovered_error_count);
+                    current_parse_state.record_failure(error);
+                    break;
+                  }
+                }
+              }
+              match parser.run(&current_parse_state) {
+                ParseResult::Success { value, length } => {
+                  current_parse_state = current_parse_state.add_offset(length);
+                  items.push(value);
+                  all_length += length;
+                }
+                ParseResult::Failure { error, .. } => {
+                  current_parse_state.truncate_recovered_errors(recovered_error_count);
+                  current_parse_state.record_failure(error);
+                  break;
+                }
+              }
             }
           }
-          if let ParseResult::Success { value, length } = parser.run(&current_parse_state) {
-            current_parse_state = current_parse_state.add_offset(length);
-            items.push(value);
-            all_length += length;
-          } else {
-            break;
+          ParseResult::Failure { error, .. } => {
+            parse_state.truncate_recovered_errors(recovered_error_count);
+            parse_state.record_failure(error);
           }
         }
       }
 
       if let Bound::Included(&min_count) = range.start() {
         if items.len() < min_count {
-          let ps = parse_state.add_offset(all_length);
           let pe = ParseError::of_mismatch(
-            ps.input(),
-            ps.last_offset().unwrap_or(0),
+            parse_state.source(),
+            parse_state.next_offset(),
             all_length,
             format!(
               "expect repeat at least {} times, found {} times",
@@ -73,5 +119,6 @@ impl RepeatParsers for ParsersImpl {
       }
       ParseResult::successful(items, all_length)
     })
+    .with_syntax_node(syntax)
   }
 }
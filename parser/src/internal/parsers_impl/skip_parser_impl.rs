@@ -1,12 +1,11 @@
-use crate::core::{ParseError, ParseResult, Parser};
+use crate::core::{ParseError, ParseResult, Parser, UserError};
 use crate::extension::parsers::SkipParsers;
 use crate::internal::ParsersImpl;
 
-impl SkipParsers for ParsersImpl {
+impl<E: UserError> SkipParsers for ParsersImpl<E> {
   fn skip<'a, I>(n: usize) -> Self::P<'a, I, ()> {
-    Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      if input.len() >= n {
+    Parser::free(move |parse_state| {
+      if parse_state.remaining_len() >= n {
         ParseResult::successful((), n)
       } else {
         ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
@@ -1,14 +1,16 @@
-use crate::core::{Element, ParseError, ParseResult, Parser};
+use crate::core::{
+  ClassKind, Element, ElementClass, ParseError, ParseResult, ParseState, Parser, Shared, Syntax, TextInput, ThreadSafe,
+  UserError,
+};
 use std::fmt::Debug;
 
 use crate::extension::parsers::TakenParsers;
 use crate::internal::ParsersImpl;
 
-impl TakenParsers for ParsersImpl {
+impl<E: UserError> TakenParsers for ParsersImpl<E> {
   fn take<'a, I>(n: usize) -> Self::P<'a, I, &'a [I]> {
     Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      if input.len() >= n {
+      if parse_state.remaining_len() >= n {
         ParseResult::successful(parse_state.slice_with_len(n), n)
       } else {
         ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
@@ -18,77 +20,59 @@ impl TakenParsers for ParsersImpl {
 
   fn take_while0<'a, I, F>(f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a, {
+    let f = Shared::new(f);
+    let syntax = Self::predicate_syntax(f.clone(), 0, None);
     Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      let mut start: Option<usize> = None;
-      let mut len = 0;
-      let mut index = 0;
-      while let Some(c) = input.get(index) {
-        if f(c) {
-          if start.is_none() {
-            start = Some(index);
-          }
-          len += 1;
-        }
-        index += 1;
+      let (start, len) = Self::scan_while(parse_state, &*f);
+      if start.map_or(0, |s| s + len) == parse_state.remaining_len() {
+        parse_state.mark_end_reached();
       }
       match start {
-        Some(s) => ParseResult::successful(&input[s..s + len], len),
+        Some(s) => ParseResult::successful(Self::slice_from(parse_state, s, len), len),
         None => ParseResult::successful(parse_state.slice_with_len(0), 0),
       }
     })
+    .with_syntax(syntax)
   }
 
   fn take_while1<'a, I, F>(f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a, {
+    let f = Shared::new(f);
+    let syntax = Self::predicate_syntax(f.clone(), 1, None);
     Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      let mut start: Option<usize> = None;
-      let mut len = 0;
-      let mut index = 0;
-      while let Some(c) = input.get(index) {
-        if f(c) {
-          if start.is_none() {
-            start = Some(index);
+      let (start, len) = Self::scan_while(parse_state, &*f);
+      match start {
+        Some(s) => {
+          if s + len == parse_state.remaining_len() {
+            parse_state.mark_end_reached();
           }
-          len += 1;
+          ParseResult::successful(Self::slice_from(parse_state, s, len), len)
         }
-        index += 1;
-      }
-      match start {
-        Some(s) => ParseResult::successful(&input[s..s + len], len),
         None => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
       }
     })
+    .with_syntax(syntax)
   }
 
   fn take_while_n_m<'a, I, F>(n: usize, m: usize, f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a, {
+    let f = Shared::new(f);
+    let syntax = Self::predicate_syntax(f.clone(), n, Some(m));
     Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      let mut start: Option<usize> = None;
-      let mut len = 0;
-      let mut index = 0;
-      while let Some(c) = input.get(index) {
-        if f(c) {
-          if start.is_none() {
-            start = Some(index);
-          }
-          len += 1;
-        }
-        index += 1;
-      }
+      let (start, len) = Self::scan_while(parse_state, &*f);
       match start {
         Some(s) => {
-          let str = &input[s..s + len];
-          if n <= str.len() && str.len() <= m {
-            ParseResult::successful(str, len)
+          if n <= len && len <= m {
+            if s + len == parse_state.remaining_len() {
+              parse_state.mark_end_reached();
+            }
+            ParseResult::successful(Self::slice_from(parse_state, s, len), len)
           } else {
             ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
           }
@@ -96,52 +80,144 @@ impl TakenParsers for ParsersImpl {
         None => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
       }
     })
+    .with_syntax(syntax)
   }
 
   fn take_till0<'a, I, F>(f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a, {
-    Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      let mut index = 0;
-      let mut b = false;
-      while let Some(c) = input.get(index) {
-        if f(c) {
-          b = true;
-          break;
-        }
-        index += 1;
-      }
-      if b {
-        ParseResult::successful(parse_state.slice_with_len(index + 1), index + 1)
-      } else {
-        let input = parse_state.input();
-        ParseResult::successful(input, input.len())
+    let f = Shared::new(f);
+    let syntax = Self::till_syntax(f.clone(), true);
+    Parser::new(move |parse_state| match parse_state.elements().position(|c| f(c)) {
+      Some(index) => ParseResult::successful(parse_state.slice_with_len(index + 1), index + 1),
+      None => {
+        parse_state.mark_end_reached();
+        let length = parse_state.remaining_len();
+        ParseResult::successful(parse_state.slice_with_len(length), length)
       }
     })
+    .with_syntax(syntax)
   }
 
   fn take_till1<'a, I, F>(f: F) -> Self::P<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Debug + 'a, {
-    Parser::new(move |parse_state| {
-      let input = parse_state.input();
-      let mut index = 0;
-      let mut b = false;
-      while let Some(c) = input.get(index) {
-        if f(c) {
-          b = true;
-          break;
-        }
-        index += 1;
+    let f = Shared::new(f);
+    let syntax = Self::till_syntax(f.clone(), false);
+    Parser::new(move |parse_state| match parse_state.elements().position(|c| f(c)) {
+      Some(index) => ParseResult::successful(parse_state.slice_with_len(index + 1), index + 1),
+      None => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+    })
+    .with_syntax(syntax)
+  }
+
+  fn take_chars_while0<'a, I, F>(f: F) -> Self::P<'a, I, I::Text<'a>>
+  where
+    F: Fn(char) -> bool + ThreadSafe + 'a,
+    I: TextInput + 'a, {
+    Parser::new(move |parse_state| Self::take_chars_while(parse_state, &f, 0))
+  }
+
+  fn take_chars_while1<'a, I, F>(f: F) -> Self::P<'a, I, I::Text<'a>>
+  where
+    F: Fn(char) -> bool + ThreadSafe + 'a,
+    I: TextInput + 'a, {
+    Parser::new(move |parse_state| Self::take_chars_while(parse_state, &f, 1))
+  }
+}
+
+impl<E: UserError> ParsersImpl<E> {
+  /// Scans the rest of the input, and returns the index of the first element satisfying the predicate, and the
+  /// number of the elements satisfying it.
+  fn scan_while<I>(parse_state: &ParseState<I>, f: &dyn Fn(&I) -> bool) -> (Option<usize>, usize) {
+    let mut start = None;
+    let mut len = 0;
+    for (index, c) in parse_state.elements().enumerate() {
+      if f(c) {
+        start.get_or_insert(index);
+        len += 1;
       }
-      if b {
-        ParseResult::successful(parse_state.slice_with_len(index + 1), index + 1)
-      } else {
+    }
+    (start, len)
+  }
+
+  /// Returns the elements of the length from the index, relative to the next offset.
+  fn slice_from<'a, I>(parse_state: &ParseState<'a, I>, index: usize, len: usize) -> &'a [I] {
+    let start = parse_state.next_offset() + index;
+    parse_state.source().slice(start, start + len)
+  }
+
+  /// Takes the characters satisfying the predicate, at least one if `min` is 1.
+  fn take_chars_while<'a, I, F>(
+    parse_state: &ParseState<'a, I>,
+    f: &F,
+    min: usize,
+  ) -> ParseResult<'a, I, I::Text<'a>, E>
+  where
+    F: Fn(char) -> bool,
+    I: TextInput + 'a, {
+    let mut index = 0;
+    while let Some((c, length)) = I::decode(Self::char_elements(parse_state, index)) {
+      if !f(c) {
+        break;
+      }
+      index += length;
+    }
+    if index == parse_state.remaining_len() {
+      parse_state.mark_end_reached();
+    }
+    if index < min {
+      return if parse_state.remaining_len() == 0 {
         ParseResult::failed_with_uncommitted(ParseError::of_in_complete())
+      } else {
+        let (found, length) = Self::found_char(Self::char_elements(parse_state, 0));
+        let msg = format!("no matched to predicate, found: {}", found);
+        let pe = ParseError::of_mismatch(parse_state.source(), parse_state.next_offset(), length, msg);
+        ParseResult::failed_with_uncommitted(pe)
+      };
+    }
+    match I::to_text(parse_state.slice_with_len(index)) {
+      Some(text) => ParseResult::successful(text, index),
+      None => {
+        let msg = "the decoded characters are not a valid text".to_string();
+        let pe = ParseError::of_conversion(parse_state.source(), parse_state.next_offset(), index, msg);
+        ParseResult::failed_with_uncommitted(pe)
       }
-    })
+    }
+  }
+
+  /// Returns the syntax of the elements satisfying the predicate, repeated `min` to `max` times.
+  fn predicate_syntax<'a, I, F>(f: Shared<F>, min: usize, max: Option<usize>) -> Syntax<'a, I>
+  where
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
+    I: Element + 'a, {
+    let class = ElementClass::new(ClassKind::Predicate, move |e| f(e));
+    Syntax::Repeat {
+      syntax: Shared::new(Syntax::Class(Shared::new(class))),
+      min,
+      max,
+      separator: None,
+    }
+  }
+
+  /// Returns the syntax of the elements up to and including the first element satisfying the predicate, which may
+  /// be missing at the end of input if `optional`.
+  fn till_syntax<'a, I, F>(f: Shared<F>, optional: bool) -> Syntax<'a, I>
+  where
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
+    I: Element + 'a, {
+    let g = f.clone();
+    let last = Syntax::Class(Shared::new(ElementClass::new(ClassKind::Predicate, move |e| g(e))));
+    let last = if optional {
+      Syntax::Choice(vec![Shared::new(last), Shared::new(Syntax::Empty)])
+    } else {
+      last
+    };
+    Syntax::Sequence(vec![
+      Shared::new(Self::predicate_syntax(Shared::new(move |e: &I| !f(e)), 0, None)),
+      Shared::new(last),
+    ])
   }
 }
@@ -0,0 +1,24 @@
+use crate::core::{ParseError, ParseResult, ParseState, Parser, ThreadSafe, Token, UserError};
+use crate::extension::parsers::TokenParsers;
+use crate::internal::ParsersImpl;
+use std::fmt::Debug;
+
+impl<E: UserError> TokenParsers for ParsersImpl<E> {
+  fn token<'a, K>(kind: K) -> Self::P<'a, Token<K>, &'a Token<K>>
+  where
+    K: PartialEq + Debug + ThreadSafe + 'a, {
+    Parser::new(
+      move |parse_state: &ParseState<'a, Token<K>>| match parse_state.chunk().first() {
+        Some(token) if token.kind == kind => ParseResult::successful(token, 1),
+        Some(token) => {
+          let expected = format!("{:?}", kind);
+          let found = token.to_string();
+          let pe =
+            ParseError::of_expected_mismatch(parse_state.source(), parse_state.next_offset(), 1, expected, found);
+          ParseResult::failed_with_uncommitted(pe)
+        }
+        None => ParseResult::failed_with_uncommitted(ParseError::of_in_complete()),
+      },
+    )
+  }
+}
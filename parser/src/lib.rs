@@ -107,7 +107,7 @@ pub mod prelude {
   pub fn successful<'a, I, A>(value: A) -> Parser<'a, I, A>
   where
     I: 'a,
-    A: Clone + 'a, {
+    A: Clone + ThreadSafe + 'a, {
     ParsersImpl::successful(value)
   }
 
@@ -135,7 +135,7 @@ pub mod prelude {
   pub fn successful_lazy<'a, I, A, F>(f: F) -> Parser<'a, I, A>
   where
     I: 'a,
-    F: Fn() -> A + 'a,
+    F: Fn() -> A + ThreadSafe + 'a,
     A: 'a, {
     ParsersImpl::successful_lazy(f)
   }
@@ -164,7 +164,7 @@ pub mod prelude {
   /// ```
   pub fn failed<'a, I, A>(value: ParseError<'a, I>, commit: CommittedStatus) -> Parser<'a, I, A>
   where
-    I: Clone + 'a,
+    I: Clone + ThreadSafe + 'a,
     A: 'a, {
     ParsersImpl::failed(value, commit)
   }
@@ -195,7 +195,7 @@ pub mod prelude {
   /// ```
   pub fn failed_with_commit<'a, I, A>(value: ParseError<'a, I>) -> Parser<'a, I, A>
   where
-    I: Clone + 'a,
+    I: Clone + ThreadSafe + 'a,
     A: 'a, {
     ParsersImpl::failed(value, CommittedStatus::Committed)
   }
@@ -226,7 +226,7 @@ pub mod prelude {
   /// ```
   pub fn failed_with_uncommit<'a, I, A>(value: ParseError<'a, I>) -> Parser<'a, I, A>
   where
-    I: Clone + 'a,
+    I: Clone + ThreadSafe + 'a,
     A: 'a, {
     ParsersImpl::failed(value, CommittedStatus::Uncommitted)
   }
@@ -256,12 +256,47 @@ pub mod prelude {
   /// ```
   pub fn failed_lazy<'a, I, A, F>(f: F) -> Parser<'a, I, A>
   where
-    F: Fn() -> (ParseError<'a, I>, CommittedStatus) + 'a,
+    F: Fn() -> (ParseError<'a, I>, CommittedStatus) + ThreadSafe + 'a,
     I: 'a,
     A: 'a, {
     ParsersImpl::failed_lazy(f)
   }
 
+  /// Returns a [Parser] that fails with a user error without committing.<br/>
+  /// ユーザーエラーで失敗し、コミットしない[Parser]を返します。
+  ///
+  /// The error is held as the payload of a `Custom` error, and can be taken by [ParseError::payload].<br/>
+  /// エラーは`Custom`エラーのペイロードとして保持され、[ParseError::payload]で取得できます。
+  ///
+  /// - error: a user error
+  /// - error: ユーザーエラー
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// #[derive(Debug, Clone, PartialEq)]
+  /// struct OutOfRange { value: u32 }
+  ///
+  /// let parser: Parser<u8, u32, OutOfRange> = elm_digit()
+  ///   .map(|b: u8| (b - b'0') as u32)
+  ///   .with_error_type()
+  ///   .flat_map(|value| if value < 6 { successful(value).with_error_type() } else { custom(OutOfRange { value }) });
+  ///
+  /// let result: ParseResult<u8, u32, OutOfRange> = parser.parse_str("7");
+  ///
+  /// assert!(result.is_failure());
+  /// assert_eq!(result.failure().unwrap().payload(), Some(&OutOfRange { value: 7 }));
+  /// ```
+  pub fn custom<'a, I, A, E>(error: E) -> Parser<'a, I, A, E>
+  where
+    E: UserError,
+    I: 'a,
+    A: 'a, {
+    ParsersImpl::custom(error)
+  }
+
   // --- Element Parsers ---
   /// Returns a [Parser] that parses an any element.(for reference)<br/>
   /// 任意の要素を解析する[Parser]を返します。(参照版)
@@ -387,7 +422,7 @@ pub mod prelude {
   /// ```
   pub fn elm_pred_ref<'a, I, F>(f: F) -> Parser<'a, I, &'a I>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + PartialEq + 'a, {
     ParsersImpl::elm_pred_ref(f)
   }
@@ -417,7 +452,7 @@ pub mod prelude {
   /// ```
   pub fn elm_pred<'a, I, F>(f: F) -> Parser<'a, I, I>
   where
-    F: Fn(&I) -> bool + 'a,
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
     I: Element + Clone + PartialEq + 'a, {
     ParsersImpl::elm_pred(f)
   }
@@ -446,8 +481,8 @@ pub mod prelude {
   /// ```
   pub fn elm_ref_of<'a, I, S>(set: &'a S) -> Parser<'a, I, &'a I>
   where
-    I: PartialEq + Display + Debug + 'a,
-    S: Set<I> + ?Sized, {
+    I: PartialEq + Display + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
     ParsersImpl::elm_ref_of(set)
   }
 
@@ -475,8 +510,8 @@ pub mod prelude {
   /// ```
   pub fn elm_of<'a, I, S>(set: &'a S) -> Parser<'a, I, I>
   where
-    I: PartialEq + Display + Clone + Debug + 'a,
-    S: Set<I> + ?Sized, {
+    I: PartialEq + Display + Clone + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
     ParsersImpl::elm_of(set)
   }
 
@@ -507,7 +542,7 @@ pub mod prelude {
   /// ```
   pub fn elm_in_ref<'a, I>(start: I, end: I) -> Parser<'a, I, &'a I>
   where
-    I: PartialEq + PartialOrd + Display + Copy + Debug + 'a, {
+    I: PartialEq + PartialOrd + Display + Copy + Debug + ThreadSafe + 'a, {
     ParsersImpl::elm_ref_in(start, end)
   }
 
@@ -538,7 +573,7 @@ pub mod prelude {
   /// ```
   pub fn elm_in<'a, I>(start: I, end: I) -> Parser<'a, I, I>
   where
-    I: PartialEq + PartialOrd + Display + Copy + Clone + Debug + 'a, {
+    I: PartialEq + PartialOrd + Display + Copy + Clone + Debug + ThreadSafe + 'a, {
     ParsersImpl::elm_in(start, end)
   }
 
@@ -569,7 +604,7 @@ pub mod prelude {
   /// ```
   pub fn elm_from_until_ref<'a, I>(start: I, end: I) -> Parser<'a, I, &'a I>
   where
-    I: PartialEq + PartialOrd + Display + Copy + Debug + 'a, {
+    I: PartialEq + PartialOrd + Display + Copy + Debug + ThreadSafe + 'a, {
     ParsersImpl::elm_ref_from_until(start, end)
   }
 
@@ -600,7 +635,7 @@ pub mod prelude {
   /// ```
   pub fn elm_from_until<'a, I>(start: I, end: I) -> Parser<'a, I, I>
   where
-    I: PartialEq + PartialOrd + Display + Copy + Clone + Debug + 'a, {
+    I: PartialEq + PartialOrd + Display + Copy + Clone + Debug + ThreadSafe + 'a, {
     ParsersImpl::elm_from_until(start, end)
   }
 
@@ -628,8 +663,8 @@ pub mod prelude {
   /// ```
   pub fn none_ref_of<'a, I, S>(set: &'a S) -> Parser<'a, I, &'a I>
   where
-    I: PartialEq + Display + Debug + 'a,
-    S: Set<I> + ?Sized, {
+    I: PartialEq + Display + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
     ParsersImpl::none_ref_of(set)
   }
 
@@ -657,8 +692,8 @@ pub mod prelude {
   /// ```
   pub fn none_of<'a, I, S>(set: &'a S) -> Parser<'a, I, I>
   where
-    I: PartialEq + Display + Clone + Debug + 'a,
-    S: Set<I> + ?Sized, {
+    I: PartialEq + Display + Clone + Debug + ThreadSafe + 'a,
+    S: Set<I> + ThreadSafe + ?Sized, {
     ParsersImpl::none_of(set)
   }
 
@@ -934,7 +969,12 @@ pub mod prelude {
   pub fn elm_digit_1_9_ref<'a, I>() -> Parser<'a, I, &'a I>
   where
     I: Element + PartialEq + 'a, {
-    elm_digit_ref().with_filter_not(|c: &&I| c.is_ascii_digit_zero())
+    let class = ElementClass::new(ClassKind::Named("[1-9]"), |c: &I| {
+      c.is_ascii_digit() && !c.is_ascii_digit_zero()
+    });
+    elm_digit_ref()
+      .with_filter_not(|c: &&I| c.is_ascii_digit_zero())
+      .with_syntax(Syntax::Class(Shared::new(class)))
   }
 
   /// Returns a [Parser] that parses digits ('1'..='9').<br/>
@@ -959,7 +999,12 @@ pub mod prelude {
   pub fn elm_digit_1_9<'a, I>() -> Parser<'a, I, I>
   where
     I: Element + Clone + PartialEq + 'a, {
-    elm_digit_1_9_ref().map(Clone::clone)
+    let class = ElementClass::new(ClassKind::Named("[1-9]"), |c: &I| {
+      c.is_ascii_digit() && !c.is_ascii_digit_zero()
+    });
+    elm_digit()
+      .with_filter_not(|c: &I| c.is_ascii_digit_zero())
+      .with_syntax(Syntax::Class(Shared::new(class)))
   }
 
   /// Returns a [Parser] that parses hex digits ('0'..='9', 'A'..='F', 'a'..='f').(for reference)<br/>
@@ -1062,10 +1107,8 @@ pub mod prelude {
     ParsersImpl::elm_oct_digit()
   }
 
-  // --- Elements Parsers ---
-
-  /// Returns a [Parser] that parses a sequence of elements.<br/>
-  /// 要素の列を解析する[Parser]を返す。
+  /// Returns a [Parser] that parses Unicode letters (Alphabetic).(for reference)<br/>
+  /// Unicodeの文字(Alphabetic)を解析する[Parser]を返します。(参照版)
   ///
   /// # Example
   ///
@@ -1073,107 +1116,99 @@ pub mod prelude {
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abc";
-  /// let input = text.as_bytes();
+  /// let text: &str = "héllo";
+  /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<u8, &str> = seq(b"abc").collect().map_res(std::str::from_utf8);
+  /// let parser: Parser<char, String> = elm_unicode_alpha_ref().of_many1().map(String::from_iter);
   ///
-  /// let result: ParseResult<u8, &str> = parser.parse(input);
+  /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
   /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn seq<'a, 'b, I>(seq: &'b [I]) -> Parser<'a, I, &'a [I]>
+  pub fn elm_unicode_alpha_ref<'a, I>() -> Parser<'a, I, &'a I>
   where
-    I: PartialEq + Debug + 'a,
-    'b: 'a, {
-    ParsersImpl::seq(seq)
+    I: UnicodeElement + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_alpha_ref()
   }
 
-  /// Returns a [Parser] that parses a string.<br/>
-  /// 文字列を解析する[Parser]を返す。
-  ///
-  /// - tag: a string
-  /// - tag: 文字列
+  /// Returns a [Parser] that parses Unicode letters (Alphabetic).<br/>
+  /// Unicodeの文字(Alphabetic)を解析する[Parser]を返します。
   ///
   /// # Example
   ///
   /// ```rust
+  /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
+  /// let text: &str = "héllo";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, &str> = tag("abc");
+  /// let parser: Parser<char, String> = elm_unicode_alpha().of_many1().map(String::from_iter);
   ///
-  /// let result: ParseResult<char, &str> = parser.parse(&input);
+  /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn tag<'a, 'b>(tag: &'b str) -> Parser<'a, char, &'a str>
+  pub fn elm_unicode_alpha<'a, I>() -> Parser<'a, I, I>
   where
-    'b: 'a, {
-    ParsersImpl::tag(tag)
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_alpha()
   }
 
-  /// Returns a [Parser] that parses a string. However, it is not case-sensitive.<br/>
-  /// 文字列を解析する[Parser]を返す。ただし大文字小文字を区別しない。
-  ///
-  /// - tag: a string
-  /// - tag: 文字列
+  /// Returns a [Parser] that parses Unicode numbers (Numeric).(for reference)<br/>
+  /// Unicodeの数字(Numeric)を解析する[Parser]を返します。(参照版)
   ///
   /// # Example
   ///
   /// ```rust
+  /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
+  /// let text: &str = "１２３";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, &str> = tag("abc");
+  /// let parser: Parser<char, String> = elm_unicode_digit_ref().of_many1().map(String::from_iter);
   ///
-  /// let result: ParseResult<char, &str> = parser.parse(&input);
+  /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn tag_no_case<'a, 'b>(tag: &'b str) -> Parser<'a, char, &'a str>
+  pub fn elm_unicode_digit_ref<'a, I>() -> Parser<'a, I, &'a I>
   where
-    'b: 'a, {
-    ParsersImpl::tag_no_case(tag)
+    I: UnicodeElement + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_digit_ref()
   }
 
-  /// Returns a [Parser] that parses a string that match a regular expression.<br/>
-  /// 正規表現に合致する文字列を解析する[Parser]を返す。
-  ///
-  /// - pattern: a regular expression
-  /// - pattern: 正規表現
+  /// Returns a [Parser] that parses Unicode numbers (Numeric).<br/>
+  /// Unicodeの数字(Numeric)を解析する[Parser]を返します。
   ///
   /// # Example
   ///
   /// ```rust
+  /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
+  /// let text: &str = "１２３";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = regex("[abc]+");
+  /// let parser: Parser<char, String> = elm_unicode_digit().of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn regex<'a>(pattern: &str) -> Parser<'a, char, String> {
-    ParsersImpl::regex(pattern)
+  pub fn elm_unicode_digit<'a, I>() -> Parser<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_digit()
   }
 
-  /// Returns a [Parser] that returns an element of the specified length.<br/>
-  /// 指定された長さの要素を返す[Parser]を返す。
-  ///
-  /// - n: Length of the reading element
-  /// - n: 読む要素の長さ
+  /// Returns a [Parser] that parses Unicode letters and numbers.(for reference)<br/>
+  /// Unicodeの文字と数字を解析する[Parser]を返します。(参照版)
   ///
   /// # Example
   ///
@@ -1181,25 +1216,24 @@ pub mod prelude {
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
+  /// let text: &str = "日本語123";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take(3).map(String::from_iter);
+  /// let parser: Parser<char, String> = elm_unicode_alpha_digit_ref().of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn take<'a, I>(n: usize) -> Parser<'a, I, &'a [I]> {
-    ParsersImpl::take(n)
+  pub fn elm_unicode_alpha_digit_ref<'a, I>() -> Parser<'a, I, &'a I>
+  where
+    I: UnicodeElement + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_alpha_digit_ref()
   }
 
-  /// クロージャの結果が真である間は要素を返す[Parser]を返す。<br/>
-  /// Returns a [Parser] that returns elements, while the result of the closure is true.
-  ///
-  /// 解析結果の長さは必須ではありません。<br/>
-  /// The length of the analysis result is not required.
+  /// Returns a [Parser] that parses Unicode letters and numbers.<br/>
+  /// Unicodeの文字と数字を解析する[Parser]を返します。
   ///
   /// # Example
   ///
@@ -1207,49 +1241,49 @@ pub mod prelude {
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
+  /// let text: &str = "日本語123";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_while0(|e| match *e {
-  ///  'a'..='c' => true,
-  ///   _ => false
-  /// }).map(String::from_iter);
+  /// let parser: Parser<char, String> = elm_unicode_alpha_digit().of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
+  pub fn elm_unicode_alpha_digit<'a, I>() -> Parser<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_alpha_digit()
+  }
+
+  /// Returns a [Parser] that parses Unicode whitespace (White_Space).(for reference)<br/>
+  /// Unicodeの空白(White_Space)を解析する[Parser]を返します。(参照版)
+  ///
+  /// # Example
   ///
   /// ```rust
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "def";
+  /// let text: &str = "\u{3000} \t";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_while0(|e| match *e {
-  ///  'a'..='c' => true,
-  ///   _ => false
-  /// }).map(String::from_iter);
+  /// let parser: Parser<char, String> = elm_unicode_space_ref().of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn take_while0<'a, I, F>(f: F) -> Parser<'a, I, &'a [I]>
+  pub fn elm_unicode_space_ref<'a, I>() -> Parser<'a, I, &'a I>
   where
-    F: Fn(&I) -> bool + 'a,
-    I: Element + Debug + 'a, {
-    ParsersImpl::take_while0(f)
+    I: UnicodeElement + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_space_ref()
   }
 
-  /// クロージャの結果が真である間は要素を返す[Parser]を返す。<br/>
-  /// Returns a [Parser] that returns elements, while the result of the closure is true.
-  ///
-  /// 解析結果の長さは1要素以上必要です。<br/>
-  /// The length of the analysis result must be at least one element.
+  /// Returns a [Parser] that parses Unicode whitespace (White_Space).<br/>
+  /// Unicodeの空白(White_Space)を解析する[Parser]を返します。
   ///
   /// # Example
   ///
@@ -1257,49 +1291,56 @@ pub mod prelude {
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
+  /// let text: &str = "\u{3000} \t";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_while1(|e| match *e {
-  ///  'a'..='c' => true,
-  ///   _ => false
-  /// }).map(String::from_iter);
+  /// let parser: Parser<char, String> = elm_unicode_space().of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
+  pub fn elm_unicode_space<'a, I>() -> Parser<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_space()
+  }
+
+  /// Returns a [Parser] that parses an element in any of the Unicode general categories.(for reference)<br/>
+  /// いずれかのUnicode一般カテゴリに含まれる要素を解析する[Parser]を返します。(参照版)
+  ///
+  /// - categories: the general categories
+  /// - categories: 一般カテゴリ
+  ///
+  /// # Example
   ///
   /// ```rust
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "def";
+  /// let text: &str = "«»";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_while1(|e| match *e {
-  ///  'a'..='c' => true,
-  ///   _ => false
-  /// }).map(String::from_iter);
+  /// let quotes = [GeneralCategory::InitialPunctuation, GeneralCategory::FinalPunctuation];
+  /// let parser: Parser<char, String> = elm_unicode_category_ref(&quotes).of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
-  /// assert!(result.is_failure());
-  /// assert_eq!(result.failure().unwrap(), ParseError::of_in_complete());
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn take_while1<'a, I, F>(f: F) -> Parser<'a, I, &'a [I]>
+  pub fn elm_unicode_category_ref<'a, I>(categories: &[GeneralCategory]) -> Parser<'a, I, &'a I>
   where
-    F: Fn(&I) -> bool + 'a,
-    I: Element + Debug + 'a, {
-    ParsersImpl::take_while1(f)
+    I: UnicodeElement + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_category_ref(categories)
   }
 
-  /// クロージャの結果が真である間は要素を返す[Parser]を返す。<br/>
-  /// Returns a [Parser] that returns elements, while the result of the closure is true.
+  /// Returns a [Parser] that parses an element in any of the Unicode general categories.<br/>
+  /// いずれかのUnicode一般カテゴリに含まれる要素を解析する[Parser]を返します。
   ///
-  /// 解析結果の長さはn要素以上m要素以下である必要があります。<br/>
-  /// The length of the analysis result should be between n and m elements.
+  /// - categories: the general categories
+  /// - categories: 一般カテゴリ
   ///
   /// # Example
   ///
@@ -1307,49 +1348,50 @@ pub mod prelude {
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
+  /// let text: &str = "€$";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_while_n_m(1, 3, |e| match *e {
-  ///  'a'..='c' => true,
-  ///   _ => false
-  /// }).map(String::from_iter);
+  /// let parser: Parser<char, String> =
+  ///   elm_unicode_category(&[GeneralCategory::CurrencySymbol]).of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
+  pub fn elm_unicode_category<'a, I>(categories: &[GeneralCategory]) -> Parser<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    ParsersImpl::elm_unicode_category(categories)
+  }
+
+  /// Returns a [Parser] that parses characters that can start an identifier (XID_Start).(for reference)<br/>
+  /// 識別子を開始できる文字(XID_Start)を解析する[Parser]を返します。(参照版)
+  ///
+  /// # Example
   ///
   /// ```rust
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "def";
+  /// let text: &str = "変数";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_while_n_m(1, 3, |e| match *e {
-  ///  'a'..='c' => true,
-  ///   _ => false
-  /// }).map(String::from_iter);
+  /// let parser: Parser<char, String> = elm_xid_start_ref().of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
-  /// assert!(result.is_failure());
-  /// assert_eq!(result.failure().unwrap(), ParseError::of_in_complete());
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn take_while_n_m<'a, I, F>(n: usize, m: usize, f: F) -> Parser<'a, I, &'a [I]>
+  pub fn elm_xid_start_ref<'a, I>() -> Parser<'a, I, &'a I>
   where
-    F: Fn(&I) -> bool + 'a,
-    I: Element + Debug + 'a, {
-    ParsersImpl::take_while_n_m(n, m, f)
+    I: UnicodeElement + PartialEq + 'a, {
+    ParsersImpl::elm_xid_start_ref()
   }
 
-  /// Returns a [Parser] that returns a sequence up to either the end element or the element that matches the condition.<br/>
-  /// 条件に一致する要素もしくは最後の要素までの連続を返す[Parser]を返す。
-  ///
-  /// 解析結果の長さは1要素以上必要です。<br/>
-  /// The length of the analysis result must be at least one element.
+  /// Returns a [Parser] that parses characters that can start an identifier (XID_Start).<br/>
+  /// 識別子を開始できる文字(XID_Start)を解析する[Parser]を返します。
   ///
   /// # Example
   ///
@@ -1357,43 +1399,49 @@ pub mod prelude {
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
+  /// let text: &str = "変数";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_till0(|e| matches!(*e, 'c')).map(String::from_iter);
+  /// let parser: Parser<char, String> = elm_xid_start().of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
+  pub fn elm_xid_start<'a, I>() -> Parser<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    ParsersImpl::elm_xid_start()
+  }
+
+  /// Returns a [Parser] that parses characters that can continue an identifier (XID_Continue).(for reference)<br/>
+  /// 識別子を継続できる文字(XID_Continue)を解析する[Parser]を返します。(参照版)
+  ///
+  /// # Example
   ///
   /// ```rust
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "def";
+  /// let text: &str = "x_1";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_till0(|e| matches!(*e, 'c')).map(String::from_iter);
+  /// let parser: Parser<char, String> = elm_xid_continue_ref().of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "def");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn take_till0<'a, I, F>(f: F) -> Parser<'a, I, &'a [I]>
+  pub fn elm_xid_continue_ref<'a, I>() -> Parser<'a, I, &'a I>
   where
-    F: Fn(&I) -> bool + 'a,
-    I: Element + Debug + 'a, {
-    ParsersImpl::take_till0(f)
+    I: UnicodeElement + PartialEq + 'a, {
+    ParsersImpl::elm_xid_continue_ref()
   }
 
-  /// Returns a [Parser] that returns a sequence up to either the end element or the element that matches the condition.<br/>
-  /// 条件に一致する要素もしくは最後の要素までの連続を返す[Parser]を返す。
-  ///
-  /// 解析結果の長さは1要素以上必要です。<br/>
-  /// The length of the analysis result must be at least one element.
+  /// Returns a [Parser] that parses characters that can continue an identifier (XID_Continue).<br/>
+  /// 識別子を継続できる文字(XID_Continue)を解析する[Parser]を返します。
   ///
   /// # Example
   ///
@@ -1401,45 +1449,58 @@ pub mod prelude {
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
+  /// let text: &str = "x_1";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_till1(|e| matches!(*e, 'c')).map(String::from_iter);
+  /// let parser: Parser<char, String> = elm_xid_continue().of_many1().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
+  pub fn elm_xid_continue<'a, I>() -> Parser<'a, I, I>
+  where
+    I: UnicodeElement + Clone + PartialEq + 'a, {
+    ParsersImpl::elm_xid_continue()
+  }
+
+  /// Returns a [Parser] that parses an identifier of Unicode Standard Annex #31 (XID_Start or `_`, then XID_Continue*).<br/>
+  /// Unicode標準附属書#31の識別子(XID_Startまたは`_`、続いてXID_Continue*)を解析する[Parser]を返します。
+  ///
+  /// For `u8` inputs, the characters are decoded from UTF-8.<br/>
+  /// `u8`の入力では、文字はUTF-8から復号されます。
+  ///
+  /// # Example
   ///
   /// ```rust
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "def";
+  /// let text: &str = "_größe1 = 0";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, String> = take_till1(|e| matches!(*e, 'c')).map(String::from_iter);
+  /// let parser: Parser<char, String> = ident_xid().map(String::from_iter);
   ///
   /// let result: ParseResult<char, String> = parser.parse(&input);
   ///
-  /// assert!(result.is_failure());
-  /// assert_eq!(result.failure().unwrap(), ParseError::of_in_complete());
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "_größe1");
   /// ```
-  pub fn take_till1<'a, I, F>(f: F) -> Parser<'a, I, &'a [I]>
+  pub fn ident_xid<'a, I>() -> Parser<'a, I, &'a [I]>
   where
-    F: Fn(&I) -> bool + 'a,
-    I: Element + Debug + 'a, {
-    ParsersImpl::take_till1(f)
+    I: TextInput + 'a, {
+    ParsersImpl::ident_xid()
   }
 
-  // --- Offset Control Parsers ---
+  // --- Elements Parsers ---
 
-  /// Returns a [Parser] that skips the specified number of elements.<br/>
-  /// 指定された数の要素をスキップする[Parser]を返す。
+  /// Returns a [Parser] that parses a sequence of elements.<br/>
+  /// 要素の列を解析する[Parser]を返す。
   ///
-  /// - size: a size of elements
-  /// - size: スキップする要素数
+  /// The parser returns the given sequence, which is equal to the matched part of the input, so it is free of the
+  /// lifetime of the input.<br/>
+  /// パーサーは入力の一致した部分と等しい、与えた要素の列を返すため、入力のライフタイムから自由である。
   ///
   /// # Example
   ///
@@ -1447,58 +1508,970 @@ pub mod prelude {
   /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "abcdef";
-  /// let input = text.chars().collect::<Vec<_>>();
+  /// let text: &str = "abc";
+  /// let input = text.as_bytes();
   ///
-  /// let parser: Parser<char, &str> = (skip(3) * tag("def"));
+  /// let parser: Parser<u8, &str> = seq(b"abc").collect().map_res(std::str::from_utf8);
   ///
-  /// let result: ParseResult<char, &str> = parser.parse(&input);
+  /// let result: ParseResult<u8, &str> = parser.parse(input);
   ///
   /// assert!(result.is_success());
-  /// assert_eq!(result.success().unwrap(), "def");
+  /// assert_eq!(result.success().unwrap(), text);
   /// ```
-  pub fn skip<'a, I>(n: usize) -> Parser<'a, I, ()> {
-    ParsersImpl::skip(n)
+  pub fn seq<'a, I>(seq: &'a [I]) -> Parser<'a, I, &'a [I]>
+  where
+    I: Clone + PartialEq + Debug + ThreadSafe + 'a, {
+    ParsersImpl::seq(seq)
   }
 
-  // --- Enhanced Parsers ---
-
-  /// Return a [Parser] that skips the previous and following [Parser]s.<br/>
-  /// 前後のパーサーをスキップするパーサーを返す。
+  /// Returns a [Parser] that parses a string.<br/>
+  /// 文字列を解析する[Parser]を返す。
   ///
-  /// - lp: 左側のパーサー
-  /// - parser: 中央のパーサー
-  /// - rp: 右側のパーサー
+  /// The input is either `char` or `u8`, the UTF-8 bytes of a `str`.<br/>
+  /// 入力は`char`または`str`のUTF-8バイト列である`u8`である。
+  ///
+  /// The parser returns the given tag, so it is free of the lifetime of the input.<br/>
+  /// パーサーは与えたタグを返すため、入力のライフタイムから自由である。
+  ///
+  /// - tag: a string
+  /// - tag: 文字列
   ///
   /// # Example
   ///
   /// ```rust
-  /// use std::iter::FromIterator;
   /// use oni_comb_parser_rs::prelude::*;
   ///
-  /// let text: &str = "(abc)";
+  /// let text: &str = "abcdef";
   /// let input = text.chars().collect::<Vec<_>>();
   ///
-  /// let parser: Parser<char, &str> = surround(elm('('), tag("abc"), elm(')'));
+  /// let parser: Parser<char, &str> = tag("abc");
   ///
   /// let result: ParseResult<char, &str> = parser.parse(&input);
   ///
   /// assert!(result.is_success());
   /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(tag("abc").parse_str(text).success(), Some("abc"));
   /// ```
-  pub fn surround<'a, I, A, B, C>(
-    lp: Parser<'a, I, A>,
-    parser: Parser<'a, I, B>,
-    rp: Parser<'a, I, C>,
-  ) -> Parser<'a, I, B>
+  pub fn tag<'a, I>(tag: &'a str) -> Parser<'a, I, &'a str>
   where
-    A: Clone + Debug + 'a,
-    B: Clone + Debug + 'a,
-    C: Clone + Debug + 'a, {
-    ParsersImpl::surround(lp, parser, rp)
+    I: TextInput + 'a, {
+    ParsersImpl::tag(tag)
   }
 
-  /// Returns a [Parser] that lazily evaluates the specified [Parser].<br/>
+  /// Returns a [Parser] that parses a string. However, it is not case-sensitive.<br/>
+  /// 文字列を解析する[Parser]を返す。ただし大文字小文字を区別しない。
+  ///
+  /// The input is either `char` or `u8`, the UTF-8 bytes of a `str`. The ASCII case is ignored, and the given string
+  /// is returned.<br/>
+  /// 入力は`char`または`str`のUTF-8バイト列である`u8`である。ASCIIの大文字小文字を区別せず、与えた文字列を返す。
+  ///
+  /// - tag: a string
+  /// - tag: 文字列
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "AbCdef";
+  ///
+  /// let parser: Parser<u8, &str> = tag_no_case("abc");
+  ///
+  /// let result: ParseResult<u8, &str> = parser.parse_str(text);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// ```
+  pub fn tag_no_case<'a, I>(tag: &'a str) -> Parser<'a, I, &'a str>
+  where
+    I: TextInput + 'a, {
+    ParsersImpl::tag_no_case(tag)
+  }
+
+  /// Returns a [Parser] that parses a string that match a regular expression.<br/>
+  /// 正規表現に合致する文字列を解析する[Parser]を返す。
+  ///
+  /// The input is either `char`, returning a `String`, or `u8`, the UTF-8 bytes of a `str`, returning a string slice
+  /// of the input. A match that is not valid UTF-8 fails with a `Conversion` error.<br/>
+  /// 入力は`String`を返す`char`、または入力の文字列スライスを返す`str`のUTF-8バイト列である`u8`である。UTF-8として
+  /// 不正な一致は`Conversion`エラーで失敗する。
+  ///
+  /// - pattern: a regular expression
+  /// - pattern: 正規表現
+  ///
+  /// # Panics
+  ///
+  /// Panics if the pattern is invalid. Use [try_regex] for a pattern given at runtime.<br/>
+  /// パターンが不正な場合はパニックする。実行時に与えられるパターンには[try_regex]を使う。
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "abcdef";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = regex("[abc]+");
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// assert_eq!(regex(r"\p{Hiragana}+").parse_str("あいうabc").success(), Some("あいう"));
+  /// ```
+  pub fn regex<'a, I>(pattern: &str) -> Parser<'a, I, I::Text<'a>>
+  where
+    I: TextInput + 'a, {
+    ParsersImpl::regex(pattern)
+  }
+
+  /// Returns a [Parser] that parses a string that match a regular expression, or an error if the pattern is
+  /// invalid.<br/>
+  /// 正規表現に合致する文字列を解析する[Parser]を返す。パターンが不正な場合はエラーを返す。
+  ///
+  /// - pattern: a regular expression
+  /// - pattern: 正規表現
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// fn field<'a>(pattern: &str) -> Result<Parser<'a, u8, &'a str>, GrammarError> {
+  ///   Ok(try_regex(pattern)? - elm(b' ').of_many0())
+  /// }
+  ///
+  /// let parser = field(r"\d+").unwrap().of_many1();
+  ///
+  /// assert_eq!(parser.parse_str("12 34").success().unwrap(), vec!["12", "34"]);
+  /// assert!(field(r"\d+(").is_err());
+  /// assert!(matches!(try_regex::<char>("[abc"), Err(GrammarError::Regex(_))));
+  /// ```
+  pub fn try_regex<'a, I>(pattern: &str) -> Result<Parser<'a, I, I::Text<'a>>, GrammarError>
+  where
+    I: TextInput + 'a, {
+    ParsersImpl::try_regex(pattern)
+  }
+
+  /// Returns a [Parser] that parses a match of a compiled regular expression, with its capture groups.<br/>
+  /// コンパイル済みの正規表現の一致を、キャプチャグループとともに解析する[Parser]を返す。
+  ///
+  /// The match is anchored at the current offset, and the rest of the input is not copied. The input is either `u8`
+  /// (UTF-8 bytes) or `char`.<br/>
+  /// 一致は現在のオフセットに固定され、入力の残りはコピーされない。入力は`u8`(UTF-8バイト列)または`char`である。
+  ///
+  /// - regex: a compiled regular expression
+  /// - regex: コンパイル済みの正規表現
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "key=42;";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let regex = Regex::new(r"(?<key>\w+)=(\d+)").unwrap();
+  /// let parser = regex_match(regex).map(|m| (m.get_string(1).unwrap(), m.get_string(2).unwrap())) - elm(';');
+  ///
+  /// let result = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), ("key".to_string(), "42".to_string()));
+  /// assert!(Regex::new("(").is_err());
+  /// ```
+  pub fn regex_match<'a, I>(regex: Regex) -> Parser<'a, I, RegexMatch<'a, I>>
+  where
+    I: RegexInput + Copy + 'a, {
+    ParsersImpl::regex_match(regex)
+  }
+
+  /// Returns a [Parser] that returns an element of the specified length.<br/>
+  /// 指定された長さの要素を返す[Parser]を返す。
+  ///
+  /// - n: Length of the reading element
+  /// - n: 読む要素の長さ
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "abcdef";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take(3).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// ```
+  pub fn take<'a, I>(n: usize) -> Parser<'a, I, &'a [I]> {
+    ParsersImpl::take(n)
+  }
+
+  /// クロージャの結果が真である間は要素を返す[Parser]を返す。<br/>
+  /// Returns a [Parser] that returns elements, while the result of the closure is true.
+  ///
+  /// 解析結果の長さは必須ではありません。<br/>
+  /// The length of the analysis result is not required.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "abcdef";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_while0(|e| match *e {
+  ///  'a'..='c' => true,
+  ///   _ => false
+  /// }).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// ```
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "def";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_while0(|e| match *e {
+  ///  'a'..='c' => true,
+  ///   _ => false
+  /// }).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "");
+  /// ```
+  pub fn take_while0<'a, I, F>(f: F) -> Parser<'a, I, &'a [I]>
+  where
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
+    I: Element + Debug + 'a, {
+    ParsersImpl::take_while0(f)
+  }
+
+  /// クロージャの結果が真である間は要素を返す[Parser]を返す。<br/>
+  /// Returns a [Parser] that returns elements, while the result of the closure is true.
+  ///
+  /// 解析結果の長さは1要素以上必要です。<br/>
+  /// The length of the analysis result must be at least one element.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "abcdef";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_while1(|e| match *e {
+  ///  'a'..='c' => true,
+  ///   _ => false
+  /// }).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// ```
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "def";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_while1(|e| match *e {
+  ///  'a'..='c' => true,
+  ///   _ => false
+  /// }).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_failure());
+  /// assert_eq!(result.failure().unwrap(), ParseError::of_in_complete());
+  /// ```
+  pub fn take_while1<'a, I, F>(f: F) -> Parser<'a, I, &'a [I]>
+  where
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
+    I: Element + Debug + 'a, {
+    ParsersImpl::take_while1(f)
+  }
+
+  /// クロージャの結果が真である間は要素を返す[Parser]を返す。<br/>
+  /// Returns a [Parser] that returns elements, while the result of the closure is true.
+  ///
+  /// 解析結果の長さはn要素以上m要素以下である必要があります。<br/>
+  /// The length of the analysis result should be between n and m elements.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "abcdef";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_while_n_m(1, 3, |e| match *e {
+  ///  'a'..='c' => true,
+  ///   _ => false
+  /// }).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// ```
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "def";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_while_n_m(1, 3, |e| match *e {
+  ///  'a'..='c' => true,
+  ///   _ => false
+  /// }).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_failure());
+  /// assert_eq!(result.failure().unwrap(), ParseError::of_in_complete());
+  /// ```
+  pub fn take_while_n_m<'a, I, F>(n: usize, m: usize, f: F) -> Parser<'a, I, &'a [I]>
+  where
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
+    I: Element + Debug + 'a, {
+    ParsersImpl::take_while_n_m(n, m, f)
+  }
+
+  /// Returns a [Parser] that returns a sequence up to either the end element or the element that matches the condition.<br/>
+  /// 条件に一致する要素もしくは最後の要素までの連続を返す[Parser]を返す。
+  ///
+  /// 解析結果の長さは1要素以上必要です。<br/>
+  /// The length of the analysis result must be at least one element.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "abcdef";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_till0(|e| matches!(*e, 'c')).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// ```
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "def";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_till0(|e| matches!(*e, 'c')).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "def");
+  /// ```
+  pub fn take_till0<'a, I, F>(f: F) -> Parser<'a, I, &'a [I]>
+  where
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
+    I: Element + Debug + 'a, {
+    ParsersImpl::take_till0(f)
+  }
+
+  /// Returns a [Parser] that returns a sequence up to either the end element or the element that matches the condition.<br/>
+  /// 条件に一致する要素もしくは最後の要素までの連続を返す[Parser]を返す。
+  ///
+  /// 解析結果の長さは1要素以上必要です。<br/>
+  /// The length of the analysis result must be at least one element.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "abcdef";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_till1(|e| matches!(*e, 'c')).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// ```
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "def";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_till1(|e| matches!(*e, 'c')).map(String::from_iter);
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_failure());
+  /// assert_eq!(result.failure().unwrap(), ParseError::of_in_complete());
+  /// ```
+  pub fn take_till1<'a, I, F>(f: F) -> Parser<'a, I, &'a [I]>
+  where
+    F: Fn(&I) -> bool + ThreadSafe + 'a,
+    I: Element + Debug + 'a, {
+    ParsersImpl::take_till1(f)
+  }
+
+  /// Returns a [Parser] that returns a text while the closure satisfies the character.<br/>
+  /// 文字がクロージャを満たす間のテキストを返す[Parser]を返す。
+  ///
+  /// The input is either `char` or `u8`, the UTF-8 bytes of a `str`. The text of `u8` is a string slice of the
+  /// input.<br/>
+  /// 入力は`char`または`str`のUTF-8バイト列である`u8`である。`u8`のテキストは入力の文字列スライスとなる。
+  ///
+  /// - f: a closure
+  /// - f: クロージャ
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "αβγdef";
+  ///
+  /// let parser: Parser<u8, &str> = take_chars_while0(|c| c.is_alphabetic() && !c.is_ascii());
+  ///
+  /// let result: ParseResult<u8, &str> = parser.parse_str(text);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "αβγ");
+  /// ```
+  pub fn take_chars_while0<'a, I, F>(f: F) -> Parser<'a, I, I::Text<'a>>
+  where
+    F: Fn(char) -> bool + ThreadSafe + 'a,
+    I: TextInput + 'a, {
+    ParsersImpl::take_chars_while0(f)
+  }
+
+  /// Returns a [Parser] that returns a non-empty text while the closure satisfies the character.<br/>
+  /// 文字がクロージャを満たす間の空でないテキストを返す[Parser]を返す。
+  ///
+  /// The input is either `char` or `u8`, the UTF-8 bytes of a `str`. The text of `u8` is a string slice of the
+  /// input.<br/>
+  /// 入力は`char`または`str`のUTF-8バイト列である`u8`である。`u8`のテキストは入力の文字列スライスとなる。
+  ///
+  /// - f: a closure
+  /// - f: クロージャ
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "def";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, String> = take_chars_while1(|c| c.is_ascii_digit());
+  ///
+  /// let result: ParseResult<char, String> = parser.parse(&input);
+  ///
+  /// assert!(result.is_failure());
+  /// ```
+  pub fn take_chars_while1<'a, I, F>(f: F) -> Parser<'a, I, I::Text<'a>>
+  where
+    F: Fn(char) -> bool + ThreadSafe + 'a,
+    I: TextInput + 'a, {
+    ParsersImpl::take_chars_while1(f)
+  }
+
+  // --- Token Parsers ---
+
+  /// Returns a [Parser] that parses a [Token] of the kind.<br/>
+  /// 指定した種類の[Token]を解析する[Parser]を返す。
+  ///
+  /// The errors are located at the spans of the tokens in the source.<br/>
+  /// エラーはソース中のトークンの範囲に位置付けられる。
+  ///
+  /// - kind: a kind of the token
+  /// - kind: トークンの種類
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// #[derive(Debug, Clone, PartialEq)]
+  /// enum Kind { Ident, Equal, Number }
+  ///
+  /// let lexer = Lexer::new()
+  ///   .token(Kind::Ident, regex("[a-z]+"))
+  ///   .token(Kind::Equal, tag("="))
+  ///   .token(Kind::Number, regex("[0-9]+"))
+  ///   .skip(take_chars_while1(char::is_whitespace));
+  /// let tokens = lexer.tokenize("x =\n  y").unwrap();
+  ///
+  /// let parser = token(Kind::Ident) - token(Kind::Equal) + token(Kind::Number);
+  ///
+  /// let error = parser.parse(&tokens).failure().unwrap();
+  ///
+  /// assert_eq!(error.position(), Some(Position::new(6, 2, 3)));
+  /// ```
+  pub fn token<'a, K>(kind: K) -> Parser<'a, Token<K>, &'a Token<K>>
+  where
+    K: PartialEq + Debug + ThreadSafe + 'a, {
+    ParsersImpl::token(kind)
+  }
+
+  // --- Binary Parsers ---
+
+  /// Returns a [Parser] that parses a number in big-endian order.<br/>
+  /// ビッグエンディアンの数値を解析する[Parser]を返す。
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let input: &[u8] = &[0x01, 0x02, 0x03];
+  ///
+  /// let parser: Parser<u8, u16> = be::<u16>();
+  ///
+  /// let result: ParseResult<u8, u16> = parser.parse(input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), 0x0102);
+  /// ```
+  pub fn be<'a, N>() -> Parser<'a, u8, N>
+  where
+    N: FromBytes + 'a, {
+    ParsersImpl::be()
+  }
+
+  /// Returns a [Parser] that parses a number in little-endian order.<br/>
+  /// リトルエンディアンの数値を解析する[Parser]を返す。
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let input: &[u8] = &[0x01, 0x02, 0x03];
+  ///
+  /// let parser: Parser<u8, u16> = le::<u16>();
+  ///
+  /// let result: ParseResult<u8, u16> = parser.parse(input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), 0x0201);
+  /// ```
+  pub fn le<'a, N>() -> Parser<'a, u8, N>
+  where
+    N: FromBytes + 'a, {
+    ParsersImpl::le()
+  }
+
+  /// Returns a [Parser] that parses an unsigned 16-bit integer in big-endian order.<br/>
+  /// ビッグエンディアンの符号なし16ビット整数を解析する[Parser]を返す。
+  pub fn be_u16<'a>() -> Parser<'a, u8, u16> {
+    ParsersImpl::be()
+  }
+
+  /// Returns a [Parser] that parses an unsigned 32-bit integer in big-endian order.<br/>
+  /// ビッグエンディアンの符号なし32ビット整数を解析する[Parser]を返す。
+  pub fn be_u32<'a>() -> Parser<'a, u8, u32> {
+    ParsersImpl::be()
+  }
+
+  /// Returns a [Parser] that parses an unsigned 64-bit integer in big-endian order.<br/>
+  /// ビッグエンディアンの符号なし64ビット整数を解析する[Parser]を返す。
+  pub fn be_u64<'a>() -> Parser<'a, u8, u64> {
+    ParsersImpl::be()
+  }
+
+  /// Returns a [Parser] that parses a signed 16-bit integer in big-endian order.<br/>
+  /// ビッグエンディアンの符号付き16ビット整数を解析する[Parser]を返す。
+  pub fn be_i16<'a>() -> Parser<'a, u8, i16> {
+    ParsersImpl::be()
+  }
+
+  /// Returns a [Parser] that parses a signed 32-bit integer in big-endian order.<br/>
+  /// ビッグエンディアンの符号付き32ビット整数を解析する[Parser]を返す。
+  pub fn be_i32<'a>() -> Parser<'a, u8, i32> {
+    ParsersImpl::be()
+  }
+
+  /// Returns a [Parser] that parses a signed 64-bit integer in big-endian order.<br/>
+  /// ビッグエンディアンの符号付き64ビット整数を解析する[Parser]を返す。
+  pub fn be_i64<'a>() -> Parser<'a, u8, i64> {
+    ParsersImpl::be()
+  }
+
+  /// Returns a [Parser] that parses a 32-bit float in big-endian order.<br/>
+  /// ビッグエンディアンの32ビット浮動小数点数を解析する[Parser]を返す。
+  pub fn be_f32<'a>() -> Parser<'a, u8, f32> {
+    ParsersImpl::be()
+  }
+
+  /// Returns a [Parser] that parses a 64-bit float in big-endian order.<br/>
+  /// ビッグエンディアンの64ビット浮動小数点数を解析する[Parser]を返す。
+  pub fn be_f64<'a>() -> Parser<'a, u8, f64> {
+    ParsersImpl::be()
+  }
+
+  /// Returns a [Parser] that parses an unsigned 16-bit integer in little-endian order.<br/>
+  /// リトルエンディアンの符号なし16ビット整数を解析する[Parser]を返す。
+  pub fn le_u16<'a>() -> Parser<'a, u8, u16> {
+    ParsersImpl::le()
+  }
+
+  /// Returns a [Parser] that parses an unsigned 32-bit integer in little-endian order.<br/>
+  /// リトルエンディアンの符号なし32ビット整数を解析する[Parser]を返す。
+  pub fn le_u32<'a>() -> Parser<'a, u8, u32> {
+    ParsersImpl::le()
+  }
+
+  /// Returns a [Parser] that parses an unsigned 64-bit integer in little-endian order.<br/>
+  /// リトルエンディアンの符号なし64ビット整数を解析する[Parser]を返す。
+  pub fn le_u64<'a>() -> Parser<'a, u8, u64> {
+    ParsersImpl::le()
+  }
+
+  /// Returns a [Parser] that parses a signed 16-bit integer in little-endian order.<br/>
+  /// リトルエンディアンの符号付き16ビット整数を解析する[Parser]を返す。
+  pub fn le_i16<'a>() -> Parser<'a, u8, i16> {
+    ParsersImpl::le()
+  }
+
+  /// Returns a [Parser] that parses a signed 32-bit integer in little-endian order.<br/>
+  /// リトルエンディアンの符号付き32ビット整数を解析する[Parser]を返す。
+  pub fn le_i32<'a>() -> Parser<'a, u8, i32> {
+    ParsersImpl::le()
+  }
+
+  /// Returns a [Parser] that parses a signed 64-bit integer in little-endian order.<br/>
+  /// リトルエンディアンの符号付き64ビット整数を解析する[Parser]を返す。
+  pub fn le_i64<'a>() -> Parser<'a, u8, i64> {
+    ParsersImpl::le()
+  }
+
+  /// Returns a [Parser] that parses a 32-bit float in little-endian order.<br/>
+  /// リトルエンディアンの32ビット浮動小数点数を解析する[Parser]を返す。
+  pub fn le_f32<'a>() -> Parser<'a, u8, f32> {
+    ParsersImpl::le()
+  }
+
+  /// Returns a [Parser] that parses a 64-bit float in little-endian order.<br/>
+  /// リトルエンディアンの64ビット浮動小数点数を解析する[Parser]を返す。
+  pub fn le_f64<'a>() -> Parser<'a, u8, f64> {
+    ParsersImpl::le()
+  }
+
+  /// Returns a [Parser] that parses an unsigned LEB128 variable-length integer.<br/>
+  /// 符号なしLEB128の可変長整数を解析する[Parser]を返す。
+  ///
+  /// Fails with a mismatch if the value does not fit in 64 bits.<br/>
+  /// 値が64ビットに収まらない場合はミスマッチで失敗する。
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let input: &[u8] = &[0xe5, 0x8e, 0x26];
+  ///
+  /// let parser: Parser<u8, u64> = uleb128();
+  ///
+  /// let result: ParseResult<u8, u64> = parser.parse(input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), 624485);
+  /// ```
+  pub fn uleb128<'a>() -> Parser<'a, u8, u64> {
+    ParsersImpl::uleb128()
+  }
+
+  /// Returns a [Parser] that parses a signed LEB128 variable-length integer.<br/>
+  /// 符号付きLEB128の可変長整数を解析する[Parser]を返す。
+  ///
+  /// Fails with a mismatch if the value does not fit in 64 bits.<br/>
+  /// 値が64ビットに収まらない場合はミスマッチで失敗する。
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let input: &[u8] = &[0xc0, 0xbb, 0x78];
+  ///
+  /// let parser: Parser<u8, i64> = sleb128();
+  ///
+  /// let result: ParseResult<u8, i64> = parser.parse(input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), -123456);
+  /// ```
+  pub fn sleb128<'a>() -> Parser<'a, u8, i64> {
+    ParsersImpl::sleb128()
+  }
+
+  /// Returns a [Parser] that parses a length, and then parses the body within that many bytes.<br/>
+  /// 長さを解析し、その後そのバイト数の範囲内で本体を解析する[Parser]を返す。
+  ///
+  /// The whole length is consumed, even if the body parses fewer bytes. A body that needs more bytes than the length
+  /// fails with a mismatch, not with `Incomplete`, because more input does not make it fit.<br/>
+  /// 本体がより少ないバイト数しか解析しなくても、長さ全体が消費される。長さより多くのバイトを必要とする本体は、入力が
+  /// 増えても収まらないため、`Incomplete`ではなく不一致で失敗する。
+  ///
+  /// - len: a parser of the length
+  /// - len: 長さのパーサー
+  /// - body: a parser of the body
+  /// - body: 本体のパーサー
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let input: &[u8] = b"\x03abcde";
+  ///
+  /// let parser: Parser<u8, &str> = length_prefixed(be::<u8>(), take_chars_while0(|_| true));
+  ///
+  /// let result: ParseResult<u8, &str> = parser.parse(input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// ```
+  pub fn length_prefixed<'a, N, A, E>(len: Parser<'a, u8, N, E>, body: Parser<'a, u8, A, E>) -> Parser<'a, u8, A, E>
+  where
+    N: TryInto<usize> + Debug + 'a,
+    A: 'a,
+    E: UserError, {
+    ParsersImpl::length_prefixed(len, body)
+  }
+
+  /// Returns a [Parser] that reads the input bit by bit with a [BitCursor].<br/>
+  /// [BitCursor]で入力をビット単位で読み取る[Parser]を返す。
+  ///
+  /// The cursor starts at the first bit of the current byte, and the function reads fields across byte boundaries.
+  /// The bytes touched by the cursor are consumed, and the rest bits of the last byte are skipped.<br/>
+  /// カーソルは現在のバイトの最初のビットから始まり、関数はバイト境界をまたいでフィールドを読み取る。カーソルが触れた
+  /// バイトが消費され、最後のバイトの残りのビットは読み飛ばされる。
+  ///
+  /// - f: a function that reads the bits with the cursor
+  /// - f: カーソルでビットを読み取る関数
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let input: &[u8] = &[0b1010_0111, 0b1100_0000];
+  ///
+  /// let parser: Parser<u8, (bool, u64, u64)> = bits(|bp| {
+  ///   let flag = bp.take_bit()?;
+  ///   bp.tag_bits(0b01, 2)?;
+  ///   let kind = bp.take_bits(3)?;
+  ///   let size = bp.take_bits(4)?;
+  ///   Ok((flag, kind, size))
+  /// });
+  ///
+  /// let result: ParseResult<u8, (bool, u64, u64)> = parser.parse(input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), (true, 0b001, 0b1111));
+  /// ```
+  pub fn bits<'a, A, F>(f: F) -> Parser<'a, u8, A>
+  where
+    F: Fn(&mut BitCursor) -> Result<A, BitError> + ThreadSafe + 'a,
+    A: 'a, {
+    ParsersImpl::bits(f)
+  }
+
+  /// Returns a [Parser] that parses consecutive bit fields of the widths, from the most significant bit.<br/>
+  /// 最上位ビットから、指定した幅の連続したビットフィールドを解析する[Parser]を返す。
+  ///
+  /// This is a shorthand of [bits] taking the fields in order.<br/>
+  /// フィールドを順に読み取る[bits]の略記。
+  ///
+  /// - widths: the numbers of bits of the fields, from 1 to 64
+  /// - widths: 各フィールドのビット数(1から64)
+  ///
+  /// # Panics
+  ///
+  /// Panics if a width is out of 1 to 64. Use [try_bit_fields] for widths given at runtime.<br/>
+  /// 幅が1から64の範囲外の場合はパニックする。実行時に与えられる幅には[try_bit_fields]を使う。
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let input: &[u8] = &[0x45, 0x00];
+  ///
+  /// let parser: Parser<u8, Vec<u64>> = bit_fields(&[4, 4, 6, 2]);
+  ///
+  /// let result: ParseResult<u8, Vec<u64>> = parser.parse(input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), vec![4, 5, 0, 0]);
+  /// ```
+  pub fn bit_fields<'a>(widths: &[usize]) -> Parser<'a, u8, Vec<u64>> {
+    ParsersImpl::bit_fields(widths)
+  }
+
+  /// Returns a [Parser] that parses consecutive bit fields of the widths, or an error if a width is out of 1 to
+  /// 64.<br/>
+  /// 指定した幅の連続したビットフィールドを解析する[Parser]を返す。幅が1から64の範囲外の場合はエラーを返す。
+  ///
+  /// - widths: the numbers of bits of the fields
+  /// - widths: 各フィールドのビット数
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let input: &[u8] = &[0x45];
+  ///
+  /// let parser: Parser<u8, Vec<u64>> = try_bit_fields(&[4, 4]).unwrap();
+  ///
+  /// assert_eq!(parser.parse(input).success().unwrap(), vec![4, 5]);
+  /// assert_eq!(try_bit_fields(&[4, 65]).err(), Some(GrammarError::BitWidth(65)));
+  /// ```
+  pub fn try_bit_fields<'a>(widths: &[usize]) -> Result<Parser<'a, u8, Vec<u64>>, GrammarError> {
+    ParsersImpl::try_bit_fields(widths)
+  }
+
+  // --- Offset Control Parsers ---
+
+  /// Returns a [Parser] that skips the specified number of elements.<br/>
+  /// 指定された数の要素をスキップする[Parser]を返す。
+  ///
+  /// - size: a size of elements
+  /// - size: スキップする要素数
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "abcdef";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, &str> = (skip(3) * tag("def"));
+  ///
+  /// let result: ParseResult<char, &str> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "def");
+  /// ```
+  pub fn skip<'a, I>(n: usize) -> Parser<'a, I, ()> {
+    ParsersImpl::skip(n)
+  }
+
+  /// Returns a [Parser] that skips elements until the specified [Parser] succeeds.<br/>
+  /// 指定した[Parser]が成功するまで要素をスキップする[Parser]を返す。
+  ///
+  /// Combined with `recover_with`, it resynchronizes the parsing at a delimiter after a failure.<br/>
+  /// `recover_with`と組み合わせると、失敗の後に区切りの位置で解析を再同期できる。
+  ///
+  /// - parser: a [Parser] of the delimiter
+  /// - parser: 区切りの[Parser]
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "a;1;b;";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let statement: Parser<char, Option<char>> =
+  ///   (elm_alpha().map(Some) - elm(';')).recover_with(skip_until(elm(';')).map(|_| None));
+  /// let parser: Parser<char, Vec<Option<char>>> = statement.of_many0() - end();
+  ///
+  /// let (result, errors) = parser.parse_with_errors(&input);
+  ///
+  /// assert_eq!(result.success().unwrap(), vec![Some('a'), None, Some('b')]);
+  /// assert_eq!(errors.len(), 1);
+  /// assert_eq!(errors[0].offset(), Some(2));
+  /// ```
+  pub fn skip_until<'a, I, A, E>(parser: Parser<'a, I, A, E>) -> Parser<'a, I, A, E>
+  where
+    A: Debug + 'a,
+    E: UserError, {
+    ParsersImpl::skip_until(parser)
+  }
+
+  // --- Enhanced Parsers ---
+
+  /// Return a [Parser] that skips the previous and following [Parser]s.<br/>
+  /// 前後のパーサーをスキップするパーサーを返す。
+  ///
+  /// - lp: 左側のパーサー
+  /// - parser: 中央のパーサー
+  /// - rp: 右側のパーサー
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::iter::FromIterator;
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "(abc)";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// let parser: Parser<char, &str> = surround(elm('('), tag("abc"), elm(')'));
+  ///
+  /// let result: ParseResult<char, &str> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), "abc");
+  /// ```
+  pub fn surround<'a, I, A, B, C, E>(
+    lp: Parser<'a, I, A, E>,
+    parser: Parser<'a, I, B, E>,
+    rp: Parser<'a, I, C, E>,
+  ) -> Parser<'a, I, B, E>
+  where
+    A: Clone + Debug + 'a,
+    B: Clone + Debug + 'a,
+    C: Clone + Debug + 'a,
+    E: UserError, {
+    ParsersImpl::surround(lp, parser, rp)
+  }
+
+  /// Returns a [Parser] that lazily evaluates the specified [Parser].<br/>
   /// 指定した[Parser]を遅延評価する[Parser]を返す。
   ///
   /// - f: Function to generate parser
@@ -1523,23 +2496,107 @@ pub mod prelude {
   /// assert!(result.is_success());
   /// assert_eq!(result.success().unwrap(), "abc");
   /// ```
-  pub fn lazy<'a, I, A, F>(f: F) -> Parser<'a, I, A>
+  #[track_caller]
+  pub fn lazy<'a, I, A, E, F>(f: F) -> Parser<'a, I, A, E>
   where
-    F: Fn() -> Parser<'a, I, A> + 'a,
-    A: Debug + 'a, {
+    F: Fn() -> Parser<'a, I, A, E> + ThreadSafe + 'a,
+    A: Debug + 'a,
+    E: UserError, {
     ParsersImpl::lazy(f)
   }
+
+  /// Returns a [Parser] defined by a recursive rule, built once.<br/>
+  /// 一度だけ構築される、再帰的な規則で定義される[Parser]を返す。
+  ///
+  /// Unlike [lazy], which builds the parser on every parse, `f` is called with the parser itself and its result is
+  /// kept, so the parser is free if the result is free.<br/>
+  /// 解析のたびにパーサーを構築する[lazy]とは異なり、`f`はパーサー自身を引数として呼び出され、その結果が保持されるため、
+  /// 結果が自由であればパーサーも自由となる。
+  ///
+  /// - f: Function to generate parser from the parser itself
+  /// - f: パーサー自身からパーサーを生成する関数
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let parser: Parser<u8, usize> = recursive(|nested| {
+  ///   surround(elm(b'('), nested.opt(), elm(b')')).map(|depth| depth.map_or(1, |depth| depth + 1))
+  /// });
+  ///
+  /// assert!(parser.is_free());
+  /// assert_eq!(parser.parse(b"((()))").success(), Some(3));
+  /// ```
+  #[track_caller]
+  pub fn recursive<'a, I, A, E, F>(f: F) -> Parser<'a, I, A, E>
+  where
+    F: Fn(Parser<'a, I, A, E>) -> Parser<'a, I, A, E>,
+    A: Debug + 'a,
+    E: UserError, {
+    ParsersImpl::recursive(f)
+  }
+
+  /// Returns a [Parser] defined by a left-recursive rule.<br/>
+  /// 左再帰の規則で定義される[Parser]を返す。
+  ///
+  /// The function receives the [Parser] itself and returns its body. At each offset, the body is re-evaluated while
+  /// it consumes more input, starting from the failure of the recursive call, so the result is left-associative.
+  /// The results are kept while the outermost call runs; use [CacheParser::cache] to memoize them in the whole parse
+  /// run. The [Parser] is free if the body is.<br/>
+  /// 関数は[Parser]自身を受け取り、その本体を返す。各オフセットで、再帰呼び出しが失敗する状態から始めて、
+  /// より多くの入力を消費する間は本体を再評価するため、結果は左結合となる。
+  /// 結果は最も外側の呼び出しの実行中に保持される。解析の実行全体でメモ化するには[CacheParser::cache]を使う。
+  /// 本体が自由なら[Parser]も自由である。
+  ///
+  /// Only the recursion through the received [Parser] is handled. Left recursion through [lazy] or [recursive] is not
+  /// detected, and overflows the stack.<br/>
+  /// 扱われるのは受け取った[Parser]を通じた再帰のみである。[lazy]や[recursive]を通じた左再帰は検出されず、
+  /// スタックオーバーフローとなる。
+  ///
+  /// - f: Function that receives the parser itself and returns its body
+  /// - f: パーサー自身を受け取り、その本体を返す関数
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oni_comb_parser_rs::prelude::*;
+  ///
+  /// let text: &str = "9-3-2";
+  /// let input = text.chars().collect::<Vec<_>>();
+  ///
+  /// fn digit<'a>() -> Parser<'a, char, i32> {
+  ///   elm_digit().map(|c: char| c.to_digit(10).unwrap() as i32)
+  /// }
+  /// let parser: Parser<char, i32> = left_rec(|expr| (expr - elm('-') + digit()).map(|(a, b)| a - b) | digit());
+  ///
+  /// let result: ParseResult<char, i32> = parser.parse(&input);
+  ///
+  /// assert!(result.is_success());
+  /// assert_eq!(result.success().unwrap(), 4);
+  /// ```
+  pub fn left_rec<'a, I, A, E, F>(f: F) -> Parser<'a, I, A, E>
+  where
+    F: FnOnce(Parser<'a, I, A, E>) -> Parser<'a, I, A, E>,
+    I: Clone + 'a,
+    A: Clone + Debug + 'a,
+    E: UserError, {
+    ParsersImpl::left_rec(f)
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use std::env;
   use std::iter::FromIterator;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::sync::Arc;
 
   use crate::core::{ParserFunctor, ParserMonad, ParserRunner};
 
   use crate::extension::parser::{
-    CollectParser, ConversionParser, DiscardParser, LoggingParser, OffsetParser, OperatorParser, RepeatParser,
+    CacheParser, CollectParser, ConversionParser, DiscardParser, LoggingParser, OffsetParser, OperatorParser,
+    RepeatParser,
   };
 
   use super::prelude::*;
@@ -1615,12 +2672,91 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_regex_match() {
+    init();
+    let date = Regex::new(r"(?<y>\d{4})-(\d{2})(-(\d{2}))?").unwrap();
+    let input = "é2024-05x".chars().collect::<Vec<_>>();
+    let p = elm('é') * regex_match(date.clone());
+    let m = p.parse(&input).success().unwrap();
+    assert_eq!(m.range(), 1..8);
+    assert_eq!(m.name("y"), Some(&input[1..5]));
+    assert_eq!(m.get_string(2), Some("05".to_string()));
+    assert_eq!((m.get(4), m.group_count()), (None, 5));
+    let m = regex_match(date).parse("2024-05-17".as_bytes()).success().unwrap();
+    assert_eq!(m.get_string(4), Some("17".to_string()));
+
+    // The current offset is the start of the text.
+    let word = Regex::new(r"^\bb|a").unwrap();
+    let input = "ab".chars().collect::<Vec<_>>();
+    let p = elm('a') * regex_match(word.clone()).map(|m| m.range());
+    assert_eq!(p.parse(&input).success(), Some(1..2));
+    let p = elm(b'a') * regex_match(word.clone());
+    assert_eq!(p.parse(b"aa").success().map(|m| m.range()), Some(1..2));
+    assert!(p.parse(b"ac").failure().unwrap().is_mismatch());
+    assert!(regex_match::<char>(word).parse(&[]).failure().unwrap().is_in_complete());
+    let input = "ä b".chars().collect::<Vec<_>>();
+    let p = regex_match(Regex::new(r"\w\b").unwrap()).map(|m| m.range());
+    assert_eq!(p.parse(&input).success(), Some(0..1));
+
+    // The ranges of the groups are in chars for char inputs and in bytes for u8 inputs.
+    let kanji = Regex::new(r"(\p{Han}+)(x?)(\p{Hiragana}*)").unwrap();
+    let input = "日本語のテキスト".chars().collect::<Vec<_>>();
+    let m = regex_match(kanji.clone()).parse(&input).success().unwrap();
+    assert_eq!(
+      (m.range(), m.get_range(2), m.get_range(3)),
+      (0..4, Some(3..3), Some(3..4))
+    );
+    assert_eq!(m.get_string(1), Some("日本語".to_string()));
+    let m = regex_match(kanji)
+      .parse("日本語のテキスト".as_bytes())
+      .success()
+      .unwrap();
+    assert_eq!(
+      (m.range(), m.get_range(2), m.get_range(3)),
+      (0..12, Some(9..9), Some(9..12))
+    );
+    assert_eq!(m.get_string(3), Some("の".to_string()));
+
+    let error = Regex::new("a{2,1}").unwrap_err();
+    assert_eq!(error.pattern(), "a{2,1}");
+  }
+
+  #[test]
+  fn test_try_construction() {
+    init();
+    let error = try_regex::<char>("a(").err().unwrap();
+    assert!(matches!(&error, GrammarError::Regex(e) if e.pattern() == "a("));
+    assert!(std::error::Error::source(&error).is_some());
+    assert!(try_regex::<u8>("(?<n>").is_err());
+
+    let (lo, hi) = (3, 1);
+    assert_eq!(
+      elm(b'a').try_repeat(lo..hi).err(),
+      Some(GrammarError::Range("3..1".to_string()))
+    );
+    assert!(elm(b'a').try_repeat(2..2).is_err());
+    assert_eq!(try_bit_fields(&[0, 8]).err(), Some(GrammarError::BitWidth(0)));
+    assert_eq!(try_bit_fields(&[64, 65]).err(), Some(GrammarError::BitWidth(65)));
+    assert_eq!(
+      try_bit_fields(&[1, 64]).unwrap().parse(&[0xff; 9]).success(),
+      Some(vec![1, u64::MAX])
+    );
+    let p = elm(b'a').try_repeat(2..=3).unwrap();
+    assert_eq!(p.parse(b"aaaa").success(), Some(vec![b'a'; 3]));
+    assert!(p.parse(b"ab").failure().unwrap().is_mismatch());
+
+    // A range without room for an item does not consume the input.
+    let p = elm(b'a').of_count(0) + elm(b'a');
+    assert_eq!(p.parse(b"a").success(), Some((vec![], b'a')));
+  }
+
   #[test]
   fn test_elm_of() {
     init();
     let patterns = b'a'..=b'f';
     let e = patterns.clone();
-    let b = e.enumerate().into_iter().map(|e| e.1).collect::<Vec<_>>();
+    let b = e.enumerate().map(|e| e.1).collect::<Vec<_>>();
     let p = elm_of(&patterns);
 
     for index in 0..b.len() {
@@ -1638,7 +2774,7 @@ mod tests {
     init();
     let patterns = b'a'..=b'f';
     let e = patterns.clone();
-    let b = e.enumerate().into_iter().map(|e| e.1).collect::<Vec<_>>();
+    let b = e.enumerate().map(|e| e.1).collect::<Vec<_>>();
     let p = none_of(&patterns);
 
     for index in 0..b.len() {
@@ -1749,6 +2885,15 @@ mod tests {
 
     let r = p.parse_as_result(b"abc").unwrap();
     assert_eq!(r, b"abc");
+
+    let pattern = String::from("xy");
+    let p = seq(pattern.as_bytes());
+    assert!(p.is_free());
+    for text in ["xyz", "xy"] {
+      let input = text.as_bytes().to_vec();
+      let r = p.shorten().unwrap().parse_as_result(&input).unwrap();
+      assert_eq!(r, b"xy");
+    }
   }
 
   #[test]
@@ -1789,7 +2934,7 @@ mod tests {
     init();
     let p = seq(b"abc").not();
 
-    let _ = p.parse_as_result(b"def").unwrap();
+    p.parse_as_result(b"def").unwrap();
   }
 
   #[test]
@@ -1820,7 +2965,7 @@ mod tests {
     let input1 = "abcd".chars().collect::<Vec<char>>();
     let p = ((elm_ref('a') + elm_ref('b')).flat_map(|e| skip(1).map(move |_| e)) + elm_any_ref() + end())
       .collect()
-      .map(|chars| String::from_iter(chars));
+      .map(String::from_iter);
 
     let result = p.parse_as_result(&input1).unwrap();
     log::debug!("result = {:?}", result);
@@ -1928,6 +3073,311 @@ mod tests {
     assert_eq!(*b, pv2);
   }
 
+  #[test]
+  fn test_binary() {
+    init();
+    let input: &[u8] = &[0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+    assert_eq!(be_u32().parse(input).success(), Some(0x12345678));
+    assert_eq!(le_u32().parse(input).success(), Some(0x78563412));
+    assert_eq!(be_i16().parse(&input[4..]).success(), Some(-0x6544));
+    assert_eq!(be_u64().parse(input).success(), Some(0x123456789abcdef0));
+    assert!(be_u64().parse(&input[1..]).failure().unwrap().is_in_complete());
+    let (float64, float32) = (1.5f64.to_le_bytes(), (-2.25f32).to_be_bytes());
+    assert_eq!(le_f64().parse(&float64).success(), Some(1.5));
+    assert_eq!(be_f32().parse(&float32).success(), Some(-2.25));
+
+    assert_eq!(uleb128().parse(&[0x7f]).success(), Some(127));
+    assert_eq!(uleb128().parse(&[0x80, 0x01]).success(), Some(128));
+    let max: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+    assert_eq!(uleb128().parse(max).success(), Some(u64::MAX));
+    let overflow: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+    assert!(uleb128().parse(overflow).failure().unwrap().is_mismatch());
+    assert!(uleb128().parse(&[0x80]).failure().unwrap().is_in_complete());
+    assert_eq!(sleb128().parse(&[0x7f]).success(), Some(-1));
+    assert_eq!(sleb128().parse(&[0x3f]).success(), Some(63));
+    assert_eq!(sleb128().parse(&[0x80, 0x7f]).success(), Some(-128));
+    let min: &[u8] = &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f];
+    assert_eq!(sleb128().parse(min).success(), Some(i64::MIN));
+
+    let record = length_prefixed(be_u16(), be::<u8>().of_many0()) + be::<u8>();
+    let input: &[u8] = &[0x00, 0x03, 0x01, 0x02, 0x03, 0x04];
+    assert_eq!(record.parse(input).success(), Some((vec![1, 2, 3], 4)));
+    let input: &[u8] = &[0x00, 0x05, 0x01, 0x02];
+    assert!(record.parse(input).failure().unwrap().is_in_complete());
+    let p = length_prefixed(be::<u8>(), be_u16()) - end();
+    let error = p.parse(&[0x01, 0x01, 0x02]).failure().unwrap();
+    assert!(error.is_mismatch());
+    assert_eq!(error.offset(), Some(1));
+    // the end of the frame is not the end of the stream
+    let frame = Grammar::new(|_| length_prefixed(be::<u8>(), elm(b'a') + elm(b'b')));
+    assert!(frame.parse(b"\x01ab").failure().unwrap().is_mismatch());
+    let mut runner = StreamRunner::new();
+    runner.feed(b"\x01ab");
+    assert!(matches!(runner.parse_next(&frame), StreamResult::Failed(e) if e.is_mismatch()));
+
+    let header = bit_fields(&[4, 4]) + bits(|bp| bp.take_bits(16)) + be::<u8>();
+    let input: &[u8] = &[0x45, 0xab, 0xcd, 0xef];
+    assert_eq!(header.parse(input).success(), Some(((vec![4, 5], 0xabcd), 0xef)));
+    // fields cross byte boundaries, and only the touched bytes are consumed
+    let fields = bits(|bp| Ok((bp.take_bits(3)?, bp.take_bits(7)?, bp.take_bit()?, bp.position()))) + be::<u8>();
+    let input: &[u8] = &[0b1010_1100, 0b1110_0000, 0x7f];
+    assert_eq!(
+      fields.parse(input).success(),
+      Some(((0b101, 0b0110011, true, (1, 3)), 0x7f))
+    );
+    let aligned = bits(|bp| {
+      let flag = bp.take_bit()?;
+      bp.align();
+      bp.skip_bits(4)?;
+      Ok((flag, bp.take_bits(4)?))
+    });
+    assert_eq!(aligned.parse(&[0x80, 0x0c]).success(), Some((true, 0x0c)));
+    let error = aligned.parse(&[0x80]).failure().unwrap();
+    assert!(error.is_in_complete());
+    // a mismatched tag after some bits is committed, at the byte of the tag
+    let tagged = bits(|bp| {
+      bp.take_bits(6)?;
+      bp.tag_bits(0b111, 3)
+    });
+    let result = tagged.parse(&[0x00, 0x00]);
+    assert_eq!(result.committed_status(), Some(CommittedStatus::Committed));
+    let error = result.failure().unwrap();
+    assert!(error.is_mismatch());
+    assert_eq!(error.offset(), Some(0));
+    assert_eq!(tagged.parse(&[0x03, 0x80]).success(), Some(0b111));
+  }
+
+  #[test]
+  fn test_unicode() {
+    init();
+    let input = "π_1 Ünï x".chars().collect::<Vec<_>>();
+    let ident = ident_xid().map(String::from_iter);
+    let p = ident.of_many1_sep(elm_unicode_space().of_many1()) - end();
+    assert_eq!(
+      p.parse(&input).success(),
+      Some(vec!["π_1".to_string(), "Ünï".to_string(), "x".to_string()])
+    );
+    let input = "1x".chars().collect::<Vec<_>>();
+    assert!(ident_xid().parse(&input).failure().unwrap().is_mismatch());
+    let input = "_".chars().collect::<Vec<_>>();
+    assert_eq!(ident_xid().parse(&input).success(), Some(&input[..]));
+
+    let input = "٣２\u{3000}".chars().collect::<Vec<_>>();
+    let p = elm_unicode_digit().of_many1() + elm_unicode_space();
+    assert_eq!(p.parse(&input).success(), Some((vec!['٣', '２'], '\u{3000}')));
+    assert!(elm_unicode_alpha().parse(&['1']).failure().unwrap().is_mismatch());
+    assert!('Ω'.is_unicode_uppercase() && 'ω'.is_unicode_lowercase() && '\u{7}'.is_unicode_control());
+
+    let input = "größe_1\u{e9} = 1".as_bytes();
+    assert_eq!(ident_xid().parse(input).success(), Some("größe_1\u{e9}".as_bytes()));
+    let input = "_変数".as_bytes();
+    assert_eq!(ident_xid().parse(input).success(), Some(input));
+    assert!(ident_xid().parse("１x".as_bytes()).failure().unwrap().is_mismatch());
+    assert!(elm_unicode_alpha().parse(&[0xc3]).failure().unwrap().is_mismatch());
+
+    let letters = [GeneralCategory::UppercaseLetter, GeneralCategory::LowercaseLetter];
+    let input = "Ωω\u{301}".chars().collect::<Vec<_>>();
+    let p = elm_unicode_category(&letters).of_many1() + elm_unicode_category(&[GeneralCategory::NonspacingMark]);
+    assert_eq!(p.parse(&input).success(), Some((vec!['Ω', 'ω'], '\u{301}')));
+    assert!(elm_unicode_category(&letters)
+      .parse(&['漢'])
+      .failure()
+      .unwrap()
+      .is_mismatch());
+    assert_eq!('漢'.unicode_category(), Some(GeneralCategory::OtherLetter));
+    assert_eq!(elm_unicode_category_ref(&letters).parse(b"a").success(), Some(&b'a'));
+    assert_eq!(0xc3u8.unicode_category(), None);
+    let p = elm_unicode_category::<char>(&letters);
+    assert_eq!(p.syntax().to_ebnf(), "grammar ::= <Lu|Ll>\n");
+  }
+
+  #[test]
+  fn test_tracer() {
+    init();
+    let mut events = vec![];
+    {
+      let p = (elm(b'a').name("a") | elm(b'b').name("b")).name("ab");
+      let (result, _) = p.parse_with_tracer(b"b", |event: TraceEvent| match event {
+        TraceEvent::Enter { name, offset } => events.push(format!("> {} {}", name, offset)),
+        TraceEvent::Exit { name, outcome, .. } => events.push(format!("< {} {:?}", name, outcome)),
+      });
+      assert!(result.is_success());
+    }
+    assert_eq!(
+      events,
+      vec![
+        "> ab 0",
+        "> a 0",
+        "< a Failure { committed: false }",
+        "> b 0",
+        "< b Success { length: 1 }",
+        "< ab Success { length: 1 }",
+      ]
+    );
+
+    fn list<'a>() -> Parser<'a, u8, usize> {
+      let item = elm_digit().map(|_| 1) | lazy(list);
+      (elm(b'(') * item.of_many0() - elm(b')'))
+        .map(|items| items.iter().sum())
+        .name("list")
+    }
+    let (result, trace) = list().parse_with_tracer(b"(1(2))", Trace::new());
+    assert_eq!(result.success(), Some(2));
+    assert_eq!(trace.roots().len(), 1);
+    let inner = &trace.roots()[0].children[0];
+    assert_eq!(
+      (inner.offset, inner.outcome),
+      (2, Some(TraceOutcome::Success { length: 3 }))
+    );
+    let rule = trace.profile().into_iter().find(|rule| rule.name == "list").unwrap();
+    assert_eq!((rule.invocations, rule.successes, rule.backtracks), (4, 2, 2));
+    assert!(rule.total_time >= rule.self_time);
+    assert_eq!(trace.roots()[0].elapsed, rule.total_time);
+    let stacks = trace.folded_stacks();
+    let frames = stacks
+      .lines()
+      .map(|line| line.rsplit_once(' ').unwrap().0)
+      .collect::<Vec<_>>();
+    assert_eq!(frames, vec!["list", "list;list", "list;list;list"]);
+    assert_eq!(trace.call_tree().lines().count(), 4);
+
+    assert_eq!(list().parse(b"(1)").success(), Some(1));
+
+    // a cache hit does not run the named parsers inside the cached parser, but the cached parser reports it
+    let word = seq(b"ab").name("ab").cache().name("word");
+    let p = (word.clone() - elm(b'x')).attempt() | word;
+    let (result, trace) = p.parse_with_tracer(b"ab", Trace::new());
+    assert!(result.is_success());
+    let names = trace
+      .roots()
+      .iter()
+      .map(|node| (node.name.as_str(), node.children.len()))
+      .collect::<Vec<_>>();
+    assert_eq!(names, vec![("word", 1), ("word", 0)]);
+  }
+
+  #[test]
+  fn test_syntax() {
+    init();
+    fn expr<'a>() -> Parser<'a, u8, ()> {
+      let number = (elm_digit_1_9() + elm_digit().of_many_n_m(0, 2)).name("number");
+      let atom = number.discard() | (elm(b'(') * lazy(expr) - elm(b')'));
+      let op = elm_of(b"+-").map(|_| |a: (), _: ()| a);
+      atom.chain_left1(op)
+    }
+    assert_eq!(
+      expr().syntax().to_ebnf(),
+      "expr   ::= (number | \"(\" expr \")\") ([+#x2D] (number | \"(\" expr \")\"))*\n\
+       number ::= [1-9] [0-9]{0,2}\n"
+    );
+
+    let key = (tag("key") | regex("[a-z]+")).name("key");
+    let pair = (key - elm(b'=').opt() + elm_in(b'0', b'9').of_many1_sep(elm(b','))).name("pair");
+    assert_eq!(
+      pair.syntax().to_ebnf(),
+      "pair ::= key \"=\"? [0-9] (\",\" [0-9])*\n\
+       key  ::= \"key\" | <regex [a-z]+>\n"
+    );
+    assert_eq!(take::<u8>(2).syntax().to_ebnf(), "grammar ::= <?>\n");
+
+    #[allow(clippy::redundant_closure)]
+    fn value<'a>() -> Parser<'a, u8, ()> {
+      lazy(|| array()) | lazy(|| object()) | elm(b'1').discard()
+    }
+    fn array<'a>() -> Parser<'a, u8, ()> {
+      surround(elm(b'['), value(), elm(b']'))
+    }
+    fn object<'a>() -> Parser<'a, u8, ()> {
+      surround(elm(b'{'), value(), elm(b'}'))
+    }
+    // The closures share their type name, but they are not the same rule.
+    assert_eq!(
+      value().syntax().to_ebnf(),
+      "grammar ::= value | value2 | \"1\"\n\
+       value2  ::= \"{\" (value | value2 | \"1\") \"}\"\n\
+       value   ::= \"[\" (value | value2 | \"1\") \"]\"\n"
+    );
+
+    let svg = pair.syntax().to_railroad_svg();
+    assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+    assert!(svg.contains(r#"<text class="rule" x="20" y="24">pair</text>"#));
+    assert!(svg.contains(r#"<rect class="opaque""#) && svg.contains(">regex [a-z]+</text>"));
+    assert_eq!(svg.matches(r#"class="rule""#).count(), 2);
+
+    assert_eq!((expr() - end()).parse(b"1+(23-4)").success(), Some(()));
+  }
+
+  #[test]
+  fn test_generator() {
+    init();
+    fn expr<'a>() -> Parser<'a, u8, ()> {
+      let number = (elm_digit_1_9() + elm_digit().of_many_n_m(0, 2)).name("number");
+      let atom = number.discard() | (elm(b'(') * lazy(expr) - elm(b')'));
+      let op = elm_of(b"+-").map(|_| |a: (), _: ()| a);
+      atom.chain_left1(op)
+    }
+    let generator = Generator::new(expr().syntax()).with_max_depth(2);
+    assert_eq!(generator.generate(42), generator.generate(42));
+    for seed in 0..200 {
+      let input = generator.generate(seed).unwrap();
+      let depth = input.iter().fold((0, 0), |(depth, max), c| match c {
+        b'(' => (depth + 1, max.max(depth + 1)),
+        b')' => (depth - 1, max),
+        _ => (depth, max),
+      });
+      assert!(depth.1 <= 2, "{}", String::from_utf8_lossy(&input));
+      assert!((expr() - end()).parse(&input).is_success());
+    }
+
+    fn comment<'a>() -> Parser<'a, u8, &'a [u8]> {
+      elm(b'#') * take_till0(|c| *c == b'\n')
+    }
+    let input = Generator::new(comment().syntax()).generate(0).unwrap();
+    assert_eq!((comment() - end()).parse(&input).success(), Some(&input[1..]));
+
+    let sevens = Generator::new(expr().syntax()).with_rule("number", elm(b'7').syntax());
+    for seed in 0..50 {
+      let input = sevens.generate(seed).unwrap();
+      assert!(
+        input.iter().all(|c| b"7+-()".contains(c)),
+        "{}",
+        String::from_utf8_lossy(&input)
+      );
+      assert!((expr() - end()).parse(&input).is_success());
+    }
+
+    let odd = Generator::new(expr().syntax()).with_filter(|input| input.len() % 2 == 1);
+    assert_eq!(odd.generate(7).unwrap().len() % 2, 1);
+    let none = Generator::new(expr().syntax()).with_max_depth(0).with_filter(|_| false);
+    assert_eq!(none.generate(0), Err(GenerateError::Rejected(1000)));
+
+    let error = Generator::new(take::<u8>(2).syntax()).generate(0);
+    assert_eq!(error, Err(GenerateError::Opaque("".to_string())));
+
+    let categories = [GeneralCategory::NonspacingMark, GeneralCategory::MathSymbol];
+    let generator = Generator::new(elm_unicode_category::<char>(&categories).syntax());
+    for seed in 0..20 {
+      let input = generator.generate(seed).unwrap();
+      assert!((elm_unicode_category(&categories) - end()).parse(&input).is_success());
+    }
+  }
+
+  #[test]
+  fn test_spanned() {
+    init();
+    let input = "ab\n cd";
+    let word = take_chars_while1(|c: char| c.is_ascii_alphabetic()).spanned();
+    let p = word.of_many1_sep(take_chars_while1(char::is_whitespace)) - end();
+
+    let result = p.parse_str(input).success().unwrap();
+    assert_eq!(result, vec![Spanned::new("ab", 0..2), Spanned::new("cd", 4..6)]);
+    assert_eq!(
+      result[1].span(input.as_bytes()),
+      Span::new(Position::new(4, 2, 2), Position::new(6, 2, 4))
+    );
+    assert_eq!(result[1].clone().map(str::len), Spanned::new(2, 4..6));
+  }
+
   #[test]
   fn test_last_offset() {
     init();
@@ -1960,6 +3410,20 @@ mod tests {
     assert_eq!(*result, pv2);
   }
 
+  #[test]
+  fn test_or_incomplete() {
+    init();
+    let input = b"PO";
+    let mismatch: ParseError<u8> = ParseError::of_mismatch(input, 0, 1, "mismatch".to_string());
+    assert!(ParseError::of_in_complete().merge(mismatch.clone()).is_in_complete());
+    assert!(mismatch.merge(ParseError::of_in_complete()).is_in_complete());
+
+    let p = tag("POST") | tag("GET");
+    assert!(p.parse(input).failure().unwrap().is_in_complete());
+    let p = tag("GET") | tag("POST");
+    assert!(p.parse(input).failure().unwrap().is_in_complete());
+  }
+
   #[test]
   fn test_skip_left() {
     init();
@@ -2057,4 +3521,649 @@ mod tests {
       assert!(result.is_err());
     }
   }
+
+  #[test]
+  fn test_position() {
+    init();
+    {
+      let input = "ab\ncd\r\nef".chars().collect::<Vec<char>>();
+      assert_eq!(Position::of(&input, 0), Position::new(0, 1, 1));
+      assert_eq!(Position::of(&input, 4), Position::new(4, 2, 2));
+      assert_eq!(Position::of(&input, 7), Position::new(7, 3, 1));
+      assert_eq!(ParseState::new(&input, 9).position(), Position::new(9, 3, 3));
+    }
+    {
+      let input = "あい\nう".as_bytes();
+      assert_eq!(Position::of(input, 6), Position::new(6, 1, 3));
+      assert_eq!(Position::of(input, 10), Position::new(10, 2, 2));
+    }
+  }
+
+  #[test]
+  fn test_parse_error_position() {
+    init();
+    let input = "abc\nabd".chars().collect::<Vec<char>>();
+    let p = tag("abc") + elm('\n') + tag("abc");
+
+    let error = p.parse_as_result(&input).unwrap_err();
+    log::debug!("error = {}", error);
+    assert_eq!(error.position(), Some(Position::new(6, 2, 3)));
+    assert!(error.to_string().contains("offset 6"));
+    assert!(error.located().to_string().contains("line 2, column 3"));
+
+    struct Cell;
+    let input = [Cell, Cell];
+    let error: ParseError<Cell> = ParseError::of_mismatch(&input, 1, 1, "unexpected cell".to_string());
+    assert_eq!(error.to_string(), "Mismatch at offset 1: unexpected cell");
+  }
+
+  #[test]
+  fn test_render() {
+    init();
+    let text = "abc\nxyz";
+    let input = text.chars().collect::<Vec<char>>();
+    let p = (tag("abc") + elm('\n') + tag("xyz").name("xyz") + elm('!')).name("line");
+
+    let error = p.parse_as_result(&input).unwrap_err();
+    let rendered = error.render(text);
+    log::debug!("rendered = \n{}", rendered);
+    assert_eq!(
+      rendered,
+      "error: offset: 7
+ --> line 2, column 4
+  |
+2 | xyz
+  |    ^
+  |
+  = note: expected `line` (at line 1, column 1)
+"
+    );
+
+    let input = "abc\nxbc".chars().collect::<Vec<char>>();
+    let p = tag("abc") + elm('\n') + tag("xyz").name("xyz");
+    let error = p.parse_as_result(&input).unwrap_err();
+    let diagnostic = error.to_diagnostic();
+    assert_eq!(diagnostic.position(), Some(Position::new(5, 2, 2)));
+    assert_eq!(diagnostic.width(), 1);
+    assert_eq!(
+      diagnostic.notes(),
+      &["expected `xyz` (at line 2, column 1)".to_string()]
+    );
+  }
+
+  #[test]
+  fn test_or_expected() {
+    init();
+    let input = "TUE".chars().collect::<Vec<char>>();
+    let p = tag("SUN").discard().name("SUN") | tag("MON").discard().expect("MON") | elm_digit().discard().name("digit");
+
+    let error = p.parse_as_result(&input).unwrap_err();
+    log::debug!("error = {}", error);
+    assert!(error.is_expected());
+    assert_eq!(error.expected(), vec!["SUN", "MON", "digit"]);
+    assert!(error
+      .located()
+      .to_string()
+      .starts_with("expected one of: `SUN`, `MON`, `digit` at line 1, column 1"));
+
+    let input = "SUX".chars().collect::<Vec<char>>();
+    let p = tag("SUN").attempt().name("SUN") | tag("MON").name("MON");
+    let error = p.parse_as_result(&input).unwrap_err();
+    assert_eq!(error.expected(), vec!["SUN"]);
+  }
+
+  #[test]
+  fn test_farthest_failure() {
+    init();
+    let input = "abcdeX".chars().collect::<Vec<char>>();
+    let p = ((tag("abc") + tag("def")).attempt().discard() | tag("ab").discard()) - end();
+
+    let error = p.parse_as_result(&input).unwrap_err();
+    log::debug!("error = {}", error);
+    assert_eq!(error.offset(), Some(5));
+    assert_eq!(error.expected(), vec!["tag \"def\""]);
+
+    let input = "12a".chars().collect::<Vec<char>>();
+    let p = elm_digit().name("digit").of_many0() - end();
+    let error = p.parse_as_result(&input).unwrap_err();
+    assert_eq!(error.offset(), Some(2));
+    assert_eq!(error.expected(), vec!["digit", "end of input"]);
+
+    let error: ParseError<char> = ParseError::of_mismatch(&input, 0, 1, "expect digit, found: a".to_string());
+    assert!(error.expected().is_empty());
+  }
+
+  #[test]
+  fn test_recover_with() {
+    init();
+    let input = "a;1;b;2c;d;".chars().collect::<Vec<char>>();
+    let statement = (elm_alpha().map(Some) - elm(';')).recover_with(skip_until(elm(';')).map(|_| None));
+    let p = statement.of_many0() - end();
+
+    let (result, errors) = p.parse_with_errors(&input);
+    assert_eq!(
+      result.success().unwrap(),
+      vec![Some('a'), None, Some('b'), None, Some('d')]
+    );
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].offset(), Some(2));
+    assert_eq!(errors[1].offset(), Some(6));
+
+    // the errors recovered in a discarded alternative are dropped
+    let input = "x;1".chars().collect::<Vec<char>>();
+    let statement = (elm_digit().map(Some) - elm(';')).recover_with(skip_until(elm(';')).map(|_| None));
+    let p = (statement - elm('!')).attempt() | (elm_alpha().map(Some) - skip(2));
+    let (result, errors) = p.parse_with_errors(&input);
+    assert_eq!(result.success().unwrap(), Some('x'));
+    assert!(errors.is_empty());
+  }
+
+  #[test]
+  fn test_stream_runner() {
+    init();
+    fn line<'a>() -> Parser<'a, u8, String> {
+      (elm_digit().of_many1().collect_vec() - elm(b'\n')).map(|s| String::from_utf8(s).unwrap())
+    }
+    let line = Grammar::new(|_| line());
+    assert!(line.is_built());
+    let mut runner = StreamRunner::new();
+
+    runner.feed(b"12");
+    assert!(matches!(runner.parse_next(&line), StreamResult::NeedMoreInput));
+    runner.feed(b"3\n45");
+    assert!(matches!(runner.parse_next(&line), StreamResult::Parsed(s) if s == "123"));
+    assert!(matches!(runner.parse_next(&line), StreamResult::NeedMoreInput));
+    assert_eq!(runner.offset(), 4);
+    runner.feed(b"\nx\n");
+    assert!(matches!(runner.parse_next(&line), StreamResult::Parsed(s) if s == "45"));
+    assert!(matches!(runner.parse_next(&line), StreamResult::Failed(e) if e.offset() == Some(0)));
+
+    let mut runner = StreamRunner::new();
+    let mut reader: &[u8] = b"1\n2";
+    while runner.read_from(&mut reader, 2).unwrap() > 0 {}
+    assert!(matches!(runner.parse_next(&line), StreamResult::Parsed(s) if s == "1"));
+    assert!(matches!(runner.parse_next(&line), StreamResult::Failed(_)));
+    assert!(!runner.is_finished());
+
+    // a value that ends at the end of the buffer does not wait, unless the parser looked beyond it
+    let mut runner = StreamRunner::new();
+    runner.feed(b"ab");
+    let pair = Grammar::new(|_| seq(b"ab").map(<[u8]>::to_vec));
+    assert!(matches!(runner.parse_next(&pair), StreamResult::Parsed(s) if s == b"ab"));
+    runner.feed(b"ab");
+    let pairs = Grammar::new(|_| seq(b"ab").of_many1().map(|pairs| pairs.len()));
+    assert!(matches!(runner.parse_next(&pairs), StreamResult::NeedMoreInput));
+
+    // a failure waits if an alternative reached the end, in either order
+    let methods = [
+      Grammar::new(|_| (tag("POST") | tag("GET")).map(str::to_string)),
+      Grammar::new(|_| (tag("GET") | tag("POST")).map(str::to_string)),
+    ];
+    for method in &methods {
+      let mut runner = StreamRunner::new();
+      runner.feed(b"PO");
+      assert!(matches!(runner.parse_next(method), StreamResult::NeedMoreInput));
+      runner.feed(b"ST");
+      assert!(matches!(runner.parse_next(method), StreamResult::Parsed(s) if s == "POST"));
+    }
+
+    let mut runner = StreamRunner::new();
+    runner.feed(b"aa");
+    // a bound parser is built for each call
+    let taken = Grammar::new(|_| take(2).map(<[u8]>::to_vec));
+    assert!(!taken.is_built());
+    assert!(matches!(runner.parse_next(&taken), StreamResult::Parsed(s) if s == b"aa"));
+  }
+
+  #[test]
+  fn test_str_parsers() {
+    init();
+    let text = "日本語 = 123";
+    let p = take_chars_while1(char::is_alphabetic) - tag(" = ") + regex("[0-9]+") - end();
+
+    let (key, value) = p.parse_str(text).success().unwrap();
+    assert_eq!(key, "日本語");
+    assert_eq!(value, "123");
+
+    let error = (tag("日本") + tag("人")).parse_str(text).failure().unwrap();
+    assert_eq!(error.offset(), Some(6));
+    assert_eq!(error.position(), Some(Position::new(6, 1, 3)));
+    assert_eq!(error.expected(), vec!["tag \"人\""]);
+
+    // the same parsers work on chars
+    let input = text.chars().collect::<Vec<_>>();
+    let p = take_chars_while1(char::is_alphabetic) - tag_no_case(" = ") + regex("[0-9]+") - end();
+    let (key, value) = p.parse(&input).success().unwrap();
+    assert_eq!(key, "日本語");
+    assert_eq!(value, "123");
+  }
+
+  #[test]
+  fn test_input() {
+    init();
+    let text = "abc".to_string();
+    let p = tag("abc");
+    assert_eq!(p.parse_str("abc").success(), Some("abc"));
+    assert_eq!(p.parse(text.as_bytes()).success(), Some("abc"));
+    assert_eq!(seq(b"abc").parse(b"abc").success(), Some(&b"abc"[..]));
+
+    let chars = "abc".chars().collect::<Vec<_>>();
+    assert_eq!(tag("abc").parse(&chars).success(), Some("abc"));
+    assert_eq!(tag("abc").parse(&chars[..]).success(), Some("abc"));
+    assert_eq!(tag("abc").parse("abc").success(), Some("abc"));
+    assert_eq!(tag("abc").parse(&text).success(), Some("abc"));
+
+    // a chunked input gives the results of the contiguous one, wherever it is split
+    let bytes = "key = 日本語, 42".as_bytes();
+    let inputs = (0..=bytes.len())
+      .map(|split| ChunkedInput::new([&bytes[..split], &bytes[split..]]))
+      .collect::<Vec<_>>();
+    let p = tag("key") * tag(" = ") * take_chars_while1(char::is_alphabetic) - tag(", ")
+      + (take_while1(|b: &u8| b.is_ascii_digit()) - end());
+    let q = tag("key = 日本") + tag("人");
+    for input in &inputs {
+      assert_eq!(p.parse(input).success(), Some(("日本語", &b"42"[..])));
+      let error = q.parse(input).failure().unwrap();
+      assert_eq!(error.offset(), Some(12));
+      assert_eq!(error.position(), Some(Position::new(12, 1, 9)));
+    }
+
+    // the copies of the ranges across the chunks are kept by the input
+    let input = ChunkedInput::new([&b"ab"[..], b"", b"cd", b"e"]);
+    let source = (&input).into_source();
+    assert_eq!(source.len(), 5);
+    assert_eq!(source.chunk(1), b"b");
+    assert_eq!(source.slice(1, 4), b"bcd");
+    assert!(std::ptr::eq(source.slice(1, 4), source.slice(1, 4)));
+    assert_eq!(source.iter(1).copied().collect::<Vec<_>>(), b"bcde");
+    assert_eq!(source.truncate(3).to_slice(), b"abc");
+  }
+
+  #[test]
+  fn test_lexer() {
+    init();
+    #[derive(Debug, Clone, PartialEq)]
+    enum Kind {
+      Ident,
+      Number,
+      Plus,
+    }
+    let lexer = Lexer::new()
+      .token(Kind::Ident, regex("[a-z]+"))
+      .token(Kind::Number, regex("[0-9]+"))
+      .token(Kind::Plus, tag("+"))
+      .skip(take_chars_while1(char::is_whitespace));
+
+    let source = "a + 12\n+ b";
+    let tokens = lexer.tokenize(source).unwrap();
+    let kinds = tokens.iter().map(|t| t.kind.clone()).collect::<Vec<_>>();
+    assert_eq!(
+      kinds,
+      vec![Kind::Ident, Kind::Plus, Kind::Number, Kind::Plus, Kind::Ident]
+    );
+    assert_eq!(
+      tokens[2].span,
+      Span::new(Position::new(4, 1, 5), Position::new(6, 1, 7))
+    );
+
+    let error_tokens = lexer.tokenize("a +\n+ b").unwrap();
+    let operand = token(Kind::Ident) | token(Kind::Number);
+    let p = operand.clone() + (token(Kind::Plus) * operand).of_many0() - end();
+    assert!(p.parse(&tokens).is_success());
+
+    let error = p.parse(&error_tokens).failure().unwrap();
+    assert_eq!(error.position(), Some(Position::new(4, 2, 1)));
+    let rendered = error.render("a +\n+ b");
+    log::debug!("{}", rendered);
+    assert!(rendered.contains("2 | + b\n  | ^\n"));
+
+    let error = lexer.tokenize("a ? b").unwrap_err();
+    assert_eq!(error.position(), Some(Position::new(2, 1, 3)));
+  }
+
+  #[test]
+  fn test_cache() {
+    init();
+    let input1 = "aby".chars().collect::<Vec<char>>();
+    let input2 = "abx".chars().collect::<Vec<char>>();
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = count.clone();
+    let word = tag("ab")
+      .map(move |s| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        s
+      })
+      .cache();
+    let p = (word.clone() - elm('x')).attempt() | (word - elm('y'));
+
+    assert_eq!(p.parse(&input1).success(), Some("ab"));
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+
+    // the memo table is not shared between the parse runs
+    assert_eq!(p.parse(&input2).success(), Some("ab"));
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+
+    // a memo hit records again the failure discarded while computing the entry
+    let input = "abcx".chars().collect::<Vec<char>>();
+    let p = ((tag("abc") + tag("d")).attempt().discard() | tag("ab").discard()).cache();
+    let parse_state = ParseState::new(&input, 0);
+    assert!(p.run(&parse_state).is_success());
+    assert_eq!(parse_state.recorded_failure::<()>().and_then(|e| e.offset()), Some(3));
+    parse_state.farthest_failure::<()>(ParseError::of_in_complete());
+    assert!(parse_state.recorded_failure::<()>().is_none());
+    assert!(p.run(&parse_state).is_success());
+    assert_eq!(parse_state.recorded_failure::<()>().and_then(|e| e.offset()), Some(3));
+
+    // an entry computed in a limited view is not reused for the whole input
+    let many = elm(b'a').of_many0().cache();
+    let p = (length_prefixed(be::<u8>(), many.clone()) - elm(b'x')).attempt() | (be::<u8>() * many);
+    assert_eq!(p.parse(b"\x01aaa").success(), Some(vec![b'a'; 3]));
+
+    // the memo table of a parse run is dropped when the run finishes
+    let value = Arc::new(());
+    let p = {
+      let value = value.clone();
+      elm_any().map(move |_| value.clone()).cache()
+    };
+    assert!(p.parse(&input).is_success());
+    assert_eq!(Arc::strong_count(&value), 2);
+
+    // the memo table belongs to the run, so it is dropped with the last state even after the parser
+    let parse_state = ParseState::new(&input, 0);
+    assert!(p.run(&parse_state).is_success());
+    assert_eq!(Arc::strong_count(&value), 3);
+    drop(p);
+    assert_eq!(Arc::strong_count(&value), 2);
+    drop(parse_state);
+    assert_eq!(Arc::strong_count(&value), 1);
+  }
+
+  #[test]
+  fn test_left_rec() {
+    init();
+    let input1 = "9-3-2".chars().collect::<Vec<char>>();
+    let input2 = "9-(3-2)".chars().collect::<Vec<char>>();
+    let input3 = "-1".chars().collect::<Vec<char>>();
+
+    fn term<'a>() -> Parser<'a, char, i32> {
+      elm_digit().map(|c: char| c.to_digit(10).unwrap() as i32) | surround(elm('('), lazy(expr), elm(')'))
+    }
+    fn expr<'a>() -> Parser<'a, char, i32> {
+      left_rec(|expr| (expr - elm('-') + term()).map(|(a, b)| a - b) | term())
+    }
+    let p = expr() - end();
+
+    assert_eq!(p.parse(&input1).success(), Some(4));
+    assert_eq!(p.parse(&input2).success(), Some(8));
+    assert!(p.parse(&input3).is_failure());
+
+    let digit = || elm_digit().map(|c: char| c.to_digit(10).unwrap() as i32);
+    let minus = elm('-');
+    let free = left_rec(move |expr| (expr - minus + digit()).map(|(a, b)| a - b) | digit());
+    assert!(free.is_free());
+    assert_eq!(free.parse(&input1).success(), Some(4));
+
+    let bound = left_rec(|expr| (expr - elm('-') + digit().cache()).map(|(a, b)| a - b) | digit());
+    assert!(!bound.is_free());
+    assert_eq!(bound.parse(&input1).success(), Some(4));
+  }
+
+  #[test]
+  fn test_expr_builder() {
+    init();
+    fn binary<'a>(op: char) -> Parser<'a, char, impl Fn(String, String) -> String> {
+      elm(op).map(move |_| move |a, b| format!("({} {} {})", op, a, b))
+    }
+    fn expr<'a>() -> Parser<'a, char, String> {
+      let atom = elm_alpha().map(|c: char| c.to_string()) | surround(elm('('), lazy(expr), elm(')'));
+      let index = surround(elm('['), lazy(expr), elm(']')).map(|i| move |a| format!("([] {} {})", a, i));
+      ExprBuilder::new(atom)
+        .ternary(1, elm('?'), elm(':'), |c, a, b| format!("(? {} {} {})", c, a, b))
+        .infix_non(2, binary('<'))
+        .infix_left(3, binary('+'))
+        .infix_left(3, binary('-'))
+        .infix_left(4, binary('*'))
+        .prefix(5, elm('-').map(|_| |a| format!("(- {})", a)))
+        .infix_right(6, binary('^'))
+        .postfix(7, elm('!').map(|_| |a| format!("(! {})", a)))
+        .postfix(7, index)
+        .build()
+    }
+    let parse = |text: &str| {
+      let input = text.chars().collect::<Vec<char>>();
+      let p = expr() - end();
+      let result = p.parse(&input).success();
+      result
+    };
+
+    assert_eq!(parse("a-b-c*d"), Some("(- (- a b) (* c d))".to_string()));
+    assert_eq!(parse("a^b^c"), Some("(^ a (^ b c))".to_string()));
+    assert_eq!(parse("-a^b*c!"), Some("(* (- (^ a b)) (! c))".to_string()));
+    assert_eq!(parse("a[b+c]!"), Some("(! ([] a (+ b c)))".to_string()));
+    assert_eq!(parse("a<b?c:d?e:f"), Some("(? (< a b) c (? d e f))".to_string()));
+    assert_eq!(parse("(a<b)<c"), Some("(< (< a b) c)".to_string()));
+    assert_eq!(parse("a<b<c"), None);
+    assert_eq!(parse("a+"), None);
+  }
+
+  #[test]
+  fn test_grammar() {
+    init();
+    fn sum<'a>() -> Parser<'a, u8, u32> {
+      let number = elm_digit().map(|b: u8| (b - b'0') as u32).cache();
+      number.of_many1_sep(elm(b',')).map(|ns| ns.iter().sum::<u32>()) - end()
+    }
+    let grammar = Grammar::new(|_| sum());
+
+    let sums = ["1,2", "3,4,5", "6,x"]
+      .iter()
+      .map(|text| {
+        let input = text.to_string();
+        let result = grammar.parse_str(&input).success();
+        result
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(sums, vec![Some(3), Some(12), None]);
+
+    let input = "7,8".to_string();
+    let p = grammar.parser().map(|n| n * 2);
+    assert_eq!(p.parse_str(&input).success(), Some(30));
+  }
+
+  #[test]
+  fn test_free() {
+    init();
+    let free: Vec<Parser<'static, u8, ()>> = vec![
+      elm(b'a').discard(),
+      elm_of(b"ab").discard(),
+      seq(b"ab").discard(),
+      tag("ab").discard(),
+      elm_digit().of_many1().collect_vec().discard(),
+      (elm(b'a') + elm(b'b')).attempt().discard() | end(),
+    ];
+    assert!(free.iter().all(Parser::is_free));
+    let bound: Vec<Parser<'static, u8, ()>> = vec![
+      elm_ref(b'a').discard(),
+      take(1).discard(),
+      elm(b'a').collect().discard(),
+      (elm(b'a') + take(1)).discard(),
+      lazy(|| elm(b'a')).discard(),
+    ];
+    assert!(bound.iter().all(|p| !p.is_free()));
+    assert!(elm_ref(b'a').shorten().is_none());
+
+    let p: Parser<'static, u8, Vec<u8>> = elm_digit().of_many1().collect_vec();
+    for text in ["1", "23", "456x"] {
+      let input = text.as_bytes().to_vec();
+      let r = p.shorten().unwrap().parse(&input).success();
+      assert_eq!(r, Some(text.trim_end_matches('x').as_bytes().to_vec()));
+    }
+  }
+
+  #[test]
+  fn test_grammar_built_once() {
+    init();
+    let builds = Arc::new(AtomicUsize::new(0));
+    let counter = builds.clone();
+    let grammar = Grammar::new(move |_| {
+      counter.fetch_add(1, Ordering::SeqCst);
+      elm_digit().of_many1().map(|ds| ds.len()) - end()
+    });
+    assert!(grammar.is_built());
+
+    for text in ["1", "23", "456"] {
+      let input = text.to_string();
+      assert_eq!(grammar.parse_str(&input).success(), Some(text.len()));
+    }
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+
+    let builds = Arc::new(AtomicUsize::new(0));
+    let counter = builds.clone();
+    let grammar = Grammar::new(move |_| {
+      counter.fetch_add(1, Ordering::SeqCst);
+      take_while1(|b: &u8| b.is_ascii_digit()).map(|ds: &[u8]| ds.len()) - end()
+    });
+    assert!(!grammar.is_built());
+
+    for text in ["1", "23", "456"] {
+      let input = text.to_string();
+      assert_eq!(grammar.parse_str(&input).success(), Some(text.len()));
+    }
+    assert_eq!(builds.load(Ordering::SeqCst), 4);
+  }
+
+  #[test]
+  fn test_recursive() {
+    init();
+    let builds = Arc::new(AtomicUsize::new(0));
+    let counter = builds.clone();
+    let p: Parser<u8, usize> = recursive(move |nested| {
+      counter.fetch_add(1, Ordering::SeqCst);
+      surround(elm(b'('), nested.opt(), elm(b')')).map(|depth| depth.map_or(1, |depth| depth + 1))
+    });
+    assert!(p.is_free());
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+    assert_eq!(p.parse(b"()").success(), Some(1));
+    assert_eq!(p.parse(b"((()))").success(), Some(3));
+    assert!(p.parse(b"(()").is_failure());
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+    assert_eq!(p.syntax().to_ebnf(), "test_recursive ::= \"(\" test_recursive? \")\"\n");
+
+    let p: Parser<u8, &[u8]> = recursive(|nested| (elm(b'(') + nested.opt() + elm(b')')).collect());
+    assert!(!p.is_free());
+    assert_eq!(p.parse(b"(())").success(), Some(&b"(())"[..]));
+  }
+
+  #[test]
+  fn test_custom() {
+    init();
+    #[derive(Debug, Clone, PartialEq)]
+    enum FieldError {
+      OutOfRange { field: &'static str, value: u32 },
+      Number(std::num::IntErrorKind),
+    }
+    impl FromConversionError<std::num::ParseIntError> for FieldError {
+      fn from_conversion_error(error: std::num::ParseIntError) -> Option<Self> {
+        Some(FieldError::Number(*error.kind()))
+      }
+    }
+    let digits = take_chars_while1(|c| c.is_ascii_digit());
+    let number: Parser<u8, u32, FieldError> = digits.clone().with_error_type().map_res(|s: &str| s.parse::<u32>());
+    let hour = number.clone().flat_map(|value| {
+      if value < 24 {
+        successful(value).with_error_type()
+      } else {
+        custom(FieldError::OutOfRange { field: "hour", value })
+      }
+    }) - end().with_error_type();
+
+    let error = hour.parse_str("25").failure().unwrap();
+    assert!(error.is_custom());
+    assert_eq!(
+      error.payload(),
+      Some(&FieldError::OutOfRange {
+        field: "hour",
+        value: 25
+      })
+    );
+    assert!(error.to_string().contains("OutOfRange"));
+
+    let error = (number - end().with_error_type())
+      .parse_str("99999999999")
+      .failure()
+      .unwrap();
+    assert!(error.is_conversion());
+    assert_eq!(
+      error.payload(),
+      Some(&FieldError::Number(std::num::IntErrorKind::PosOverflow))
+    );
+    let error = error.map_payload(|e| match e {
+      FieldError::Number(kind) => Some(kind),
+      FieldError::OutOfRange { .. } => None,
+    });
+    assert_eq!(error.payload(), Some(&std::num::IntErrorKind::PosOverflow));
+
+    // without a user error type, only the message of a conversion error is kept
+    let error = digits
+      .map_res(|s: &str| s.parse::<u8>())
+      .parse_str("256")
+      .failure()
+      .unwrap();
+    assert!(error.is_conversion() && error.payload().is_none());
+    assert!(error.to_string().contains("PosOverflow"));
+  }
+
+  #[test]
+  fn test_into_owned() {
+    init();
+    fn parse(text: &str) -> anyhow::Result<Vec<u8>> {
+      let input = text.to_string();
+      let digit = elm_digit().map(|b: u8| b - b'0');
+      let line = digit.of_many1().expect("digits");
+      let parser = line.of_many1_sep(elm(b'\n')).map(|lines| lines.concat()) - end();
+      Ok(parser.parse_str(&input).to_result().map_err(ParseError::into_owned)?)
+    }
+    fn assert_error<E: std::error::Error + Send + Sync + 'static>(_: &E) {}
+
+    assert_eq!(parse("12\n3").unwrap(), vec![1, 2, 3]);
+
+    let error = parse("12\n3x4").unwrap_err();
+    let error = error.downcast_ref::<OwnedParseError>().unwrap();
+    assert_error(error);
+    assert_eq!(error.offset(), Some(4));
+    assert_eq!(error.position(), Some(Position::new(4, 2, 2)));
+    assert_eq!(error.snippet(), Some("3x4"));
+    assert_eq!(error.message(), "expected `end of input`");
+    assert_eq!(
+      error.to_string(),
+      "expected `end of input` at line 2, column 2\n  | 3x4\n  |  ^"
+    );
+  }
+
+  #[cfg(feature = "sync")]
+  #[test]
+  fn test_sync() {
+    init();
+    let inputs = ["1,2", "3,4,5", "6,x"];
+    let number = elm_digit().map(|b: u8| (b - b'0') as u32).cache();
+    let p = number.of_many1_sep(elm(b',')).map(|ns| ns.iter().sum::<u32>()) - end();
+
+    let sums = std::thread::scope(|scope| {
+      let handles = inputs
+        .iter()
+        .map(|input| {
+          let p = &p;
+          scope.spawn(move || p.parse_str(input).success())
+        })
+        .collect::<Vec<_>>();
+      handles.into_iter().map(|h| h.join().unwrap()).collect::<Vec<_>>()
+    });
+
+    assert_eq!(sums, vec![Some(3), Some(12), None]);
+
+    // the parsed values are not captured, so they are not required to be Send + Sync
+    let digit = elm_digit().map(|b: u8| std::rc::Rc::new(b - b'0'));
+    let p = (digit.clone() + digit.clone().cache()).chain_left1(elm(b'+').map(|_| |a, _| a)) - end();
+    assert_eq!(p.parse_str("12+34").success().map(|(a, b)| (*a, *b)), Some((1, 2)));
+  }
 }
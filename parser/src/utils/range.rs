@@ -7,82 +7,82 @@ pub enum Bound<'a, T: 'a> {
 }
 
 pub trait RangeArgument<T> {
-  fn start(&self) -> Bound<T>;
-  fn end(&self) -> Bound<T>;
+  fn start(&self) -> Bound<'_, T>;
+  fn end(&self) -> Bound<'_, T>;
 }
 
 // ..
 impl<T> RangeArgument<T> for RangeFull {
-  fn start(&self) -> Bound<T> {
+  fn start(&self) -> Bound<'_, T> {
     Bound::Unbounded
   }
 
-  fn end(&self) -> Bound<T> {
+  fn end(&self) -> Bound<'_, T> {
     Bound::Unbounded
   }
 }
 
 // start..end
 impl<T> RangeArgument<T> for Range<T> {
-  fn start(&self) -> Bound<T> {
+  fn start(&self) -> Bound<'_, T> {
     Bound::Included(&self.start)
   }
 
-  fn end(&self) -> Bound<T> {
+  fn end(&self) -> Bound<'_, T> {
     Bound::Excluded(&self.end)
   }
 }
 
 // start..=end
 impl<T> RangeArgument<T> for RangeInclusive<T> {
-  fn start(&self) -> Bound<T> {
+  fn start(&self) -> Bound<'_, T> {
     Bound::Included(RangeInclusive::start(self))
   }
 
-  fn end(&self) -> Bound<T> {
+  fn end(&self) -> Bound<'_, T> {
     Bound::Included(RangeInclusive::end(self))
   }
 }
 
 // start..
 impl<T> RangeArgument<T> for RangeFrom<T> {
-  fn start(&self) -> Bound<T> {
+  fn start(&self) -> Bound<'_, T> {
     Bound::Included(&self.start)
   }
 
-  fn end(&self) -> Bound<T> {
+  fn end(&self) -> Bound<'_, T> {
     Bound::Unbounded
   }
 }
 
 // ..end
 impl<T> RangeArgument<T> for RangeTo<T> {
-  fn start(&self) -> Bound<T> {
+  fn start(&self) -> Bound<'_, T> {
     Bound::Unbounded
   }
 
-  fn end(&self) -> Bound<T> {
+  fn end(&self) -> Bound<'_, T> {
     Bound::Excluded(&self.end)
   }
 }
 
 // ..=end
 impl<T> RangeArgument<T> for RangeToInclusive<T> {
-  fn start(&self) -> Bound<T> {
+  fn start(&self) -> Bound<'_, T> {
     Bound::Unbounded
   }
 
-  fn end(&self) -> Bound<T> {
+  fn end(&self) -> Bound<'_, T> {
     Bound::Included(&self.end)
   }
 }
 
 impl RangeArgument<usize> for usize {
-  fn start(&self) -> Bound<usize> {
+  fn start(&self) -> Bound<'_, usize> {
     Bound::Included(self)
   }
 
-  fn end(&self) -> Bound<usize> {
+  fn end(&self) -> Bound<'_, usize> {
     Bound::Included(self)
   }
 }
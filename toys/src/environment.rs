@@ -9,10 +9,6 @@ pub struct Environment {
 }
 
 impl Environment {
-  pub fn as_bindings_mut(&mut self) -> &HashMap<String, Value> {
-    &mut self.bindings
-  }
-
   pub fn as_bindings(&self) -> &HashMap<String, Value> {
     &self.bindings
   }
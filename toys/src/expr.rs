@@ -13,9 +13,6 @@ pub enum Expr {
   Symbol(String),
   FunctionCall(String, Vec<Rc<Expr>>),
   LabelledCall(String, Vec<LabelledParameter>),
-  Identifier(String),
-  Plus(Rc<Expr>),
-  Minus(Rc<Expr>),
   Println(Rc<Expr>),
   While(Rc<Expr>, Rc<Expr>),
   If(Rc<Expr>, Rc<Expr>, Option<Rc<Expr>>),
@@ -89,54 +86,54 @@ impl Expr {
   }
 
   pub fn of_less_than(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::LessThan, lhs, rhs))
+    Self::of_binary(Operator::LessThan, lhs, rhs)
   }
 
   pub fn of_greater_than(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::GreaterThan, lhs, rhs))
+    Self::of_binary(Operator::GreaterThan, lhs, rhs)
   }
 
   pub fn of_less_or_equal(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::LessOrEqual, lhs, rhs))
+    Self::of_binary(Operator::LessOrEqual, lhs, rhs)
   }
 
   pub fn of_greater_or_equal(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::GreaterOrEqual, lhs, rhs))
+    Self::of_binary(Operator::GreaterOrEqual, lhs, rhs)
   }
 
   pub fn of_equal_equal(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::EqualEqual, lhs, rhs))
+    Self::of_binary(Operator::EqualEqual, lhs, rhs)
   }
 
   pub fn of_not_equal(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::NotEqual, lhs, rhs))
+    Self::of_binary(Operator::NotEqual, lhs, rhs)
   }
 
   pub fn of_and(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::And, lhs, rhs))
+    Self::of_binary(Operator::And, lhs, rhs)
   }
 
   pub fn of_or(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::Or, lhs, rhs))
+    Self::of_binary(Operator::Or, lhs, rhs)
   }
 
   pub fn of_add(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::Add, lhs, rhs))
+    Self::of_binary(Operator::Add, lhs, rhs)
   }
 
   pub fn of_subtract(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::Subtract, lhs, rhs))
+    Self::of_binary(Operator::Subtract, lhs, rhs)
   }
 
   pub fn of_mod(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::Mod, lhs, rhs))
+    Self::of_binary(Operator::Mod, lhs, rhs)
   }
 
   pub fn of_multiply(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::Multiply, lhs, rhs))
+    Self::of_binary(Operator::Multiply, lhs, rhs)
   }
 
   pub fn of_divide(lhs: Rc<Expr>, rhs: Rc<Expr>) -> Rc<Expr> {
-    Rc::new(Expr::Binary(Operator::Divide, lhs, rhs))
+    Self::of_binary(Operator::Divide, lhs, rhs)
   }
 }
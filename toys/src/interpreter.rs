@@ -18,15 +18,6 @@ impl Interpreter {
     }
   }
 
-  pub fn reset(&mut self) {
-    self.variable_environment = Environment::new(HashMap::new(), None);
-    self.function_environment.clear();
-  }
-
-  pub fn get_value(&self, name: &str) -> &Value {
-    self.variable_environment.as_bindings().get(name).unwrap()
-  }
-
   pub fn call_main(&mut self, expr: Rc<Expr>) -> Value {
     match &*expr {
       Expr::Program(definitions) => {
@@ -78,6 +69,7 @@ impl Interpreter {
       }
       Expr::StringLiteral(value) => Value::String(value.clone()),
       Expr::IntegerLiteral(value) => Value::Int(*value),
+      Expr::ArrayLiteral(values) => Value::Array(values.iter().map(|value| self.interpret(value.clone())).collect()),
       Expr::Parenthesized(expr) => self.interpret(expr.clone()),
       Expr::Symbol(name) => {
         let bindings_opt = self.variable_environment.find_binding(name);
@@ -144,11 +136,9 @@ impl Interpreter {
             .collect::<Vec<_>>();
           let backup = self.variable_environment.clone();
           self.variable_environment = Environment::new(HashMap::new(), Some(Rc::new(backup.clone())));
-          let mut i = 0;
-          for formal_param_name in formal_params {
+          for (formal_param_name, value) in formal_params.iter().zip(&values) {
             let mut bindings = self.variable_environment.as_bindings().clone();
-            bindings.insert(formal_param_name.clone(), values[i].clone());
-            i += 1;
+            bindings.insert(formal_param_name.clone(), value.clone());
           }
           let result = self.interpret(body.clone());
           self.variable_environment = backup.clone();
@@ -90,10 +90,6 @@ fn global_variable_definition<'a>() -> Parser<'a, char, Rc<Expr>> {
   (space() * p - space()).name("global variable definition").cache()
 }
 
-fn lines<'a>() -> Parser<'a, char, Vec<Rc<Expr>>> {
-  line().of_many1() - space() - end()
-}
-
 fn line<'a>() -> Parser<'a, char, Rc<Expr>> {
   let p = println() | lazy(r#while) | lazy(r#if) | lazy(r#for) | assignment() | expression_line() | block();
   (space() * p - space()).name("line").cache()
@@ -165,7 +161,7 @@ fn println<'a>() -> Parser<'a, char, Rc<Expr>> {
 
 fn integer<'a>() -> Parser<'a, char, Rc<Expr>> {
   let p = regex(r#"^-?\d+"#)
-    .map_res(|s| s.parse::<i64>())
+    .map_res(|s: String| s.parse::<i64>())
     .map(Expr::of_integer_literal);
   (space() * p - space()).name("integer").cache()
 }
@@ -285,7 +281,7 @@ fn string_literal<'a>() -> Parser<'a, char, Rc<Expr>> {
     .of_many1()
     .map(String::from_iter);
   let utf16_char: Parser<char, u16> = tag("\\u")
-    * elm_pred(|c: &char| c.is_digit(16))
+    * elm_pred(|c: &char| c.is_ascii_hexdigit())
       .of_count(4)
       .map(String::from_iter)
       .map_res(|digits| u16::from_str_radix(&digits, 16));
@@ -37,11 +37,4 @@ impl Value {
       _ => panic!("Value is not a boolean"),
     }
   }
-
-  pub fn as_string(&self) -> String {
-    match self {
-      Value::String(s) => s.clone(),
-      _ => panic!("Value is not a string"),
-    }
-  }
 }
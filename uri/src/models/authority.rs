@@ -2,23 +2,13 @@ use crate::models::host_name::HostName;
 use crate::models::user_info::UserInfo;
 use std::fmt::Formatter;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash, Default)]
 pub struct Authority {
   host_name: HostName,
   port: Option<u16>,
   user_info: Option<UserInfo>,
 }
 
-impl Default for Authority {
-  fn default() -> Self {
-    Authority {
-      host_name: HostName::default(),
-      port: Option::default(),
-      user_info: Option::default(),
-    }
-  }
-}
-
 impl std::fmt::Display for Authority {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     write!(
@@ -27,12 +17,12 @@ impl std::fmt::Display for Authority {
       self
         .user_info
         .iter()
-        .map(|ui| format!("{}@", ui.to_string()))
+        .map(|ui| format!("{}@", ui))
         .fold("".to_string(), |mut acc, s| {
           acc.push_str(&s);
           acc
         }),
-      self.host_name.to_string(),
+      self.host_name,
       self.port.map(|n| format!(":{}", n)).unwrap_or("".to_string()),
     )
   }
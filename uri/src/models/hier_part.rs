@@ -3,7 +3,7 @@ use crate::models::path::Path;
 
 use std::fmt::Formatter;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash, Default)]
 pub struct HierPart {
   pub(crate) authority: Option<Authority>,
   pub(crate) path: Path,
@@ -17,9 +17,9 @@ impl std::fmt::Display for HierPart {
       self
         .authority
         .as_ref()
-        .map(|e| format!("//{}", e.to_string()))
+        .map(|e| format!("//{}", e))
         .unwrap_or("".to_string()),
-      self.path.to_string()
+      self.path
     )
   }
 }
@@ -33,12 +33,3 @@ impl HierPart {
     HierPart { authority: None, path }
   }
 }
-
-impl Default for HierPart {
-  fn default() -> HierPart {
-    HierPart {
-      authority: None,
-      path: Path::default(),
-    }
-  }
-}
@@ -44,7 +44,7 @@ impl Path {
   pub fn of_rootless_from_strs(parts: &[&str]) -> Self {
     Path::RootlessPath {
       type_name: "rootless_path",
-      parts: parts.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+      parts: parts.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
     }
   }
 
@@ -58,7 +58,7 @@ impl Path {
   pub fn of_abempty_from_strs(parts: &[&str]) -> Self {
     Path::AbemptyPath {
       type_name: "abempty_path",
-      parts: parts.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+      parts: parts.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
     }
   }
 
@@ -72,7 +72,7 @@ impl Path {
   pub fn of_absolute_from_strs(parts: &[&str]) -> Self {
     Path::AbsolutePath {
       type_name: "absolute_path",
-      parts: parts.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+      parts: parts.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
     }
   }
 
@@ -86,7 +86,7 @@ impl Path {
   pub fn of_no_scheme_from_strs(parts: &[&str]) -> Self {
     Path::NoSchemePath {
       type_name: "no_scheme_path",
-      parts: parts.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+      parts: parts.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
     }
   }
 
@@ -104,12 +104,12 @@ impl Path {
   }
 
   pub fn type_name(&self) -> &'static str {
-    match self {
-      &Path::RootlessPath { type_name, .. } => type_name,
-      &Path::AbemptyPath { type_name, .. } => type_name,
-      &Path::AbsolutePath { type_name, .. } => type_name,
-      &Path::NoSchemePath { type_name, .. } => type_name,
-      &Path::EmptyPath { type_name } => type_name,
+    match *self {
+      Path::RootlessPath { type_name, .. } => type_name,
+      Path::AbemptyPath { type_name, .. } => type_name,
+      Path::AbsolutePath { type_name, .. } => type_name,
+      Path::NoSchemePath { type_name, .. } => type_name,
+      Path::EmptyPath { type_name } => type_name,
     }
   }
 
@@ -152,11 +152,8 @@ impl Path {
       Path::NoSchemePath { parts, .. } => Some(parts),
       Path::EmptyPath { .. } => None,
     };
-    match parts_opt {
-      Some(parts) => {
-        parts.push(part);
-      }
-      None => (),
+    if let Some(parts) = parts_opt {
+      parts.push(part);
     }
   }
 
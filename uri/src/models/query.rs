@@ -2,17 +2,11 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt::Formatter;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash, Default)]
 pub struct Query {
   params: Vec<(String, Option<String>)>,
 }
 
-impl Default for Query {
-  fn default() -> Self {
-    Query { params: Vec::default() }
-  }
-}
-
 impl PartialOrd for Query {
   fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
     self.to_string().partial_cmp(&other.to_string())
@@ -71,17 +65,13 @@ impl Query {
   }
 
   pub fn add_opt(&mut self, key: String, value: Option<String>) {
-    match value {
-      Some(v) => {
-        self.add(key, v);
-        ()
-      }
-      None => (),
+    if let Some(v) = value {
+      self.add(key, v);
     }
   }
 
   pub fn get_param(&self, key: String) -> Option<Vec<&String>> {
-    self.params().get(&key).map(|v| v.clone())
+    self.params().get(&key).cloned()
   }
 
   pub fn as_string(&self) -> String {
@@ -1,14 +1,8 @@
 use std::fmt::Formatter;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash, Default)]
 pub struct Scheme(String);
 
-impl Default for Scheme {
-  fn default() -> Self {
-    Scheme(String::default())
-  }
-}
-
 impl std::fmt::Display for Scheme {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     write!(f, "{}", self.0)
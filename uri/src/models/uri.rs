@@ -6,12 +6,16 @@ use crate::models::query::Query;
 use crate::models::scheme::Scheme;
 use crate::models::user_info::UserInfo;
 use crate::parsers::uri_parsers;
-use oni_comb_parser_rs::prelude::{ParseError, ParserRunner};
+use oni_comb_parser_rs::prelude::{Grammar, ParseError, ParserRunner};
 use std::fmt::Formatter;
 
 pub type Fragment = String;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+thread_local! {
+  static URI: Grammar<u8, Uri> = Grammar::new(|_| uri_parsers::uri());
+}
+
+#[derive(Debug, Clone, PartialEq, Hash, Default)]
 pub struct Uri {
   schema: Option<Scheme>,
   hier_path: Option<HierPart>,
@@ -19,17 +23,6 @@ pub struct Uri {
   fragment: Option<String>,
 }
 
-impl Default for Uri {
-  fn default() -> Self {
-    Uri {
-      schema: Option::default(),
-      hier_path: Option::default(),
-      query: Option::default(),
-      fragment: Option::default(),
-    }
-  }
-}
-
 impl std::fmt::Display for Uri {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     write!(
@@ -42,7 +35,7 @@ impl std::fmt::Display for Uri {
         .map(|hp| hp
           .authority
           .as_ref()
-          .map(|a| format!("//{}", a.to_string()))
+          .map(|a| format!("//{}", a))
           .unwrap_or("".to_string()),)
         .unwrap_or("".to_string()),
       self
@@ -50,11 +43,7 @@ impl std::fmt::Display for Uri {
         .as_ref()
         .map(|hp| hp.path.to_string())
         .unwrap_or("".to_string()),
-      self
-        .query
-        .as_ref()
-        .map(|q| format!("?{}", q.to_string()))
-        .unwrap_or("".to_string()),
+      self.query.as_ref().map(|q| format!("?{}", q)).unwrap_or("".to_string()),
       self
         .fragment
         .as_ref()
@@ -65,8 +54,8 @@ impl std::fmt::Display for Uri {
 }
 
 impl Uri {
-  pub fn parse(text: &str) -> Result<Uri, ParseError<u8>> {
-    uri_parsers::uri().parse(text.as_bytes()).to_result()
+  pub fn parse(text: &str) -> Result<Uri, ParseError<'_, u8>> {
+    URI.with(|grammar| grammar.parse(text.as_bytes()).to_result())
   }
 
   pub fn new(
@@ -1,20 +1,11 @@
 use std::fmt::Formatter;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash, Default)]
 pub struct UserInfo {
   user_name: String,
   password: Option<String>,
 }
 
-impl Default for UserInfo {
-  fn default() -> Self {
-    UserInfo {
-      user_name: String::default(),
-      password: Option::default(),
-    }
-  }
-}
-
 impl std::fmt::Display for UserInfo {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     write!(
@@ -49,6 +40,6 @@ impl UserInfo {
   }
 
   pub fn password(&self) -> Option<&str> {
-    self.password.as_ref().map(|s| s.as_str())
+    self.password.as_deref()
   }
 }
@@ -1,4 +1,5 @@
 //  authority     = [ userinfo "@" ] host [ ":" port ]
+// The port may be empty, and the empty port is the same as no port.
 
 use crate::models::authority::Authority;
 use crate::parsers::host_parsers::host;
@@ -7,7 +8,11 @@ use crate::parsers::user_info_parsers::user_info;
 use oni_comb_parser_rs::prelude::*;
 
 pub fn authority<'a>() -> Parser<'a, u8, Authority> {
-  ((user_info() - elm_ref(b'@')).opt() + host() + (elm_ref(b':') * port()).opt())
+  let port = (elm(b':') * port().opt())
+    .opt()
+    .map(Option::flatten)
+    .name("authority_port");
+  ((user_info() - elm(b'@')).opt() + host() + port)
     .map(|((user_info, host_name), port)| Authority::new(host_name, port, user_info))
 }
 
@@ -73,4 +78,17 @@ mod tests {
     });
     prop::test_with_prop(prop, 5, TEST_COUNT, RNG::new())
   }
+
+  #[test]
+  fn test_authority_empty_port() {
+    for (s, port, normal) in [
+      ("host", None, "host"),
+      ("host:", None, "host"),
+      ("host:80", Some(80), "host:80"),
+    ] {
+      let authority = (authority() - end()).parse(s.as_bytes()).success().unwrap();
+      assert_eq!(authority.port(), port, "{}", s);
+      assert_eq!(authority.to_string(), normal);
+    }
+  }
 }
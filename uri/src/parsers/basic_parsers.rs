@@ -1,55 +1,66 @@
 use oni_comb_parser_rs::prelude::*;
 
+// The parsers collect the bytes they consume instead of returning slices of the input: the outputs of a free parser do
+// not depend on the lifetime of the input, so that the URI grammar is built once.
+
 // pchar         = unreserved / pct-encoded / sub-delims / ":" / "@"
-pub(crate) fn pchar<'a>() -> Parser<'a, u8, &'a [u8]> {
-  (unreserved() | pct_encoded() | sub_delims() | elm_ref_of(b":@").collect())
-    .collect()
+pub(crate) fn pchar<'a>() -> Parser<'a, u8, Vec<u8>> {
+  (unreserved() | pct_encoded() | sub_delims() | elm_of(b":@").collect_vec())
+    .collect_vec()
     .name("pchar")
 }
 
-pub(crate) fn pchar_without_eq_amp<'a>() -> Parser<'a, u8, &'a [u8]> {
-  (unreserved() | pct_encoded() | sub_delims_without_eq_ampersand() | elm_ref_of(b":@").collect())
-    .collect()
+pub(crate) fn pchar_without_eq_amp<'a>() -> Parser<'a, u8, Vec<u8>> {
+  (unreserved() | pct_encoded() | sub_delims_without_eq_ampersand() | elm_of(b":@").collect_vec())
+    .collect_vec()
     .name("pchar")
 }
 
 //  pct-encoded   = "%" HEXDIG HEXDIG
-pub(crate) fn pct_encoded<'a>() -> Parser<'a, u8, &'a [u8]> {
-  (elm_ref(b'%') + elm_hex_digit_ref() + elm_hex_digit_ref())
-    .collect()
+pub(crate) fn pct_encoded<'a>() -> Parser<'a, u8, Vec<u8>> {
+  (elm(b'%') + elm_hex_digit() + elm_hex_digit())
+    .collect_vec()
     .name("pct-encoded")
 }
 
 //  unreserved    = ALPHA / DIGIT / "-" / "." / "_" / "~"
-pub(crate) fn unreserved<'a>() -> Parser<'a, u8, &'a [u8]> {
-  (elm_alpha_ref() | elm_digit_ref() | elm_ref_of(b"-._~"))
-    .collect()
+pub(crate) fn unreserved<'a>() -> Parser<'a, u8, Vec<u8>> {
+  (elm_alpha() | elm_digit() | elm_of(b"-._~"))
+    .collect_vec()
     .name("unreserved")
 }
 
 //  reserved      = gen-delims / sub-delims
-pub(crate) fn reserved<'a>() -> Parser<'a, u8, &'a [u8]> {
+#[cfg(test)]
+pub(crate) fn reserved<'a>() -> Parser<'a, u8, Vec<u8>> {
   (gen_delims() | sub_delims()).name("reserved")
 }
 
 // gen-delims    = ":" / "/" / "?" / "#" / "[" / "]" / "@"
-pub(crate) fn gen_delims<'a>() -> Parser<'a, u8, &'a [u8]> {
-  elm_ref_of(b":/?#[]@").name("gen-delims").collect()
+#[cfg(test)]
+pub(crate) fn gen_delims<'a>() -> Parser<'a, u8, Vec<u8>> {
+  elm_of(b":/?#[]@").name("gen-delims").collect_vec()
 }
 
 // sub-delims    = "!" / "$" / "&" / "'" / "(" / ")" / "*" / "+" / "," / ";" / "="
-pub(crate) fn sub_delims<'a>() -> Parser<'a, u8, &'a [u8]> {
-  elm_ref_of(b"!$&'()*+,;=").name("sub-delims").collect()
+pub(crate) fn sub_delims<'a>() -> Parser<'a, u8, Vec<u8>> {
+  elm_of(b"!$&'()*+,;=").name("sub-delims").collect_vec()
 }
 
-pub(crate) fn sub_delims_without_eq_ampersand<'a>() -> Parser<'a, u8, &'a [u8]> {
-  elm_ref_of(b"!$'()*+,;").name("sub-delims").collect()
+pub(crate) fn sub_delims_without_eq_ampersand<'a>() -> Parser<'a, u8, Vec<u8>> {
+  elm_of(b"!$'()*+,;").name("sub-delims").collect_vec()
 }
 
 #[cfg(test)]
 pub mod gens {
+  use oni_comb_parser_rs::prelude::*;
   use prop_check_rs::gen::{Gen, Gens};
 
+  // An input derived from the grammar for each seed.
+  pub fn generator_gen(generator: Generator<'static, u8>) -> Gen<String> {
+    Gens::one_u64().map(move |seed| String::from_utf8(generator.generate(seed).unwrap()).unwrap())
+  }
+
   pub fn to_option(gen: Gen<String>) -> Gen<Option<String>> {
     Gens::one_bool().flat_map(move |b| {
       if b {
@@ -78,30 +89,8 @@ pub mod gens {
     Gens::one_of_values(min..=max)
   }
 
-  pub enum HexDigitMode {
-    All,
-    Lower,
-    Upper,
-  }
-
-  pub fn hex_digit_gen(mode: HexDigitMode) -> Gen<char> {
-    Gens::frequency([
-      (1, digit_gen('0', '9')),
-      (
-        1,
-        match mode {
-          HexDigitMode::All | HexDigitMode::Upper => Gens::choose('A', 'F'),
-          _ => digit_gen('0', '9'),
-        },
-      ),
-      (
-        1,
-        match mode {
-          HexDigitMode::All | HexDigitMode::Lower => Gens::choose('a', 'f'),
-          _ => digit_gen('0', '9'),
-        },
-      ),
-    ])
+  pub fn hex_digit_gen() -> Gen<char> {
+    Gens::frequency([(2, digit_gen('0', '9')), (1, Gens::choose('a', 'f'))])
   }
 
   pub fn repeat_gen_of_char(len: u8, g: Gen<char>) -> Gen<String> {
@@ -157,7 +146,7 @@ pub mod gens {
   }
 
   pub fn pct_encoded_gen() -> Gen<String> {
-    Gens::list_of_n(2, hex_digit_gen(HexDigitMode::Lower)).map(|cl| {
+    Gens::list_of_n(2, hex_digit_gen()).map(|cl| {
       let s = cl.into_iter().collect::<String>();
       format!("%{}", s)
     })
@@ -224,7 +213,7 @@ mod tests {
         .collect()
         .map(|e| e.to_vec())
         .map_res(String::from_utf8)
-        .parse(&input)
+        .parse(input)
         .to_result();
       assert_eq!(result.unwrap(), s);
       true
@@ -3,26 +3,21 @@ use oni_comb_parser_rs::prelude::*;
 
 // fragment      = *( pchar / "/" / "?" )
 pub fn fragment<'a>() -> Parser<'a, u8, String> {
-  (pchar() | elm_ref_of(b"/?").collect())
+  (pchar() | elm_of(b"/?").collect_vec())
     .of_many0()
-    .collect()
-    .map(|e| e.to_vec())
+    .collect_vec()
     .map_res(String::from_utf8)
     .name("fragment")
 }
 
 #[cfg(test)]
 pub mod gens {
-  use crate::parsers::basic_parsers::gens::{pchar_gen, repeat_gen_of_string};
-  use prop_check_rs::gen::{Gen, Gens};
+  use super::*;
+  use crate::parsers::basic_parsers::gens::generator_gen;
+  use prop_check_rs::gen::Gen;
 
   pub fn fragment_gen() -> Gen<String> {
-    repeat_gen_of_string(1, u8::MAX - 1, {
-      Gens::frequency([
-        (1, pchar_gen(1, 1)),
-        (1, Gens::one_of_values(['/', '?']).map(|c| c.into())),
-      ])
-    })
+    generator_gen(Generator::new(fragment().syntax()).with_max_repeat(u8::MAX as usize - 1))
   }
 }
 
@@ -1,6 +1,6 @@
 use crate::models::hier_part::HierPart;
 use crate::parsers::authority_parsers::authority;
-use crate::parsers::path_parsers::{path_abempty, path_rootless};
+use crate::parsers::path_parsers::{path_abempty, path_absolute, path_rootless};
 use oni_comb_parser_rs::prelude::*;
 
 //  hier-part     = "//" authority path-abempty
@@ -9,31 +9,33 @@ use oni_comb_parser_rs::prelude::*;
 //                / path-empty
 pub fn hier_part<'a>() -> Parser<'a, u8, Option<HierPart>> {
   let p1 = (seq(b"//") * authority() + path_abempty(false)).map(|(a, b)| HierPart::new(Some(a), b));
-  let p2 = (path_abempty(true).attempt() | path_rootless()).map(|e| HierPart::of_path(e));
+  let p2 = (path_absolute().attempt() | path_rootless()).map(HierPart::of_path);
   (p1.attempt() | p2).opt()
 }
 
 #[cfg(test)]
 pub mod gens {
-  use crate::parsers::authority_parsers::gens::authority_gen;
-  use prop_check_rs::gen::{Gen, Gens};
+  use prop_check_rs::gen::Gen;
 
-  use crate::parsers::path_parsers::gens::{path_abempty_gen, path_str_without_abempty_gen, Pair};
+  use super::*;
+  use crate::parsers::basic_parsers::gens::generator_gen;
+  use crate::parsers::port_parsers::port;
+
+  // The ports and the IPv6 addresses are printed in their normal forms, so they are generated in them: the ports
+  // without leading zeros and never empty, and the IPv6 addresses of eight non-zero groups in lowercase.
+  pub fn hier_part_generator<'a>(syntax: Syntax<'a, u8>) -> Generator<'a, u8> {
+    let authority_port = (elm(b':') + port()).opt();
+    let port = elm_digit_1_9() + elm_digit().of_many_n_m(0, 3);
+    let h16 = elm_of(b"123456789abcdef") + elm_of(b"0123456789abcdef").of_many_n_m(0, 3);
+    let ip_v6_address = h16.clone() + (elm(b':') + h16).of_many_n_m(7, 7);
+    Generator::new(syntax)
+      .with_rule("authority_port", authority_port.syntax())
+      .with_rule("port", port.syntax())
+      .with_rule("ip_v6_address", ip_v6_address.syntax())
+  }
 
-  pub fn hier_part_gen() -> Gen<Pair<String, Option<bool>>> {
-    let gen1 = {
-      authority_gen().flat_map(move |authority| {
-        path_abempty_gen().map(move |path_abempty| format!("//{}{}", authority, path_abempty))
-      })
-    };
-    let gen2 = { path_str_without_abempty_gen().map(|Pair(p1, p2)| Pair(p2, Some(p1 == "empty_path".to_string()))) };
-    Gens::one_bool().flat_map(move |b| {
-      if b {
-        gen1.clone().map(|s| Pair(s, None))
-      } else {
-        gen2.clone()
-      }
-    })
+  pub fn hier_part_gen() -> Gen<String> {
+    generator_gen(hier_part_generator(hier_part().syntax()))
   }
 }
 
@@ -41,7 +43,6 @@ pub mod gens {
 mod tests {
   use std::env;
 
-  use crate::parsers::path_parsers::gens::Pair;
   use anyhow::Result;
   use prop_check_rs::prop;
   use prop_check_rs::prop::TestCases;
@@ -49,6 +50,7 @@ mod tests {
 
   use super::gens::*;
   use super::*;
+  use crate::models::path::Path;
 
   const TEST_COUNT: TestCases = 100;
 
@@ -61,7 +63,7 @@ mod tests {
   #[test]
   fn test_hier_part() -> Result<()> {
     let mut counter = 0;
-    let prop = prop::for_all_gen(hier_part_gen(), move |Pair(s, _b)| {
+    let prop = prop::for_all_gen(hier_part_gen(), move |s| {
       counter += 1;
       log::debug!("{:>03}, hier_part:string = {}", counter, s);
       let input = s.as_bytes();
@@ -73,4 +75,13 @@ mod tests {
     });
     prop::test_with_prop(prop, 5, TEST_COUNT, RNG::new())
   }
+
+  #[test]
+  fn test_hier_part_path_absolute() {
+    for s in ["/", "/a", "/a//b"] {
+      let hier_part_value = (hier_part() - end()).parse(s.as_bytes()).success().flatten().unwrap();
+      assert!(matches!(hier_part_value.path, Path::AbsolutePath { .. }), "{}", s);
+      assert_eq!(hier_part_value.to_string(), s);
+    }
+  }
 }
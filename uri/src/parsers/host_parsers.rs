@@ -13,19 +13,18 @@ pub fn host<'a>() -> Parser<'a, u8, HostName> {
 
 // IP-literal    = "[" ( IPv6address / IPvFuture  ) "]"
 pub fn ip_literal<'a>() -> Parser<'a, u8, IpLiteral> {
-  (elm_ref(b'[') * (ip_v6_address().map(IpLiteral::Ipv6Address).attempt() | ip_v_future().map(IpLiteral::IpvFuture))
-    - elm_ref(b']'))
+  (elm(b'[') * (ip_v6_address().map(IpLiteral::Ipv6Address).attempt() | ip_v_future().map(IpLiteral::IpvFuture))
+    - elm(b']'))
   .name("ip-literal")
 }
 
 // "v" 1*HEXDIG "." 1*( unreserved / sub-delims / ":" )
 pub fn ip_v_future<'a>() -> Parser<'a, u8, String> {
-  (elm_ref(b'v')
+  (elm(b'v')
     + elm_hex_digit().of_many1()
     + elm(b'.')
-    + (unreserved() | sub_delims() | elm_ref(b':').collect()).of_many1())
-  .collect()
-  .map(|e| e.to_vec())
+    + (unreserved() | sub_delims() | elm(b':').collect_vec()).of_many1())
+  .collect_vec()
   .map_res(String::from_utf8)
   .name("ipv-future")
 }
@@ -34,8 +33,7 @@ pub fn ip_v_future<'a>() -> Parser<'a, u8, String> {
 pub fn reg_name<'a>() -> Parser<'a, u8, HostName> {
   (unreserved().attempt() | pct_encoded().attempt() | sub_delims())
     .of_many1()
-    .collect()
-    .map(|e| e.to_vec())
+    .collect_vec()
     .map_res(String::from_utf8)
     .map(HostName::RegName)
     .name("reg-name")
@@ -60,7 +58,7 @@ pub mod gens {
   }
 
   pub fn ip_v_future_gen() -> Gen<String> {
-    let a = repeat_gen_of_char(5, hex_digit_gen(HexDigitMode::Lower));
+    let a = repeat_gen_of_char(5, hex_digit_gen());
     let b = {
       repeat_gen_of_char(5, {
         Gens::frequency([
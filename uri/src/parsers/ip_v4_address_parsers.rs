@@ -15,15 +15,14 @@ pub fn ip_v4_address<'a>() -> Parser<'a, u8, Ipv4Addr> {
 //                / "2" %x30-34 DIGIT     ; 200-249
 //                / "25" %x30-35          ; 250-255
 pub fn dec_octet<'a>() -> Parser<'a, u8, u8> {
-  let p1 = elm_digit().collect();
-  let p2 = (elm_digit_1_9() + elm_digit()).collect();
-  let p3 = (elm(b'1') + elm_digit() + elm_digit()).collect();
-  let p4 = (elm(b'2') + elm_in(b'0', b'4') + elm_digit()).collect();
-  let p5 = (elm(b'2') + elm(b'5') + elm_in(b'0', b'5')).collect();
+  let p1 = elm_digit().collect_vec();
+  let p2 = (elm_digit_1_9() + elm_digit()).collect_vec();
+  let p3 = (elm(b'1') + elm_digit() + elm_digit()).collect_vec();
+  let p4 = (elm(b'2') + elm_in(b'0', b'4') + elm_digit()).collect_vec();
+  let p5 = (elm(b'2') + elm(b'5') + elm_in(b'0', b'5')).collect_vec();
 
   (p5.attempt() | p4.attempt() | p3.attempt() | p2.attempt() | p1)
-    .collect()
-    .map(|e| e.to_vec())
+    .collect_vec()
     .map_res(String::from_utf8)
     .map_res(|s| s.parse::<u8>())
     .name("dec-octet")
@@ -70,7 +70,7 @@ fn ip_v6_address3<'a>() -> Parser<'a, u8, Ipv6Addr> {
   })
 }
 
-fn ip_v6_address_p1<'a>(n: usize) -> Parser<'a, u8, &'a [u8]> {
+fn ip_v6_address_p1<'a>(n: usize) -> Parser<'a, u8, Vec<u8>> {
   ((h16() + (elm(b':') * h16()).of_many_n_m(0, n)).map(|(h, vec)| {
     vec.into_iter().fold(vec![h], |mut acc, e| {
       acc.push(e);
@@ -78,12 +78,12 @@ fn ip_v6_address_p1<'a>(n: usize) -> Parser<'a, u8, &'a [u8]> {
     });
   }))
   .opt()
-  .collect()
+  .collect_vec()
 }
 
-fn ip_v6_address_p2<'a>(n: usize, m: usize) -> Parser<'a, u8, &'a [u8]> {
-  let p2 = ((h16() + elm(b':')).of_count(m) + ls32()).collect();
-  (ip_v6_address_p1(n) + seq(b"::") + p2).collect()
+fn ip_v6_address_p2<'a>(n: usize, m: usize) -> Parser<'a, u8, Vec<u8>> {
+  let p2 = ((h16() + elm(b':')).of_count(m) + ls32()).collect_vec();
+  (ip_v6_address_p1(n) + seq(b"::") + p2).collect_vec()
 }
 
 //                / [ *1( h16 ":" ) h16 ] "::" 3( h16 ":" ) ls32
@@ -113,8 +113,7 @@ fn ip_v6_address6<'a>() -> Parser<'a, u8, Ipv6Addr> {
 //                / [ *4( h16 ":" ) h16 ] "::"              ls32
 fn ip_v6_address7<'a>() -> Parser<'a, u8, Ipv6Addr> {
   (ip_v6_address_p1(4) + seq(b"::") + ls32())
-    .collect()
-    .map(|e| e.to_vec())
+    .collect_vec()
     .map_res(String::from_utf8)
     .map_res(|s| s.parse::<Ipv6Addr>())
 }
@@ -122,8 +121,7 @@ fn ip_v6_address7<'a>() -> Parser<'a, u8, Ipv6Addr> {
 //                / [ *5( h16 ":" ) h16 ] "::"              h16
 fn ip_v6_address8<'a>() -> Parser<'a, u8, Ipv6Addr> {
   (ip_v6_address_p1(5) + seq(b"::") + h16())
-    .collect()
-    .map(|e| e.to_vec())
+    .collect_vec()
     .map_res(String::from_utf8)
     .map_res(|s| s.parse::<Ipv6Addr>())
 }
@@ -131,8 +129,7 @@ fn ip_v6_address8<'a>() -> Parser<'a, u8, Ipv6Addr> {
 //                / [ *6( h16 ":" ) h16 ] "::"
 fn ip_v6_address9<'a>() -> Parser<'a, u8, Ipv6Addr> {
   (ip_v6_address_p1(5) + seq(b"::"))
-    .collect()
-    .map(|e| e.to_vec())
+    .collect_vec()
     .map_res(String::from_utf8)
     .map_res(|s| s.parse::<Ipv6Addr>())
 }
@@ -150,7 +147,7 @@ pub fn ip_v6_address<'a>() -> Parser<'a, u8, Ipv6Addr> {
   .name("ip_v6_address")
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 struct H16(u16);
 
 impl H16 {
@@ -169,8 +166,7 @@ impl std::fmt::Display for H16 {
 fn h16<'a>() -> Parser<'a, u8, H16> {
   elm_hex_digit()
     .of_many_n_m(1, 4)
-    .collect()
-    .map(|e| e.to_vec())
+    .collect_vec()
     .map_res(String::from_utf8)
     .map_res(|s| u16::from_str_radix(&s, 16))
     .map(H16)
@@ -194,7 +190,7 @@ impl std::fmt::Display for LS32 {
 
 //  ls32          = ( h16 ":" h16 ) / IPv4address
 fn ls32<'a>() -> Parser<'a, u8, LS32> {
-  (h16() - elm(b':') + h16()).map(|(a, b)| LS32::Ls32(a, b)).attempt() | ip_v4_address().map(|a| LS32::Ipv4Address(a))
+  (h16() - elm(b':') + h16()).map(|(a, b)| LS32::Ls32(a, b)).attempt() | ip_v4_address().map(LS32::Ipv4Address)
 }
 
 #[cfg(test)]
@@ -206,7 +202,7 @@ pub mod gens {
 
   pub fn h16_gen() -> Gen<String> {
     Gens::choose_u8(1, 4)
-      .flat_map(|n| repeat_gen_of_char(n, hex_digit_gen(HexDigitMode::Lower)))
+      .flat_map(|n| repeat_gen_of_char(n, hex_digit_gen()))
       .map(|s| u16::from_str_radix(&s, 16).unwrap())
       .map(|n| format!("{:x}", n))
   }
@@ -386,8 +382,8 @@ mod tests {
 
   fn new_rng() -> RNG {
     let mut rand = rand::thread_rng();
-    let rng = RNG::new().with_seed(rand.gen());
-    rng
+
+    RNG::new().with_seed(rand.gen())
   }
 
   #[test]
@@ -7,6 +7,7 @@ use oni_comb_parser_rs::prelude::*;
 //                / path-noscheme   ; begins with a non-colon segment
 //                / path-rootless   ; begins with a segment
 //                / path-empty      ; zero characters
+#[cfg(test)]
 pub fn path<'a>() -> Parser<'a, u8, Option<Path>> {
   (path_rootless().attempt() | path_abempty(true).attempt() | path_absolute().attempt() | path_noscheme())
     .opt()
@@ -16,8 +17,7 @@ pub fn path<'a>() -> Parser<'a, u8, Option<Path>> {
 //  path-abempty  = *( "/" segment )
 pub fn path_abempty<'a>(required: bool) -> Parser<'a, u8, Path> {
   let n = if required { 1 } else { 0 };
-  ((elm(b'/') + segment()).collect())
-    .map(|e| e.to_vec())
+  ((elm(b'/') + segment()).collect_vec())
     .map_res(String::from_utf8)
     .repeat(n..)
     .map(|e| Path::of_abempty_from_strings(&e))
@@ -26,84 +26,71 @@ pub fn path_abempty<'a>(required: bool) -> Parser<'a, u8, Path> {
 
 //  path-absolute = "/" [ segment-nz *( "/" segment ) ]
 pub fn path_absolute<'a>() -> Parser<'a, u8, Path> {
-  let p = (seqment_nz() + ((elm(b'/') + segment()).collect()).of_many0())
+  let p = (seqment_nz() + ((elm(b'/') + segment()).collect_vec()).of_many0())
     .map(|(a, b)| {
       let mut l = vec![a];
-      l.extend_from_slice(&b);
+      l.extend(b);
       l
     })
     .opt();
-  (elm(b'/').collect() + p)
+  (elm(b'/').collect_vec() + p)
     .map(|(a, b_opt)| match b_opt {
       None => vec![a],
       Some(b) => {
         let mut l = vec![a];
-        l.extend_from_slice(&b);
+        l.extend(b);
         l
       }
     })
-    .map(|e| {
-      e.into_iter()
-        .map(|e| e.to_vec())
-        .map(|v| String::from_utf8(v).unwrap())
-        .collect::<Vec<_>>()
-    })
+    .map(|e| e.into_iter().map(|v| String::from_utf8(v).unwrap()).collect::<Vec<_>>())
     .map(|e| Path::of_absolute_from_strings(&e))
     .name("path-absolute")
 }
 
 //  path-rootless = segment-nz *( "/" segment )
 pub fn path_rootless<'a>() -> Parser<'a, u8, Path> {
-  (seqment_nz() + ((elm(b'/') + segment()).collect()).of_many0())
+  (seqment_nz() + ((elm(b'/') + segment()).collect_vec()).of_many0())
     .map(|(a, b)| {
       let mut l = vec![a];
-      l.extend_from_slice(&b);
+      l.extend(b);
       l
     })
-    .map(|e| {
-      e.into_iter()
-        .map(|e| e.to_vec())
-        .map(|v| String::from_utf8(v).unwrap())
-        .collect::<Vec<_>>()
-    })
+    .map(|e| e.into_iter().map(|v| String::from_utf8(v).unwrap()).collect::<Vec<_>>())
     .map(|e| Path::of_rootless_from_strings(&e))
     .name("path-rootless")
 }
 
 //  path-noscheme = segment-nz-nc *( "/" segment )
+#[cfg(test)]
 pub fn path_noscheme<'a>() -> Parser<'a, u8, Path> {
-  (seqment_nz_nc() + ((elm(b'/') + segment()).collect()).of_many0())
+  (seqment_nz_nc() + ((elm(b'/') + segment()).collect_vec()).of_many0())
     .map(|(a, b)| {
       let mut l = vec![a];
-      l.extend_from_slice(&b);
+      l.extend(b);
       l
     })
-    .map(|e| {
-      e.into_iter()
-        .map(|e| e.to_vec())
-        .map(|v| String::from_utf8(v).unwrap())
-        .collect::<Vec<_>>()
-    })
+    .map(|e| e.into_iter().map(|v| String::from_utf8(v).unwrap()).collect::<Vec<_>>())
     .map(|e| Path::of_rootless_from_strings(&e))
     .name("path-noscheme")
 }
 
 // segment       = *pchar
-fn segment<'a>() -> Parser<'a, u8, &'a [u8]> {
-  pchar().of_many0().collect().name("segment")
+fn segment<'a>() -> Parser<'a, u8, Vec<u8>> {
+  pchar().of_many0().collect_vec().name("segment")
 }
 
 // segment-nz    = 1*pchar
-fn seqment_nz<'a>() -> Parser<'a, u8, &'a [u8]> {
-  pchar().of_many1().collect().name("segment-nz")
+fn seqment_nz<'a>() -> Parser<'a, u8, Vec<u8>> {
+  pchar().of_many1().collect_vec().name("segment-nz")
 }
 
 // segment-nz-nc = 1*( unreserved / pct-encoded / sub-delims / "@" )
 // ; non-zero-length segment without any colon ":"
-fn seqment_nz_nc<'a>() -> Parser<'a, u8, &'a [u8]> {
-  (unreserved() | pct_encoded() | sub_delims() | elm(b'@').collect())
+#[cfg(test)]
+fn seqment_nz_nc<'a>() -> Parser<'a, u8, Vec<u8>> {
+  (unreserved() | pct_encoded() | sub_delims() | elm(b'@').collect_vec())
     .of_many1()
-    .collect()
+    .collect_vec()
     .name("segment-nz-nc")
 }
 
@@ -181,14 +168,6 @@ pub mod gens {
       (1, Gens::pure(Pair("empty_path".to_string(), "".to_string()))),
     ])
   }
-
-  pub fn path_str_without_abempty_gen() -> Gen<Pair<String, String>> {
-    Gens::frequency([
-      (1, path_absolute_gen().map(|s| Pair("absolute_path".to_string(), s))),
-      (1, path_rootless_gen().map(|s| Pair("rootless_path".to_string(), s))),
-      (1, Gens::pure(Pair("empty_path".to_string(), "".to_string()))),
-    ])
-  }
 }
 
 #[cfg(test)]
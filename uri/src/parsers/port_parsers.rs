@@ -1,8 +1,24 @@
 use oni_comb_parser_rs::prelude::*;
 
+//  port          = *DIGIT
+// The empty port is left to the authority, and the value is limited to u16 after the leading zeros:
+//                  "6553" %x30-35        ; 65530-65535
+//                / "655" %x30-32 DIGIT   ; 65500-65529
+//                / "65" %x30-34 2DIGIT   ; 65000-65499
+//                / "6" %x30-34 3DIGIT    ; 60000-64999
+//                / %x31-35 4DIGIT        ; 10000-59999
+//                / 1*4DIGIT              ; 0-9999
 pub fn port<'a>() -> Parser<'a, u8, u16> {
-  elm_digit()
-    .of_many0()
+  let p1 = (seq(b"6553") + elm_in(b'0', b'5')).collect_vec();
+  let p2 = (seq(b"655") + elm_in(b'0', b'2') + elm_digit()).collect_vec();
+  let p3 = (seq(b"65") + elm_in(b'0', b'4') + elm_digit().of_count(2)).collect_vec();
+  let p4 = (elm(b'6') + elm_in(b'0', b'4') + elm_digit().of_count(3)).collect_vec();
+  let p5 = (elm_in(b'1', b'5') + elm_digit().of_count(4)).collect_vec();
+  let p6 = elm_digit().of_many_n_m(1, 4).collect_vec();
+
+  let value = p1.attempt() | p2.attempt() | p3.attempt() | p4.attempt() | p5.attempt() | p6;
+
+  ((elm(b'0').of_many1() * value.clone().opt()).collect_vec() | value)
     .map_res(String::from_utf8)
     .map_res(|s| s.parse::<u16>())
     .name("port")
@@ -51,4 +67,15 @@ mod tests {
     });
     prop::test_with_prop(prop, 5, TEST_COUNT, RNG::new())
   }
+
+  #[test]
+  fn test_port_range() {
+    init();
+    for (s, n) in [("0", 0), ("000", 0), ("0080", 80), ("65535", 65535), ("064999", 64999)] {
+      assert_eq!((port() - end()).parse(s.as_bytes()).success(), Some(n), "{}", s);
+    }
+    for s in ["", "65536", "70000", "655350"] {
+      assert!((port() - end()).parse(s.as_bytes()).is_failure(), "{}", s);
+    }
+  }
 }
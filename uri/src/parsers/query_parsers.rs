@@ -5,13 +5,12 @@ use oni_comb_parser_rs::prelude::*;
 //  query         = *( pchar / "/" / "?" )
 pub fn query<'a>() -> Parser<'a, u8, Query> {
   let code_point = || {
-    (pchar_without_eq_amp() | elm_of(b"/?").collect())
+    (pchar_without_eq_amp() | elm_of(b"/?").collect_vec())
       .of_many0()
-      .collect()
-      .map(|e| e.to_vec())
+      .collect_vec()
       .map_res(String::from_utf8)
   };
-  let key_values = || (code_point() + (elm(b'=') * code_point()).opt());
+  let key_values = || code_point() + (elm(b'=') * code_point()).opt();
   (key_values() + (elm(b'&') * key_values()).of_many0()).map(|(a, b)| {
     let mut m = vec![a];
     m.extend(b);
@@ -23,16 +22,13 @@ pub fn query<'a>() -> Parser<'a, u8, Query> {
 pub mod gens {
   use prop_check_rs::gen::{Gen, Gens};
 
+  use super::*;
   use crate::parsers::basic_parsers::gens::*;
 
   fn sub_delims_without_gen_of_char() -> Gen<char> {
     Gens::one_of_values(['!', '$', '\'', '(', ')', '*', '+', ',', ';'])
   }
 
-  fn sub_delims_without_gen(len: u8) -> Gen<String> {
-    repeat_gen_of_char(len, sub_delims_without_gen_of_char())
-  }
-
   pub fn pchar_without_eq_amp_gen(min: u8, max: u8) -> Gen<String> {
     repeat_gen_of_string(min, max, {
       Gens::frequency([
@@ -45,15 +41,7 @@ pub mod gens {
   }
 
   pub fn query_gen() -> Gen<String> {
-    Gens::list_of_n(3, {
-      pchar_without_eq_amp_gen(1, 10).flat_map(|key| {
-        Gens::list_of_n(2, pchar_without_eq_amp_gen(1, 10)).map(move |vl| {
-          let kvl = vl.into_iter().map(|v| format!("{}={}", key, v)).collect::<Vec<_>>();
-          kvl.join("&")
-        })
-      })
-    })
-    .map(|v| v.join("&"))
+    generator_gen(Generator::new(query().syntax()).with_max_repeat(10))
   }
 }
 
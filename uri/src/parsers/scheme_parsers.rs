@@ -4,30 +4,20 @@ use crate::models::scheme::Scheme;
 use oni_comb_parser_rs::prelude::*;
 
 pub fn scheme<'a>() -> Parser<'a, u8, Scheme> {
-  ((elm_alpha_ref() + (elm_alpha_ref() | elm_digit_ref() | elm_ref_of(b"+-.")).of_many0()).collect())
-    .map(|e| e.to_vec())
+  ((elm_alpha() + (elm_alpha() | elm_digit() | elm_of(b"+-.")).of_many0()).collect_vec())
     .map_res(String::from_utf8)
     .map(Scheme::new)
 }
 
 #[cfg(test)]
 pub mod gens {
-  use prop_check_rs::gen::{Gen, Gens};
+  use prop_check_rs::gen::Gen;
 
+  use super::*;
   use crate::parsers::basic_parsers::gens::*;
 
   pub fn scheme_gen() -> Gen<String> {
-    repeat_gen_of_char(
-      5,
-      Gens::frequency([
-        (1, alpha_char_gen()),
-        (1, digit_gen('0', '9')),
-        (1, Gens::pure('+')),
-        (1, Gens::pure('-')),
-        (1, Gens::pure('.')),
-      ]),
-    )
-    .flat_map(|s| alpha_char_gen().map(move |c| format!("{}{}", c, s)))
+    generator_gen(Generator::new(scheme().syntax()))
   }
 }
 
@@ -5,11 +5,6 @@ use crate::parsers::query_parsers::query;
 use crate::parsers::scheme_parsers::scheme;
 use oni_comb_parser_rs::prelude::*;
 
-//  absolute-URI  = scheme ":" hier-part [ "?" query ]
-pub fn absolute_uri<'a>() -> Parser<'a, u8, Uri> {
-  ((scheme().opt() - elm(b':')) + hier_part() + (elm(b'?') * query()).opt()).map(|((a, b), c)| Uri::new(a, b, c, None))
-}
-
 // URI = scheme ":" hier-part [ "?" query ] [ "#" fragment ]
 pub fn uri<'a>() -> Parser<'a, u8, Uri> {
   ((scheme().opt() - elm(b':')) + hier_part() + (elm(b'?') * query()).opt() + (elm(b'#') * fragment()).opt())
@@ -18,34 +13,14 @@ pub fn uri<'a>() -> Parser<'a, u8, Uri> {
 
 #[cfg(test)]
 pub mod gens {
-  use crate::parsers::fragment_parsers::gens::fragment_gen;
-  use crate::parsers::hier_part_parsers::gens::hier_part_gen;
-  use crate::parsers::path_parsers::gens::Pair;
-  use crate::parsers::query_parsers::gens::query_gen;
-  use crate::parsers::scheme_parsers::gens::scheme_gen;
-  use prop_check_rs::gen::{Gen, Gens};
+  use super::*;
+  use crate::parsers::basic_parsers::gens::generator_gen;
+  use crate::parsers::hier_part_parsers::gens::hier_part_generator;
+  use prop_check_rs::gen::Gen;
 
   // URI = scheme ":" hier-part [ "?" query ] [ "#" fragment ]
   pub fn uri_gen() -> Gen<String> {
-    scheme_gen().flat_map(|scheme| {
-      let base_gen =
-        hier_part_gen().map(move |Pair(hier_part, is_empty)| (format!("{}:{}", scheme, hier_part), is_empty));
-      let query_gen = base_gen.flat_map(|(s, is_empty_opt)| {
-        if is_empty_opt.unwrap_or(false) {
-          Gens::pure((s.clone(), is_empty_opt))
-        } else {
-          query_gen().map(move |q| (format!("{}?{}", s, q), is_empty_opt))
-        }
-      });
-      let fragment_gen = query_gen.flat_map(|(s, is_empty_opt)| {
-        if is_empty_opt.unwrap_or(false) {
-          Gens::pure(s.clone())
-        } else {
-          fragment_gen().map(move |f| format!("{}#{}", s, f))
-        }
-      });
-      fragment_gen
-    })
+    generator_gen(hier_part_generator(uri().syntax()))
   }
 }
 
@@ -69,6 +44,11 @@ mod tests {
     let _ = env_logger::builder().is_test(true).try_init();
   }
 
+  #[test]
+  fn test_uri_is_free() {
+    assert!(uri().is_free());
+  }
+
   #[test]
   fn test_uri() -> Result<()> {
     let mut counter = 0;
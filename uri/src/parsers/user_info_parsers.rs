@@ -4,11 +4,11 @@ use oni_comb_parser_rs::prelude::*;
 
 //  userinfo      = *( unreserved / pct-encoded / sub-delims / ":" )
 pub fn user_info<'a>() -> Parser<'a, u8, UserInfo> {
-  let p = || (unreserved().attempt() | pct_encoded().attempt() | sub_delims());
-  (p().of_many0().collect().map(|e| e.to_vec()).map_res(String::from_utf8)
-    + (elm(b':') * p().of_many1().collect().map(|e| e.to_vec()).map_res(String::from_utf8)).opt())
-  .map(|(user_name, password)| UserInfo::new(user_name, password))
-  .name("user_info")
+  let p = || unreserved().attempt() | pct_encoded().attempt() | sub_delims();
+  let password = (p() | elm(b':').collect_vec()).of_many0().collect_vec();
+  (p().of_many0().collect_vec().map_res(String::from_utf8) + (elm(b':') * password.map_res(String::from_utf8)).opt())
+    .map(|(user_name, password)| UserInfo::new(user_name, password))
+    .name("user_info")
 }
 
 #[cfg(test)]
@@ -30,7 +30,7 @@ pub mod gens {
           .clone()
           .flat_map(move |s1| g.clone().map(move |s2| format!("{}:{}", s1, s2)))
       } else {
-        gen.clone().map(|s| format!("{}", s))
+        gen.clone().map(|s| s.to_string())
       }
     })
   }
@@ -71,4 +71,18 @@ mod tests {
     });
     prop::test_with_prop(prop, 5, TEST_COUNT, RNG::new())
   }
+
+  #[test]
+  fn test_user_info_password() {
+    for (s, password) in [
+      ("user", None),
+      ("user:", Some("")),
+      ("user:pa:ss", Some("pa:ss")),
+      (":", Some("")),
+    ] {
+      let user_info = (user_info() - end()).parse(s.as_bytes()).success().unwrap();
+      assert_eq!(user_info.password(), password, "{}", s);
+      assert_eq!(user_info.to_string(), s);
+    }
+  }
 }